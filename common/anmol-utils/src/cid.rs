@@ -0,0 +1,390 @@
+//! Parsing of IPFS content identifiers.
+//!
+//! Accepts the text form of a CID, either a CIDv0 (a base58btc sha2-256 multihash starting with
+//! `Qm`) or a multibase encoded CIDv1 (`<version><multicodec><multihash>`), see
+//! <https://github.com/multiformats/cid>. Both are normalized to the binary CIDv1 form, so the
+//! CIDv0 of some content and its CIDv1 `dag-pb` counterpart have the same canonical bytes.
+
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+/// Length of the text form of a CIDv0
+pub const CID_V0_LENGTH: usize = 46;
+
+/// Multicodec of the content of a CIDv0
+pub const DAG_PB: u64 = 0x70;
+/// Multihash code of a CIDv0
+pub const SHA2_256: u64 = 0x12;
+
+/// Content multicodecs accepted in a CIDv1
+const CODECS: &[u64] = &[
+	0x55,   // raw
+	DAG_PB, // dag-pb
+	0x71,   // dag-cbor
+	0x72,   // libp2p-key
+	0x0129, // dag-json
+	0x0200, // json
+];
+
+/// Multihash codes accepted in a CID with the length of their digest
+const HASHES: &[(u64, usize)] = &[
+	(SHA2_256, 32), // sha2-256
+	(0x13, 64),     // sha2-512
+	(0x14, 64),     // sha3-512
+	(0x16, 32),     // sha3-256
+	(0x1b, 32),     // keccak-256
+	(0x1e, 32),     // blake3
+	(0xb220, 32),   // blake2b-256
+];
+
+const BASE58_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const BASE32_ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+/// Reason a CID was rejected
+#[derive(Clone, Copy, Eq, PartialEq, RuntimeDebug)]
+pub enum CidError {
+	/// Input is empty
+	Empty,
+	/// Multibase prefix is not one of base58btc, base32 or base16
+	UnsupportedMultibase,
+	/// Input contains characters outside its base alphabet or non-zero padding bits
+	InvalidEncoding,
+	/// Varint is truncated, not minimal or overflows `u64`
+	InvalidVarint,
+	/// CID version is neither 0 nor 1
+	UnsupportedVersion,
+	/// Content multicodec is not supported
+	UnsupportedCodec,
+	/// Multihash function is not supported
+	UnsupportedHash,
+	/// Digest does not have the length of the multihash function
+	InvalidDigestLength,
+}
+
+/// Version of a CID
+#[derive(Clone, Copy, Eq, PartialEq, RuntimeDebug)]
+pub enum CidVersion {
+	V0,
+	V1,
+}
+
+/// Decoded CID
+#[derive(Clone, Eq, PartialEq, RuntimeDebug)]
+pub struct Cid {
+	/// Version of the text form
+	pub version: CidVersion,
+	/// Multicodec of the content
+	pub codec: u64,
+	/// Multihash function
+	pub hash: u64,
+	/// Hash of the content
+	pub digest: Vec<u8>,
+}
+
+impl Cid {
+	/// Decode the text form of a CID
+	pub fn parse(input: &[u8]) -> Result<Self, CidError> {
+		if input.is_empty() {
+			return Err(CidError::Empty);
+		}
+
+		if input.len() == CID_V0_LENGTH && input.starts_with(b"Qm") {
+			let multihash = decode_base58(input)?;
+			let (hash, digest) = decode_multihash(&multihash)?;
+			if hash != SHA2_256 {
+				return Err(CidError::UnsupportedHash);
+			}
+			return Ok(Cid {
+				version: CidVersion::V0,
+				codec: DAG_PB,
+				hash,
+				digest: digest.to_vec(),
+			});
+		}
+
+		let bytes = match input[0] {
+			b'z' => decode_base58(&input[1..])?,
+			b'b' => decode_base32(&input[1..], false)?,
+			b'B' => decode_base32(&input[1..], true)?,
+			b'f' => decode_base16(&input[1..], false)?,
+			b'F' => decode_base16(&input[1..], true)?,
+			_ => return Err(CidError::UnsupportedMultibase),
+		};
+		Self::from_bytes(&bytes)
+	}
+
+	/// Decode the binary form of a CIDv1
+	pub fn from_bytes(bytes: &[u8]) -> Result<Self, CidError> {
+		let (version, rest) = decode_varint(bytes)?;
+		if version != 1 {
+			return Err(CidError::UnsupportedVersion);
+		}
+		let (codec, multihash) = decode_varint(rest)?;
+		if !CODECS.contains(&codec) {
+			return Err(CidError::UnsupportedCodec);
+		}
+		let (hash, digest) = decode_multihash(multihash)?;
+
+		Ok(Cid {
+			version: CidVersion::V1,
+			codec,
+			hash,
+			digest: digest.to_vec(),
+		})
+	}
+
+	/// Binary CIDv1 form, the same whatever the version and multibase of the text form
+	pub fn to_bytes(&self) -> Vec<u8> {
+		let mut bytes = Vec::with_capacity(self.digest.len() + 8);
+		encode_varint(1, &mut bytes);
+		encode_varint(self.codec, &mut bytes);
+		bytes.extend(self.multihash());
+		bytes
+	}
+
+	/// Multihash of the content, the same whatever the version and codec of the CID
+	pub fn multihash(&self) -> Vec<u8> {
+		let mut bytes = Vec::with_capacity(self.digest.len() + 4);
+		encode_varint(self.hash, &mut bytes);
+		encode_varint(self.digest.len() as u64, &mut bytes);
+		bytes.extend_from_slice(&self.digest);
+		bytes
+	}
+}
+
+/// Canonical binary form of the text form of a CID
+pub fn canonicalize(input: &[u8]) -> Result<Vec<u8>, CidError> {
+	Cid::parse(input).map(|cid| cid.to_bytes())
+}
+
+/// Split a multihash into its function and a digest of the expected length
+fn decode_multihash(bytes: &[u8]) -> Result<(u64, &[u8]), CidError> {
+	let (hash, rest) = decode_varint(bytes)?;
+	let (length, digest) = decode_varint(rest)?;
+	let expected = HASHES
+		.iter()
+		.find(|(code, _)| *code == hash)
+		.map(|(_, length)| *length)
+		.ok_or(CidError::UnsupportedHash)?;
+	if length != expected as u64 || digest.len() != expected {
+		return Err(CidError::InvalidDigestLength);
+	}
+	Ok((hash, digest))
+}
+
+/// Decode an unsigned LEB128 varint, returning it with the remaining bytes
+fn decode_varint(bytes: &[u8]) -> Result<(u64, &[u8]), CidError> {
+	let mut value = 0_u64;
+	// multiformats varints are at most 9 bytes long
+	for (i, byte) in bytes.iter().take(9).enumerate() {
+		value |= u64::from(byte & 0x7f) << (7 * i);
+		if byte & 0x80 == 0 {
+			if *byte == 0 && i > 0 {
+				return Err(CidError::InvalidVarint);
+			}
+			return Ok((value, &bytes[i + 1..]));
+		}
+	}
+	Err(CidError::InvalidVarint)
+}
+
+fn encode_varint(mut value: u64, bytes: &mut Vec<u8>) {
+	while value >= 0x80 {
+		bytes.push(value as u8 | 0x80);
+		value >>= 7;
+	}
+	bytes.push(value as u8);
+}
+
+fn decode_base58(input: &[u8]) -> Result<Vec<u8>, CidError> {
+	// little endian base 256 digits of the decoded number
+	let mut digits: Vec<u8> = Vec::with_capacity(input.len());
+	for c in input {
+		let mut carry = BASE58_ALPHABET
+			.iter()
+			.position(|a| a == c)
+			.ok_or(CidError::InvalidEncoding)? as u32;
+		for digit in digits.iter_mut() {
+			carry += u32::from(*digit) * 58;
+			*digit = carry as u8;
+			carry >>= 8;
+		}
+		while carry > 0 {
+			digits.push(carry as u8);
+			carry >>= 8;
+		}
+	}
+	// every leading '1' encodes a leading zero byte
+	let zeros = input.iter().take_while(|c| **c == b'1').count();
+	let mut bytes = Vec::with_capacity(zeros + digits.len());
+	bytes.resize(zeros, 0);
+	bytes.extend(digits.iter().rev());
+	Ok(bytes)
+}
+
+/// Decode RFC 4648 base32 without padding
+fn decode_base32(input: &[u8], upper: bool) -> Result<Vec<u8>, CidError> {
+	let mut bytes = Vec::with_capacity(input.len() * 5 / 8);
+	let mut buffer = 0_u32;
+	let mut bits = 0;
+	for c in input {
+		if (upper && c.is_ascii_lowercase()) || (!upper && c.is_ascii_uppercase()) {
+			return Err(CidError::InvalidEncoding);
+		}
+		let c = c.to_ascii_lowercase();
+		let value = BASE32_ALPHABET
+			.iter()
+			.position(|a| *a == c)
+			.ok_or(CidError::InvalidEncoding)? as u32;
+		buffer = (buffer << 5) | value;
+		bits += 5;
+		if bits >= 8 {
+			bits -= 8;
+			bytes.push((buffer >> bits) as u8);
+			buffer &= (1 << bits) - 1;
+		}
+	}
+	if bits >= 5 || buffer != 0 {
+		return Err(CidError::InvalidEncoding);
+	}
+	Ok(bytes)
+}
+
+fn decode_base16(input: &[u8], upper: bool) -> Result<Vec<u8>, CidError> {
+	let pairs = input.chunks_exact(2);
+	if !pairs.remainder().is_empty() {
+		return Err(CidError::InvalidEncoding);
+	}
+	let nibble = |c: u8| match c {
+		b'0'..=b'9' => Ok(c - b'0'),
+		b'a'..=b'f' if !upper => Ok(c - b'a' + 10),
+		b'A'..=b'F' if upper => Ok(c - b'A' + 10),
+		_ => Err(CidError::InvalidEncoding),
+	};
+	pairs
+		.map(|pair| Ok(nibble(pair[0])? << 4 | nibble(pair[1])?))
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const V0: &[u8] = b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG";
+	const V1: &[u8] = b"bafybeie5nqv6kd3qnfjupgvz34woh3oksc3iau6abmyajn7qvtf6d2ho34";
+
+	#[test]
+	fn parse_v0_works() {
+		let cid = Cid::parse(V0).unwrap();
+		assert_eq!(cid.version, CidVersion::V0);
+		assert_eq!(cid.codec, DAG_PB);
+		assert_eq!(cid.hash, SHA2_256);
+		assert_eq!(cid.digest.len(), 32);
+	}
+
+	#[test]
+	fn v0_and_v1_have_the_same_canonical_form() {
+		let v1 = Cid::parse(V1).unwrap();
+		assert_eq!(v1.version, CidVersion::V1);
+		assert_eq!(canonicalize(V0), canonicalize(V1));
+		assert_eq!(v1.multihash(), decode_base58(V0).unwrap());
+		assert_eq!(Cid::from_bytes(&v1.to_bytes()), Ok(v1.clone()));
+
+		let upper = V1.to_ascii_uppercase();
+		assert_eq!(canonicalize(&upper), Ok(v1.to_bytes()));
+		let mut base16 = b"f".to_vec();
+		for byte in v1.to_bytes() {
+			base16.extend_from_slice(&[
+				b"0123456789abcdef"[(byte >> 4) as usize],
+				b"0123456789abcdef"[(byte & 0xf) as usize],
+			]);
+		}
+		assert_eq!(canonicalize(&base16), Ok(v1.to_bytes()));
+	}
+
+	#[test]
+	fn parse_base32_v1_works() {
+		let raw =
+			Cid::parse(b"bafkreifzjut3te2nhyekklss27nh3k72ysco7y32koao5eei66wof36n5e").unwrap();
+		assert_eq!(raw.version, CidVersion::V1);
+		assert_eq!(raw.codec, 0x55);
+		assert_eq!(raw.hash, SHA2_256);
+		assert_eq!(raw.digest.len(), 32);
+
+		let cbor = Cid::parse(
+			b"BAFYRGQBQT3GERHAS23VUZRAPKDEQF4VU2DWXP3SRDJ6HVG6NHSUG2TGYN6MJ3U23YX7UTFTQ3I2CKW2FWDH5QMHID5QF3T35YVKC5E5OTTLW6",
+		)
+		.unwrap();
+		assert_eq!(cbor.codec, 0x71);
+		assert_eq!(cbor.hash, 0x13);
+		assert_eq!(cbor.digest.len(), 64);
+		assert_eq!(Cid::from_bytes(&cbor.to_bytes()), Ok(cbor));
+	}
+
+	#[test]
+	fn wrong_multihash_lengths_are_rejected() {
+		let mut short = vec![0x01, 0x55, 0x12, 0x1f];
+		short.extend_from_slice(&[7; 31]);
+		assert_eq!(Cid::from_bytes(&short), Err(CidError::InvalidDigestLength));
+
+		let mut declared_short = vec![0x01, 0x55, 0x12, 0x1f];
+		declared_short.extend_from_slice(&[7; 32]);
+		assert_eq!(
+			Cid::from_bytes(&declared_short),
+			Err(CidError::InvalidDigestLength)
+		);
+
+		let mut long = Cid::parse(V1).unwrap().to_bytes();
+		long.push(7);
+		assert_eq!(Cid::from_bytes(&long), Err(CidError::InvalidDigestLength));
+	}
+
+	#[test]
+	fn oversized_cids_are_rejected() {
+		let mut v0 = V0.to_vec();
+		v0.push(b'a');
+		assert_eq!(Cid::parse(&v0), Err(CidError::UnsupportedMultibase));
+
+		let mut v1 = V1.to_vec();
+		v1.extend_from_slice(b"aaaaaaaa");
+		assert_eq!(Cid::parse(&v1), Err(CidError::InvalidDigestLength));
+
+		let mut huge = b"b".to_vec();
+		huge.resize(16 * 1024, b'a');
+		assert_eq!(Cid::parse(&huge), Err(CidError::UnsupportedVersion));
+	}
+
+	#[test]
+	fn malformed_cids_are_rejected() {
+		assert_eq!(Cid::parse(b""), Err(CidError::Empty));
+		assert_eq!(Cid::parse(&[0, 1, 2]), Err(CidError::UnsupportedMultibase));
+		assert_eq!(
+			Cid::parse(b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbd0"),
+			Err(CidError::InvalidEncoding)
+		);
+		assert_eq!(
+			Cid::parse(b"bafybeie5nqv6kd3qnfjupgvz34woh3oksc3iau6abmyajn7qvtf6d2ho3A"),
+			Err(CidError::InvalidEncoding)
+		);
+		let mut truncated = Cid::parse(V1).unwrap().to_bytes();
+		truncated.pop();
+		assert_eq!(
+			Cid::from_bytes(&truncated),
+			Err(CidError::InvalidDigestLength)
+		);
+		assert_eq!(
+			Cid::from_bytes(&[0x02, 0x70]),
+			Err(CidError::UnsupportedVersion)
+		);
+		assert_eq!(
+			Cid::from_bytes(&[0x01, 0x01]),
+			Err(CidError::UnsupportedCodec)
+		);
+		assert_eq!(
+			Cid::from_bytes(&[0x01, 0x55, 0x00, 0x00]),
+			Err(CidError::UnsupportedHash)
+		);
+		assert_eq!(Cid::from_bytes(&[0x01, 0xf0]), Err(CidError::InvalidVarint));
+		assert_eq!(Cid::from_bytes(&[0x81, 0x00]), Err(CidError::InvalidVarint));
+	}
+}
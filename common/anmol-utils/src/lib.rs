@@ -2,6 +2,10 @@
 
 use sp_std::vec::Vec;
 
+pub mod cid;
+pub mod metadata;
+pub mod nonfungibles;
+
 pub fn remove_vector_item<'a, T: Ord>(vector: &'a mut Vec<T>, item: &T) -> Result<T, &'static str> {
 	match vector.binary_search(item) {
 		Ok(index) => Ok(vector.remove(index)),
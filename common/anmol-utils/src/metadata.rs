@@ -0,0 +1,209 @@
+//! Anmol metadata standard of NFT classes and tokens.
+//!
+//! Metadata is the JSON document a token CID points to. The standard follows the ERC-721 and
+//! ERC-1155 metadata JSON schemas, so a document valid for Anmol is valid for both:
+//!
+//! ```json
+//! {
+//!     "name": "Asset name",
+//!     "description": "Optional description",
+//!     "image": "ipfs://<cid>",
+//!     "animation_url": "ipfs://<cid>",
+//!     "attributes": [{ "trait_type": "Color", "value": "red" }]
+//! }
+//! ```
+//!
+//! - `name` is a non-empty string
+//! - `image` is an optional URI, e.g. `ipfs://`, `https://` or `data:`
+//! - `description` is an optional string
+//! - `animation_url` is an optional URI
+//! - `attributes` is an optional array of objects, each with a string, number or boolean `value`
+//!   and optional string `trait_type` and `display_type`
+//!
+//! Other fields, e.g. the ERC-1155 `properties` or `decimals`, are allowed and ignored. Validation
+//! parses a whole JSON document, it is meant for offchain workers and clients, not for
+//! dispatchables.
+
+use lite_json::{parse_json, JsonValue};
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+/// Reason metadata does not follow the standard
+#[derive(Clone, Copy, Eq, PartialEq, RuntimeDebug)]
+pub enum MetadataError {
+	/// Document is not UTF-8 encoded JSON
+	NotJson,
+	/// Document is not a JSON object
+	NotAnObject,
+	/// `name` is missing, not a string or empty
+	InvalidName,
+	/// `description` is not a string
+	InvalidDescription,
+	/// `image` is not a URI
+	InvalidImage,
+	/// `animation_url` is not a URI
+	InvalidAnimationUrl,
+	/// `attributes` is not an array of objects with a valid `value`, `trait_type` and
+	/// `display_type`
+	InvalidAttributes,
+}
+
+/// Check that `document` follows the Anmol metadata standard
+pub fn validate(document: &[u8]) -> Result<(), MetadataError> {
+	let document = sp_std::str::from_utf8(document).map_err(|_| MetadataError::NotJson)?;
+	let json = parse_json(document).map_err(|_| MetadataError::NotJson)?;
+	validate_json(&json)
+}
+
+/// Check that a parsed document follows the Anmol metadata standard
+pub fn validate_json(json: &JsonValue) -> Result<(), MetadataError> {
+	let fields = match json {
+		JsonValue::Object(fields) => fields,
+		_ => return Err(MetadataError::NotAnObject),
+	};
+
+	match field(fields, "name") {
+		Some(JsonValue::String(name)) if !name.is_empty() => {}
+		_ => return Err(MetadataError::InvalidName),
+	}
+	match field(fields, "description") {
+		None | Some(JsonValue::String(_)) => {}
+		_ => return Err(MetadataError::InvalidDescription),
+	}
+	match field(fields, "image") {
+		None => {}
+		Some(JsonValue::String(image)) if is_uri(image) => {}
+		_ => return Err(MetadataError::InvalidImage),
+	}
+	match field(fields, "animation_url") {
+		None => {}
+		Some(JsonValue::String(url)) if is_uri(url) => {}
+		_ => return Err(MetadataError::InvalidAnimationUrl),
+	}
+	match field(fields, "attributes") {
+		None => {}
+		Some(JsonValue::Array(attributes)) if attributes.iter().all(is_attribute) => {}
+		_ => return Err(MetadataError::InvalidAttributes),
+	}
+
+	Ok(())
+}
+
+/// Value of the first field of an object named `name`
+fn field<'a>(fields: &'a [(Vec<char>, JsonValue)], name: &str) -> Option<&'a JsonValue> {
+	fields
+		.iter()
+		.find(|(key, _)| key.iter().copied().eq(name.chars()))
+		.map(|(_, value)| value)
+}
+
+/// Whether `value` is an object with a string, number or boolean `value` and optional string
+/// `trait_type` and `display_type`
+fn is_attribute(value: &JsonValue) -> bool {
+	let fields = match value {
+		JsonValue::Object(fields) => fields,
+		_ => return false,
+	};
+	let is_optional_string =
+		|name| matches!(field(fields, name), None | Some(JsonValue::String(_)));
+
+	matches!(
+		field(fields, "value"),
+		Some(JsonValue::String(_)) | Some(JsonValue::Number(_)) | Some(JsonValue::Boolean(_))
+	) && is_optional_string("trait_type")
+		&& is_optional_string("display_type")
+}
+
+/// Whether `value` is `<scheme>:<rest>` with an RFC 3986 scheme and a non-empty rest
+fn is_uri(value: &[char]) -> bool {
+	match value.iter().position(|c| *c == ':') {
+		Some(colon) if colon + 1 < value.len() => {
+			value[0].is_ascii_alphabetic()
+				&& value[1..colon]
+					.iter()
+					.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+		}
+		_ => false,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn erc721_and_erc1155_metadata_is_valid() {
+		assert_eq!(validate(br#"{"name": "Anmol"}"#), Ok(()));
+		assert_eq!(
+			validate(br#"{"name": "Anmol", "image": "ipfs://QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"}"#),
+			Ok(())
+		);
+		assert_eq!(
+			validate(
+				br#"{
+					"name": "Anmol",
+					"description": "Sunrise",
+					"image": "https://anmol.network/sunrise.png",
+					"animation_url": "ar://sunrise",
+					"attributes": [
+						{"trait_type": "Color", "value": "red"},
+						{"display_type": "number", "trait_type": "Edition", "value": 2},
+						{"value": true}
+					],
+					"decimals": 0,
+					"properties": {"artist": "DotMatrix"}
+				}"#
+			),
+			Ok(())
+		);
+	}
+
+	#[test]
+	fn nonstandard_metadata_is_rejected() {
+		assert_eq!(validate(&[0xff]), Err(MetadataError::NotJson));
+		assert_eq!(validate(b"{\"name\": "), Err(MetadataError::NotJson));
+		assert_eq!(validate(b"[]"), Err(MetadataError::NotAnObject));
+		assert_eq!(
+			validate(br#"{"image": "ipfs://a"}"#),
+			Err(MetadataError::InvalidName)
+		);
+		assert_eq!(
+			validate(br#"{"name": "", "image": "ipfs://a"}"#),
+			Err(MetadataError::InvalidName)
+		);
+		assert_eq!(
+			validate(br#"{"name": "Anmol", "description": 1, "image": "ipfs://a"}"#),
+			Err(MetadataError::InvalidDescription)
+		);
+		assert_eq!(
+			validate(br#"{"name": "Anmol", "image": 1}"#),
+			Err(MetadataError::InvalidImage)
+		);
+		assert_eq!(
+			validate(br#"{"name": "Anmol", "image": "sunrise.png"}"#),
+			Err(MetadataError::InvalidImage)
+		);
+		assert_eq!(
+			validate(br#"{"name": "Anmol", "image": "ipfs:"}"#),
+			Err(MetadataError::InvalidImage)
+		);
+		assert_eq!(
+			validate(br#"{"name": "Anmol", "image": "ipfs://a", "animation_url": "1a:b"}"#),
+			Err(MetadataError::InvalidAnimationUrl)
+		);
+		assert_eq!(
+			validate(br#"{"name": "Anmol", "image": "ipfs://a", "attributes": {}}"#),
+			Err(MetadataError::InvalidAttributes)
+		);
+		assert_eq!(
+			validate(br#"{"name": "Anmol", "image": "ipfs://a", "attributes": [{"trait_type": "Color"}]}"#),
+			Err(MetadataError::InvalidAttributes)
+		);
+		assert_eq!(
+			validate(
+				br#"{"name": "Anmol", "image": "ipfs://a", "attributes": [{"trait_type": 1, "value": 1}]}"#
+			),
+			Err(MetadataError::InvalidAttributes)
+		);
+	}
+}
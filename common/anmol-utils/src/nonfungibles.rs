@@ -0,0 +1,134 @@
+//! Traits for fractionally owned non-fungible tokens.
+//!
+//! Modelled on the `frame_support::traits::tokens::nonfungibles` family of later Substrate
+//! releases. An instance is split into `Inspect::total_shares` shares, every account holding
+//! at least one share is an owner, so ownership queries and transfers are expressed in shares.
+
+use sp_runtime::{DispatchError, DispatchResult};
+use sp_std::{boxed::Box, vec::Vec};
+
+/// Read the state of classes and instances.
+pub trait Inspect<AccountId> {
+	/// Type identifying a class
+	type ClassId;
+	/// Type identifying an instance within a class
+	type InstanceId;
+	/// Type accounting the fractional ownership of an instance
+	type Shares;
+
+	/// Shares every instance is split into
+	fn total_shares() -> Self::Shares;
+
+	/// Accounts holding shares of `instance`, empty if it does not exist
+	fn owners(class: &Self::ClassId, instance: &Self::InstanceId) -> Vec<AccountId>;
+
+	/// Shares of `instance` held by `who`
+	fn shares_of(
+		class: &Self::ClassId,
+		instance: &Self::InstanceId,
+		who: &AccountId,
+	) -> Self::Shares;
+
+	/// Owner of `class`
+	fn class_owner(class: &Self::ClassId) -> Option<AccountId>;
+
+	/// Metadata of `class`
+	fn class_metadata(class: &Self::ClassId) -> Option<Vec<u8>>;
+
+	/// Metadata of `instance`
+	fn metadata(class: &Self::ClassId, instance: &Self::InstanceId) -> Option<Vec<u8>>;
+
+	/// Whether shares of `instance` can currently be transferred
+	fn can_transfer(class: &Self::ClassId, instance: &Self::InstanceId) -> bool {
+		let _ = (class, instance);
+		true
+	}
+}
+
+/// Class, instance and shares of an instance held by an account
+pub type OwnedInstanceOf<T, AccountId> = (
+	<T as Inspect<AccountId>>::ClassId,
+	<T as Inspect<AccountId>>::InstanceId,
+	<T as Inspect<AccountId>>::Shares,
+);
+
+/// Enumerate classes and instances.
+pub trait InspectEnumerable<AccountId>: Inspect<AccountId> {
+	/// All classes
+	fn classes() -> Box<dyn Iterator<Item = Self::ClassId>>;
+
+	/// All instances of `class`
+	fn instances(class: &Self::ClassId) -> Box<dyn Iterator<Item = Self::InstanceId>>;
+
+	/// Instances `who` holds shares of, with the shares
+	fn owned(who: &AccountId) -> Box<dyn Iterator<Item = OwnedInstanceOf<Self, AccountId>>>;
+
+	/// Instances of `class` `who` holds shares of, with the shares
+	fn owned_in_class(
+		class: &Self::ClassId,
+		who: &AccountId,
+	) -> Box<dyn Iterator<Item = (Self::InstanceId, Self::Shares)>>;
+}
+
+/// Create classes, the implementation assigns the identifier.
+pub trait Create<AccountId>: Inspect<AccountId> {
+	/// Create a class owned by `owner`
+	fn create_class(owner: &AccountId, metadata: Vec<u8>) -> Result<Self::ClassId, DispatchError>;
+}
+
+/// Mint and burn instances.
+pub trait Mutate<AccountId>: Inspect<AccountId> {
+	/// Mint an instance of `class`, every share of it goes to `who`
+	fn mint_into(
+		class: &Self::ClassId,
+		who: &AccountId,
+		metadata: Vec<u8>,
+	) -> Result<Self::InstanceId, DispatchError>;
+
+	/// Burn `instance` on behalf of `approvers`
+	///
+	/// The approvers must together hold enough shares, as defined by the implementation, for
+	/// the burn to go through. A sole owner approves alone.
+	fn burn_from(
+		class: &Self::ClassId,
+		instance: &Self::InstanceId,
+		approvers: &[AccountId],
+	) -> DispatchResult;
+
+	/// Replace the metadata of `instance`, `who` must hold every share of it
+	fn set_metadata(
+		class: &Self::ClassId,
+		instance: &Self::InstanceId,
+		who: &AccountId,
+		metadata: Vec<u8>,
+	) -> DispatchResult;
+}
+
+/// Move shares of instances between accounts.
+pub trait Transfer<AccountId>: Inspect<AccountId> {
+	/// Transfer `shares` of `instance` from `source` to `destination`
+	fn transfer(
+		class: &Self::ClassId,
+		instance: &Self::InstanceId,
+		source: &AccountId,
+		destination: &AccountId,
+		shares: Self::Shares,
+	) -> DispatchResult;
+
+	/// Transfer every share of `instance` held by `source` to `destination`
+	fn transfer_all(
+		class: &Self::ClassId,
+		instance: &Self::InstanceId,
+		source: &AccountId,
+		destination: &AccountId,
+	) -> DispatchResult {
+		let shares = Self::shares_of(class, instance, source);
+		Self::transfer(class, instance, source, destination, shares)
+	}
+}
+
+/// Destroy classes.
+pub trait Destroy<AccountId>: Inspect<AccountId> {
+	/// Destroy `class` owned by `owner`, it must have no instances left
+	fn destroy(class: &Self::ClassId, owner: &AccountId) -> DispatchResult;
+}
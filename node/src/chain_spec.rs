@@ -1,5 +1,6 @@
 use anmol_runtime::{
-	AccountId, AuraConfig, BalancesConfig, BaseNftConfig, GenesisConfig, GrandpaConfig, Signature,
+	pallet_nft::crypto::Public as NftOracleId, AccountId, AuraConfig, BalancesConfig,
+	BaseNftConfig, DividendsConfig, GenesisConfig, GrandpaConfig, NftModuleConfig, Signature,
 	SudoConfig, SystemConfig, WASM_BINARY,
 };
 use sc_service::{ChainType, Properties};
@@ -31,9 +32,13 @@ where
 	AccountPublic::from(get_from_seed::<TPublic>(seed)).into_account()
 }
 
-/// Generate an Aura authority key.
-pub fn authority_keys_from_seed(s: &str) -> (AuraId, GrandpaId) {
-	(get_from_seed::<AuraId>(s), get_from_seed::<GrandpaId>(s))
+/// Generate the Aura, Grandpa and NFT oracle keys of an authority.
+pub fn authority_keys_from_seed(s: &str) -> (AuraId, GrandpaId, NftOracleId) {
+	(
+		get_from_seed::<AuraId>(s),
+		get_from_seed::<GrandpaId>(s),
+		get_from_seed::<NftOracleId>(s),
+	)
 }
 
 pub fn chain_properties() -> Properties {
@@ -136,12 +141,12 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 /// Configure initial storage state for FRAME modules.
 fn testnet_genesis(
 	wasm_binary: &[u8],
-	initial_authorities: Vec<(AuraId, GrandpaId)>,
+	initial_authorities: Vec<(AuraId, GrandpaId, NftOracleId)>,
 	root_key: AccountId,
 	endowed_accounts: Vec<AccountId>,
 	_enable_println: bool,
 ) -> GenesisConfig {
-	// base nft class for genesis block
+	// public IPFS nft class for genesis block, see `PublicIpfsClassId` in the runtime
 	let initial_state = vec![(
 		get_account_id_from_seed::<sr25519::Public>("Alice"),
 		[0].to_vec(),
@@ -179,5 +184,14 @@ fn testnet_genesis(
 		base_nft: Some(BaseNftConfig {
 			tokens: initial_state,
 		}),
+		pallet_nft: Some(NftModuleConfig {
+			// Authorities attest NFT metadata with their `nfto` key, sudo manages the oracles.
+			oracles: initial_authorities
+				.iter()
+				.map(|x| AccountPublic::from(sr25519::Public::from(x.2.clone())).into_account())
+				.collect(),
+		}),
+		// Endows the account holding unclaimed revenue.
+		pallet_dividends: Some(DividendsConfig::default()),
 	}
 }
@@ -7,7 +7,7 @@
 
 use std::sync::Arc;
 
-use anmol_runtime::{opaque::Block, AccountId, Balance, Index};
+use anmol_runtime::{opaque::Block, AccountId, Balance, ClassId, Index, Shares, TokenId};
 pub use sc_rpc_api::DenyUnsafe;
 use sp_api::ProvideRuntimeApi;
 use sp_block_builder::BlockBuilder;
@@ -32,9 +32,11 @@ where
 	C: Send + Sync + 'static,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+	C::Api: pallet_nft_rpc::NftRuntimeApi<Block, AccountId, ClassId, TokenId, Shares, Balance>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + 'static,
 {
+	use pallet_nft_rpc::{Nft, NftApi};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApi};
 	use substrate_frame_rpc_system::{FullSystem, SystemApi};
 
@@ -55,10 +57,7 @@ where
 		client.clone(),
 	)));
 
-	// Extend this RPC with a custom API by using the following syntax.
-	// `YourRpcStruct` should have a reference to a client, which is needed
-	// to call into the runtime.
-	// `io.extend_with(YourRpcTrait::to_delegate(YourRpcStruct::new(ReferenceToClient, ...)));`
+	io.extend_with(NftApi::to_delegate(Nft::new(client.clone())));
 
 	io
 }
@@ -0,0 +1,193 @@
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::traits::OnInitialize;
+use frame_system::RawOrigin;
+use sp_runtime::traits::Bounded;
+use sp_std::{boxed::Box, vec, vec::Vec};
+
+fn funded_account<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+	let who: T::AccountId = account(name, index, 0);
+	<T as base_nft::Config>::Currency::make_free_balance_be(
+		&who,
+		BalanceOf::<T>::max_value() / 2_u32.into(),
+	);
+	who
+}
+
+/// Token auctioned by a funded seller, with a royalty paid to `r` funded beneficiaries
+fn auctioned_token<T: Config>(
+	kind: AuctionKind<BalanceOf<T>>,
+	r: u32,
+) -> (T::AccountId, (T::ClassId, T::TokenId)) {
+	let seller = funded_account::<T>("seller", 0);
+	let class_id = BaseNft::<T>::create_class(&seller, vec![1_u8], Default::default()).unwrap();
+	let token_id = BaseNft::<T>::mint(&seller, class_id, vec![1_u8], Default::default()).unwrap();
+	let token = (class_id, token_id);
+
+	let rate = Perbill::from_parts(
+		T::MaxRoyalty::get().deconstruct() / T::MaxRoyaltyBeneficiaries::get().max(1),
+	);
+	let royalty = (0..r)
+		.map(|i| (funded_account::<T>("beneficiary", i), rate))
+		.collect();
+	BaseNft::<T>::set_class_royalty(&seller, class_id, royalty).unwrap();
+
+	Pallet::<T>::create_auction(
+		RawOrigin::Signed(seller.clone()).into(),
+		token,
+		kind,
+		T::MinimumDuration::get(),
+	)
+	.unwrap();
+	(seller, token)
+}
+
+/// Sealed bid of `amount` committed by a funded bidder, returns the bidder and the salt
+fn committed_bid<T: Config>(
+	token: (T::ClassId, T::TokenId),
+	index: u32,
+	amount: BalanceOf<T>,
+) -> (T::AccountId, [u8; 32]) {
+	let bidder = funded_account::<T>("bidder", index);
+	let salt = [index as u8; 32];
+	Pallet::<T>::commit_bid(
+		RawOrigin::Signed(bidder.clone()).into(),
+		token,
+		Pallet::<T>::commitment(amount, &salt),
+		amount,
+	)
+	.unwrap();
+	(bidder, salt)
+}
+
+fn sealed<T: Config>() -> AuctionKind<BalanceOf<T>> {
+	AuctionKind::Sealed {
+		reserve_price: 100_u32.into(),
+	}
+}
+
+fn english<T: Config>() -> AuctionKind<BalanceOf<T>> {
+	AuctionKind::English {
+		reserve_price: 100_u32.into(),
+	}
+}
+
+benchmarks! {
+	create_auction {
+		let seller = funded_account::<T>("seller", 0);
+		let class_id = BaseNft::<T>::create_class(&seller, vec![1_u8], Default::default())?;
+		let token_id = BaseNft::<T>::mint(&seller, class_id, vec![1_u8], Default::default())?;
+		let duration = T::MinimumDuration::get();
+	}: _(RawOrigin::Signed(seller), (class_id, token_id), english::<T>(), duration)
+
+	bid {
+		let (_, token) = auctioned_token::<T>(english::<T>(), 0);
+		let outbid = funded_account::<T>("bidder", 1);
+		Pallet::<T>::bid(RawOrigin::Signed(outbid).into(), token, 100_u32.into())?;
+
+		let caller: T::AccountId = whitelisted_caller();
+		<T as base_nft::Config>::Currency::make_free_balance_be(
+			&caller,
+			BalanceOf::<T>::max_value() / 2_u32.into(),
+		);
+	}: _(RawOrigin::Signed(caller), token, 1_000_u32.into())
+
+	buy {
+		let r in 0 .. T::MaxRoyaltyBeneficiaries::get();
+
+		let (_, token) = auctioned_token::<T>(
+			AuctionKind::Dutch {
+				start_price: 1_000_000_u32.into(),
+				end_price: 100_000_u32.into(),
+			},
+			r,
+		);
+
+		let caller: T::AccountId = whitelisted_caller();
+		<T as base_nft::Config>::Currency::make_free_balance_be(
+			&caller,
+			BalanceOf::<T>::max_value() / 2_u32.into(),
+		);
+	}: _(RawOrigin::Signed(caller), token, 1_000_000_u32.into())
+
+	cancel_auction {
+		let (seller, token) = auctioned_token::<T>(english::<T>(), 0);
+	}: _(RawOrigin::Signed(seller), token)
+
+	settle {
+		let r in 0 .. T::MaxRoyaltyBeneficiaries::get();
+
+		let (_, token) = auctioned_token::<T>(english::<T>(), r);
+		let bidder = funded_account::<T>("bidder", 1);
+		Pallet::<T>::bid(RawOrigin::Signed(bidder.clone()).into(), token, 1_000_000_u32.into())?;
+		let end = Pallet::<T>::auctions(token.0, token.1).unwrap().end;
+	}: {
+		<Pallet<T> as OnInitialize<T::BlockNumber>>::on_initialize(end);
+	}
+	verify {
+		assert_eq!(BaseNft::<T>::shares_of(&bidder, token), T::SharesPerToken::get());
+	}
+
+	commit_bid {
+		let (_, token) = auctioned_token::<T>(sealed::<T>(), 0);
+
+		let caller: T::AccountId = whitelisted_caller();
+		<T as base_nft::Config>::Currency::make_free_balance_be(
+			&caller,
+			BalanceOf::<T>::max_value() / 2_u32.into(),
+		);
+		let commitment = Pallet::<T>::commitment(1_000_u32.into(), &[0; 32]);
+	}: _(RawOrigin::Signed(caller), token, commitment, 1_000_u32.into())
+
+	reveal_bid {
+		let (_, token) = auctioned_token::<T>(sealed::<T>(), 0);
+		let (outbid, outbid_salt) = committed_bid::<T>(token, 1, 100_u32.into());
+
+		let caller: T::AccountId = whitelisted_caller();
+		<T as base_nft::Config>::Currency::make_free_balance_be(
+			&caller,
+			BalanceOf::<T>::max_value() / 2_u32.into(),
+		);
+		let salt = [0; 32];
+		Pallet::<T>::commit_bid(
+			RawOrigin::Signed(caller.clone()).into(),
+			token,
+			Pallet::<T>::commitment(1_000_u32.into(), &salt),
+			2_000_u32.into(),
+		)?;
+
+		let end = Pallet::<T>::auctions(token.0, token.1).unwrap().end;
+		frame_system::Pallet::<T>::set_block_number(end);
+		Pallet::<T>::reveal_bid(RawOrigin::Signed(outbid).into(), token, 100_u32.into(), outbid_salt)?;
+	}: _(RawOrigin::Signed(caller), token, 1_000_u32.into(), salt)
+
+	settle_sealed {
+		let b in 0 .. T::MaxSealedBids::get() - 1;
+		let r in 0 .. T::MaxRoyaltyBeneficiaries::get();
+
+		let (_, token) = auctioned_token::<T>(sealed::<T>(), r);
+		for i in 0 .. b {
+			committed_bid::<T>(token, i + 2, 100_u32.into());
+		}
+		let (bidder, salt) = committed_bid::<T>(token, 1, 1_000_000_u32.into());
+
+		let end = Pallet::<T>::auctions(token.0, token.1).unwrap().end;
+		frame_system::Pallet::<T>::set_block_number(end);
+		Pallet::<T>::reveal_bid(
+			RawOrigin::Signed(bidder.clone()).into(),
+			token,
+			1_000_000_u32.into(),
+			salt,
+		)?;
+		let settlement = end + T::RevealPeriod::get();
+	}: {
+		<Pallet<T> as OnInitialize<T::BlockNumber>>::on_initialize(settlement);
+	}
+	verify {
+		assert_eq!(BaseNft::<T>::shares_of(&bidder, token), T::SharesPerToken::get());
+	}
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Runtime);
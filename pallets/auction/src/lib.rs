@@ -0,0 +1,683 @@
+//! # Auction
+//! English, Dutch and sealed-bid auctions of `base_nft` tokens.
+//!
+//! An auction sells every share of a token and is keyed by `(ClassId, TokenId)`. The token is
+//! locked with `base_nft::Pallet::lock` while the auction is live.
+//!
+//! - English auctions reserve each bid and refund the previous highest bidder. A bid placed
+//!   within `Config::AntiSnipingPeriod` of the end extends the auction.
+//! - Dutch auctions sell to the first buyer at a price decaying linearly from the start price
+//!   to the end price.
+//! - Sealed-bid auctions take `BlakeTwo256` commitments of `(amount, salt)` with a reserved
+//!   deposit until the end, then reveals for `Config::RevealPeriod`. The highest revealed bid
+//!   wins and deposits of unrevealed bids are slashed to `Config::Slash`.
+//!
+//! `Config::TradeFilter` keeps tokens out of auctions and bids, e.g. tokens of classes requiring
+//! strict metadata until their metadata is verified. An auction of a token filtered out after
+//! its creation is still settled with the bids placed before, or cancelled without bids.
+//!
+//! Royalties of the token when the auction was created are paid out of the winning price, the
+//! seller receives the rest or claims it with `base_nft::Pallet::claim_proceeds` when it cannot
+//! be paid.
+//!
+//! Auctions are settled in `on_initialize` of the block they end at, or at the end of the reveal
+//! period for sealed-bid auctions. At most `Config::MaxAuctionsPerBlock` auctions are settled in
+//! a block, creating an auction ending in a full block fails and a late bid does not extend an
+//! auction into a full block.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use base_nft::{BalanceOf, Module as BaseNft, OnTokenChange};
+use codec::{Decode, Encode};
+use frame_support::{
+	debug,
+	dispatch::DispatchResultWithPostInfo,
+	pallet_prelude::*,
+	traits::{Currency, ExistenceRequirement, Filter, OnUnbalanced, ReservableCurrency},
+	transactional,
+};
+use frame_system::pallet_prelude::*;
+use sp_core::H256;
+use sp_runtime::{
+	traits::{BlakeTwo256, Hash, One, SaturatedConversion, Saturating},
+	DispatchResult, Perbill, RuntimeDebug,
+};
+use sp_std::vec::Vec;
+
+pub use pallet::*;
+
+mod mock;
+mod tests;
+
+mod benchmarking;
+pub mod weights;
+pub use weights::WeightInfo;
+
+/// Auction format
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
+pub enum AuctionKind<Balance> {
+	/// Ascending bids, the highest bid at the end wins
+	English {
+		/// Lowest accepted bid
+		reserve_price: Balance,
+	},
+	/// Price decays linearly over the auction, the first buyer wins
+	Dutch {
+		/// Price at the start of the auction
+		start_price: Balance,
+		/// Price at the end of the auction
+		end_price: Balance,
+	},
+	/// Committed bids revealed after the end, the highest revealed bid wins
+	Sealed {
+		/// Lowest accepted bid and deposit
+		reserve_price: Balance,
+	},
+}
+
+/// Auction info
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
+pub struct AuctionInfo<AccountId, Balance, BlockNumber> {
+	/// Owner of the auctioned token
+	pub seller: AccountId,
+	/// Auction format
+	pub kind: AuctionKind<Balance>,
+	/// Block the auction started at
+	pub start: BlockNumber,
+	/// Block the auction ends at, sealed-bid auctions are settled `RevealPeriod` later
+	pub end: BlockNumber,
+	/// Highest bid of an English auction or highest revealed bid, reserved from the bidder
+	pub bid: Option<(AccountId, Balance)>,
+	/// Royalty of the token when the auction was created, paid on the sale
+	pub royalty: Vec<(AccountId, Perbill)>,
+}
+
+pub type AuctionInfoOf<T> = AuctionInfo<
+	<T as frame_system::Config>::AccountId,
+	BalanceOf<T>,
+	<T as frame_system::Config>::BlockNumber,
+>;
+
+/// Commitment of a sealed bid
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
+pub struct SealedBid<Balance> {
+	/// `BlakeTwo256` hash of the encoded `(amount, salt)`
+	pub commitment: H256,
+	/// Reserved deposit, the revealed amount must not exceed it
+	pub deposit: Balance,
+}
+
+pub type SealedBidOf<T> = SealedBid<BalanceOf<T>>;
+
+type NegativeImbalanceOf<T> = <<T as base_nft::Config>::Currency as Currency<
+	<T as frame_system::Config>::AccountId,
+>>::NegativeImbalance;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + base_nft::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		type WeightInfo: WeightInfo;
+		/// Shortest auction
+		#[pallet::constant]
+		type MinimumDuration: Get<Self::BlockNumber>;
+		/// Longest auction
+		#[pallet::constant]
+		type MaximumDuration: Get<Self::BlockNumber>;
+		/// A bid placed this close to the end moves the end to this period after the bid
+		#[pallet::constant]
+		type AntiSnipingPeriod: Get<Self::BlockNumber>;
+		/// Minimum raise of a bid over the highest bid
+		#[pallet::constant]
+		type MinimumBidIncrement: Get<Perbill>;
+		/// Blocks after the end of a sealed-bid auction to reveal bids in
+		#[pallet::constant]
+		type RevealPeriod: Get<Self::BlockNumber>;
+		/// Maximum sealed bids committed in an auction
+		#[pallet::constant]
+		type MaxSealedBids: Get<u32>;
+		/// Maximum auctions settled in a block
+		#[pallet::constant]
+		type MaxAuctionsPerBlock: Get<u32>;
+		/// Destination of the slashed deposits of unrevealed bids
+		type Slash: OnUnbalanced<NegativeImbalanceOf<Self>>;
+		/// Tokens that may be auctioned and bid on
+		type TradeFilter: Filter<(Self::ClassId, Self::TokenId)>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	/// Live auctions.
+	#[pallet::storage]
+	#[pallet::getter(fn auctions)]
+	pub type Auctions<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::ClassId, Twox64Concat, T::TokenId, AuctionInfoOf<T>>;
+
+	/// Auctions by the block they are settled at.
+	#[pallet::storage]
+	#[pallet::getter(fn auction_end_time)]
+	pub type AuctionEndTime<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		Twox64Concat,
+		(T::ClassId, T::TokenId),
+		(),
+	>;
+
+	/// Number of auctions settled at a block, at most `MaxAuctionsPerBlock`.
+	#[pallet::storage]
+	#[pallet::getter(fn auction_end_count)]
+	pub type AuctionEndCount<T: Config> =
+		StorageMap<_, Twox64Concat, T::BlockNumber, u32, ValueQuery>;
+
+	/// Unrevealed sealed bids of an auction by bidder.
+	#[pallet::storage]
+	#[pallet::getter(fn sealed_bids)]
+	pub type SealedBids<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		(T::ClassId, T::TokenId),
+		Twox64Concat,
+		T::AccountId,
+		SealedBidOf<T>,
+	>;
+
+	/// Number of sealed bids committed in an auction.
+	#[pallet::storage]
+	#[pallet::getter(fn sealed_bid_count)]
+	pub type SealedBidCount<T: Config> =
+		StorageMap<_, Twox64Concat, (T::ClassId, T::TokenId), u32, ValueQuery>;
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Duration is out of the `MinimumDuration` and `MaximumDuration` bounds
+		InvalidDuration,
+		/// Dutch auction price must not increase
+		InvalidPrice,
+		/// Only the holder of every share of a token may auction it
+		NotWholeTokenOwner,
+		/// Token is not auctioned
+		AuctionNotFound,
+		/// Operation requires the auction seller
+		NotSeller,
+		/// Sellers cannot bid in their own auction
+		BidderIsSeller,
+		/// Bids are only placed in English auctions
+		NotEnglishAuction,
+		/// Only Dutch auctions are bought at their current price
+		NotDutchAuction,
+		/// Bid is below the reserve price or the minimum raise over the highest bid
+		BidTooLow,
+		/// Current price is above the price the buyer accepts
+		PriceTooHigh,
+		/// Auctions with bids cannot be cancelled
+		AuctionHasBids,
+		/// Sealed bids are only committed in sealed-bid auctions
+		NotSealedAuction,
+		/// Sealed bids are committed before the end of the auction
+		NotCommitPhase,
+		/// Sealed bids are revealed between the end of the auction and the settlement
+		NotRevealPhase,
+		/// Bidder already committed a bid in the auction
+		AlreadyCommitted,
+		/// Auction reached `MaxSealedBids`
+		TooManyBids,
+		/// Bidder has no unrevealed bid in the auction
+		CommitmentNotFound,
+		/// Amount and salt do not match the commitment
+		InvalidReveal,
+		/// Revealed amount is above the deposit
+		BidExceedsDeposit,
+		/// `MaxAuctionsPerBlock` auctions are already settled at the block the auction ends at
+		TooManyAuctionsEnding,
+		/// Token is not allowed to trade by `TradeFilter`
+		TokenInactive,
+	}
+
+	#[pallet::event]
+	#[pallet::metadata(
+		T::AccountId = "AccountId",
+		BalanceOf<T> = "Balance",
+		T::BlockNumber = "BlockNumber"
+	)]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Auction of a token started. \[seller, token, end\]
+		AuctionCreated(T::AccountId, (T::ClassId, T::TokenId), T::BlockNumber),
+		/// Bid was placed in an English auction. \[bidder, token, amount\]
+		BidPlaced(T::AccountId, (T::ClassId, T::TokenId), BalanceOf<T>),
+		/// Late bid extended an auction. \[token, end\]
+		AuctionExtended((T::ClassId, T::TokenId), T::BlockNumber),
+		/// Token was sold. \[seller, buyer, token, price\]
+		AuctionSold(
+			T::AccountId,
+			T::AccountId,
+			(T::ClassId, T::TokenId),
+			BalanceOf<T>,
+		),
+		/// Auction ended without a winner. \[token\]
+		AuctionClosed((T::ClassId, T::TokenId)),
+		/// Auction was cancelled by its seller. \[token\]
+		AuctionCancelled((T::ClassId, T::TokenId)),
+		/// Sealed bid was committed. \[bidder, token, deposit\]
+		BidCommitted(T::AccountId, (T::ClassId, T::TokenId), BalanceOf<T>),
+		/// Sealed bid was revealed. \[bidder, token, amount\]
+		BidRevealed(T::AccountId, (T::ClassId, T::TokenId), BalanceOf<T>),
+		/// Deposit of an unrevealed bid was slashed. \[bidder, token, deposit\]
+		DepositSlashed(T::AccountId, (T::ClassId, T::TokenId), BalanceOf<T>),
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let mut weight: Weight = T::DbWeight::get().writes(1);
+			AuctionEndCount::<T>::remove(now);
+			for (token, _) in AuctionEndTime::<T>::drain_prefix(now) {
+				if let Some(auction) = Auctions::<T>::take(token.0, token.1) {
+					let royalties = T::MaxRoyaltyBeneficiaries::get();
+					weight = weight.saturating_add(match auction.kind {
+						AuctionKind::Sealed { .. } => {
+							T::WeightInfo::settle_sealed(SealedBidCount::<T>::get(token), royalties)
+						}
+						_ => T::WeightInfo::settle(royalties),
+					});
+					weight = weight.saturating_add(T::OnTokenChange::on_transfer_weight());
+					Self::settle(token, auction);
+				}
+			}
+			weight
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Auction every share of `token` for `duration` blocks
+		#[pallet::weight(T::WeightInfo::create_auction())]
+		#[transactional]
+		pub fn create_auction(
+			origin: OriginFor<T>,
+			token: (T::ClassId, T::TokenId),
+			kind: AuctionKind<BalanceOf<T>>,
+			duration: T::BlockNumber,
+		) -> DispatchResultWithPostInfo {
+			let seller = ensure_signed(origin)?;
+
+			ensure!(
+				duration >= T::MinimumDuration::get() && duration <= T::MaximumDuration::get(),
+				Error::<T>::InvalidDuration
+			);
+			if let AuctionKind::Dutch {
+				start_price,
+				end_price,
+			} = &kind
+			{
+				ensure!(start_price >= end_price, Error::<T>::InvalidPrice);
+			}
+			ensure!(
+				BaseNft::<T>::shares_of(&seller, token) == T::SharesPerToken::get(),
+				Error::<T>::NotWholeTokenOwner
+			);
+			ensure!(T::TradeFilter::filter(&token), Error::<T>::TokenInactive);
+
+			BaseNft::<T>::lock(token)?;
+
+			let start = frame_system::Pallet::<T>::block_number();
+			let end = start.saturating_add(duration);
+			Self::schedule(token, Self::settlement(&kind, end))?;
+			Auctions::<T>::insert(
+				token.0,
+				token.1,
+				AuctionInfo {
+					seller: seller.clone(),
+					kind,
+					start,
+					end,
+					bid: None,
+					royalty: BaseNft::<T>::royalty_of(token),
+				},
+			);
+
+			Self::deposit_event(Event::AuctionCreated(seller, token, end));
+			Ok(().into())
+		}
+
+		/// Bid `amount` in the English auction of `token`, the amount is reserved until outbid
+		#[pallet::weight(T::WeightInfo::bid())]
+		#[transactional]
+		pub fn bid(
+			origin: OriginFor<T>,
+			token: (T::ClassId, T::TokenId),
+			amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let bidder = ensure_signed(origin)?;
+			ensure!(T::TradeFilter::filter(&token), Error::<T>::TokenInactive);
+
+			Auctions::<T>::try_mutate(token.0, token.1, |auction| -> DispatchResult {
+				let auction = auction.as_mut().ok_or(Error::<T>::AuctionNotFound)?;
+				ensure!(bidder != auction.seller, Error::<T>::BidderIsSeller);
+
+				let minimum_bid = match (&auction.kind, &auction.bid) {
+					(AuctionKind::English { .. }, Some((_, highest))) => {
+						let increment = (T::MinimumBidIncrement::get() * *highest).max(One::one());
+						highest.saturating_add(increment)
+					}
+					(AuctionKind::English { reserve_price }, None) => *reserve_price,
+					(AuctionKind::Dutch { .. }, _) | (AuctionKind::Sealed { .. }, _) => {
+						return Err(Error::<T>::NotEnglishAuction.into())
+					}
+				};
+				ensure!(amount >= minimum_bid, Error::<T>::BidTooLow);
+
+				match auction.bid.take() {
+					Some((highest_bidder, highest)) if highest_bidder == bidder => {
+						<T as base_nft::Config>::Currency::reserve(&bidder, amount - highest)?;
+					}
+					Some((highest_bidder, highest)) => {
+						<T as base_nft::Config>::Currency::reserve(&bidder, amount)?;
+						<T as base_nft::Config>::Currency::unreserve(&highest_bidder, highest);
+					}
+					None => {
+						<T as base_nft::Config>::Currency::reserve(&bidder, amount)?;
+					}
+				}
+				auction.bid = Some((bidder.clone(), amount));
+				Self::deposit_event(Event::BidPlaced(bidder.clone(), token, amount));
+
+				// the auction keeps its end when the block it would move to is full
+				let now = frame_system::Pallet::<T>::block_number();
+				if auction.end.saturating_sub(now) < T::AntiSnipingPeriod::get() {
+					let end = now.saturating_add(T::AntiSnipingPeriod::get());
+					if end != auction.end && Self::schedule(token, end).is_ok() {
+						Self::unschedule(token, auction.end);
+						auction.end = end;
+						Self::deposit_event(Event::AuctionExtended(token, end));
+					}
+				}
+				Ok(())
+			})?;
+
+			Ok(().into())
+		}
+
+		/// Buy `token` in a Dutch auction at its current price, paying at most `max_price`
+		#[pallet::weight(
+			T::WeightInfo::buy(T::MaxRoyaltyBeneficiaries::get())
+				.saturating_add(T::OnTokenChange::on_transfer_weight())
+		)]
+		#[transactional]
+		pub fn buy(
+			origin: OriginFor<T>,
+			token: (T::ClassId, T::TokenId),
+			max_price: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let buyer = ensure_signed(origin)?;
+
+			let auction =
+				Auctions::<T>::get(token.0, token.1).ok_or(Error::<T>::AuctionNotFound)?;
+			ensure!(buyer != auction.seller, Error::<T>::BidderIsSeller);
+			ensure!(T::TradeFilter::filter(&token), Error::<T>::TokenInactive);
+			let now = frame_system::Pallet::<T>::block_number();
+			let price = Self::dutch_price(&auction, now).ok_or(Error::<T>::NotDutchAuction)?;
+			ensure!(price <= max_price, Error::<T>::PriceTooHigh);
+
+			let royalties = BaseNft::<T>::pay_royalties(&buyer, token, &auction.royalty, price)?;
+			<T as base_nft::Config>::Currency::transfer(
+				&buyer,
+				&auction.seller,
+				price.saturating_sub(royalties),
+				ExistenceRequirement::KeepAlive,
+			)?;
+			Self::close(token, &auction);
+			BaseNft::<T>::transfer(&auction.seller, &buyer, token, T::SharesPerToken::get())?;
+
+			Self::deposit_event(Event::AuctionSold(auction.seller, buyer, token, price));
+			Ok(().into())
+		}
+
+		/// Cancel the auction of `token`, English auctions must not have bids
+		#[pallet::weight(T::WeightInfo::cancel_auction())]
+		pub fn cancel_auction(
+			origin: OriginFor<T>,
+			token: (T::ClassId, T::TokenId),
+		) -> DispatchResultWithPostInfo {
+			let seller = ensure_signed(origin)?;
+
+			let auction =
+				Auctions::<T>::get(token.0, token.1).ok_or(Error::<T>::AuctionNotFound)?;
+			ensure!(auction.seller == seller, Error::<T>::NotSeller);
+			ensure!(
+				auction.bid.is_none() && SealedBidCount::<T>::get(token) == 0,
+				Error::<T>::AuctionHasBids
+			);
+
+			Self::close(token, &auction);
+
+			Self::deposit_event(Event::AuctionCancelled(token));
+			Ok(().into())
+		}
+
+		/// Commit a sealed bid in the auction of `token`, reserving `deposit`
+		///
+		/// `commitment` is the `BlakeTwo256` hash of the SCALE encoded `(amount, salt)`, see
+		/// `Pallet::commitment`. The deposit must cover the amount revealed later.
+		#[pallet::weight(T::WeightInfo::commit_bid())]
+		pub fn commit_bid(
+			origin: OriginFor<T>,
+			token: (T::ClassId, T::TokenId),
+			commitment: H256,
+			deposit: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let bidder = ensure_signed(origin)?;
+
+			let auction =
+				Auctions::<T>::get(token.0, token.1).ok_or(Error::<T>::AuctionNotFound)?;
+			let reserve_price = match auction.kind {
+				AuctionKind::Sealed { reserve_price } => reserve_price,
+				_ => return Err(Error::<T>::NotSealedAuction.into()),
+			};
+			ensure!(bidder != auction.seller, Error::<T>::BidderIsSeller);
+			ensure!(T::TradeFilter::filter(&token), Error::<T>::TokenInactive);
+			ensure!(
+				frame_system::Pallet::<T>::block_number() < auction.end,
+				Error::<T>::NotCommitPhase
+			);
+			ensure!(
+				!SealedBids::<T>::contains_key(token, &bidder),
+				Error::<T>::AlreadyCommitted
+			);
+			ensure!(deposit >= reserve_price, Error::<T>::BidTooLow);
+			let count = SealedBidCount::<T>::get(token);
+			ensure!(count < T::MaxSealedBids::get(), Error::<T>::TooManyBids);
+
+			<T as base_nft::Config>::Currency::reserve(&bidder, deposit)?;
+			SealedBids::<T>::insert(
+				token,
+				&bidder,
+				SealedBid {
+					commitment,
+					deposit,
+				},
+			);
+			SealedBidCount::<T>::insert(token, count + 1);
+
+			Self::deposit_event(Event::BidCommitted(bidder, token, deposit));
+			Ok(().into())
+		}
+
+		/// Reveal the sealed bid of the caller in the auction of `token`
+		///
+		/// The highest revealed bid keeps `amount` reserved, the rest of its deposit and the
+		/// deposits of lower bids are refunded.
+		#[pallet::weight(T::WeightInfo::reveal_bid())]
+		#[transactional]
+		pub fn reveal_bid(
+			origin: OriginFor<T>,
+			token: (T::ClassId, T::TokenId),
+			amount: BalanceOf<T>,
+			salt: [u8; 32],
+		) -> DispatchResultWithPostInfo {
+			let bidder = ensure_signed(origin)?;
+
+			Auctions::<T>::try_mutate(token.0, token.1, |auction| -> DispatchResult {
+				let auction = auction.as_mut().ok_or(Error::<T>::AuctionNotFound)?;
+				let reserve_price = match auction.kind {
+					AuctionKind::Sealed { reserve_price } => reserve_price,
+					_ => return Err(Error::<T>::NotSealedAuction.into()),
+				};
+				ensure!(
+					frame_system::Pallet::<T>::block_number() >= auction.end,
+					Error::<T>::NotRevealPhase
+				);
+
+				let sealed =
+					SealedBids::<T>::take(token, &bidder).ok_or(Error::<T>::CommitmentNotFound)?;
+				ensure!(
+					Self::commitment(amount, &salt) == sealed.commitment,
+					Error::<T>::InvalidReveal
+				);
+				ensure!(amount <= sealed.deposit, Error::<T>::BidExceedsDeposit);
+
+				let wins = amount >= reserve_price
+					&& match &auction.bid {
+						Some((_, highest)) => amount > *highest,
+						None => true,
+					};
+				if wins {
+					<T as base_nft::Config>::Currency::unreserve(&bidder, sealed.deposit - amount);
+					if let Some((highest_bidder, highest)) =
+						auction.bid.replace((bidder.clone(), amount))
+					{
+						<T as base_nft::Config>::Currency::unreserve(&highest_bidder, highest);
+					}
+				} else {
+					<T as base_nft::Config>::Currency::unreserve(&bidder, sealed.deposit);
+				}
+
+				Self::deposit_event(Event::BidRevealed(bidder.clone(), token, amount));
+				Ok(())
+			})?;
+
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Commitment of a sealed bid of `amount` hidden with `salt`
+	pub fn commitment(amount: BalanceOf<T>, salt: &[u8; 32]) -> H256 {
+		BlakeTwo256::hash_of(&(amount, salt))
+	}
+
+	/// Current price of the Dutch auction of `token`
+	pub fn current_price(token: (T::ClassId, T::TokenId)) -> Option<BalanceOf<T>> {
+		let auction = Auctions::<T>::get(token.0, token.1)?;
+		Self::dutch_price(&auction, frame_system::Pallet::<T>::block_number())
+	}
+
+	/// Price of a Dutch auction at block `now`, `None` for English auctions
+	fn dutch_price(auction: &AuctionInfoOf<T>, now: T::BlockNumber) -> Option<BalanceOf<T>> {
+		match auction.kind {
+			AuctionKind::Dutch {
+				start_price,
+				end_price,
+			} => {
+				let elapsed = now.saturating_sub(auction.start).saturated_into::<u64>();
+				let duration = auction
+					.end
+					.saturating_sub(auction.start)
+					.saturated_into::<u64>();
+				let decay = Perbill::from_rational_approximation(elapsed, duration)
+					* start_price.saturating_sub(end_price);
+				Some(start_price.saturating_sub(decay))
+			}
+			_ => None,
+		}
+	}
+
+	/// Block an auction of `kind` ending at `end` is settled at
+	fn settlement(kind: &AuctionKind<BalanceOf<T>>, end: T::BlockNumber) -> T::BlockNumber {
+		match kind {
+			AuctionKind::Sealed { .. } => end.saturating_add(T::RevealPeriod::get()),
+			_ => end,
+		}
+	}
+
+	/// Settle the auction of `token` at block `at` unless `MaxAuctionsPerBlock` auctions already
+	/// settle at it
+	fn schedule(token: (T::ClassId, T::TokenId), at: T::BlockNumber) -> DispatchResult {
+		AuctionEndCount::<T>::try_mutate(at, |count| -> DispatchResult {
+			ensure!(
+				*count < T::MaxAuctionsPerBlock::get(),
+				Error::<T>::TooManyAuctionsEnding
+			);
+			*count += 1;
+			Ok(())
+		})?;
+		AuctionEndTime::<T>::insert(at, token, ());
+		Ok(())
+	}
+
+	/// Stop settling the auction of `token` at block `at`
+	fn unschedule(token: (T::ClassId, T::TokenId), at: T::BlockNumber) {
+		AuctionEndTime::<T>::remove(at, token);
+		AuctionEndCount::<T>::mutate_exists(at, |count| {
+			*count = count
+				.and_then(|count| count.checked_sub(1))
+				.filter(|count| *count > 0)
+		});
+	}
+
+	/// Remove the auction of `token` and unlock the token
+	fn close(token: (T::ClassId, T::TokenId), auction: &AuctionInfoOf<T>) {
+		Auctions::<T>::remove(token.0, token.1);
+		Self::unschedule(token, Self::settlement(&auction.kind, auction.end));
+		BaseNft::<T>::unlock(token);
+	}
+
+	/// Hand an ended auction to the highest bidder, or back to the seller without bids
+	fn settle(token: (T::ClassId, T::TokenId), auction: AuctionInfoOf<T>) {
+		BaseNft::<T>::unlock(token);
+
+		SealedBidCount::<T>::remove(token);
+		for (bidder, sealed) in SealedBids::<T>::drain_prefix(token) {
+			let (imbalance, _) =
+				<T as base_nft::Config>::Currency::slash_reserved(&bidder, sealed.deposit);
+			T::Slash::on_unbalanced(imbalance);
+			Self::deposit_event(Event::DepositSlashed(bidder, token, sealed.deposit));
+		}
+
+		let (winner, amount) = match auction.bid {
+			Some(bid) => bid,
+			None => {
+				Self::deposit_event(Event::AuctionClosed(token));
+				return;
+			}
+		};
+
+		match BaseNft::<T>::transfer(&auction.seller, &winner, token, T::SharesPerToken::get()) {
+			Ok(()) => {
+				// what cannot be paid to the seller is left reserved for them to claim
+				let royalties =
+					BaseNft::<T>::repatriate_royalties(&winner, token, &auction.royalty, amount);
+				BaseNft::<T>::repatriate_proceeds(
+					&winner,
+					&auction.seller,
+					token,
+					amount.saturating_sub(royalties),
+				);
+				Self::deposit_event(Event::AuctionSold(auction.seller, winner, token, amount));
+			}
+			Err(e) => {
+				debug::error!("Settling the auction of {:?} failed: {:?}", token, e);
+				<T as base_nft::Config>::Currency::unreserve(&winner, amount);
+				Self::deposit_event(Event::AuctionClosed(token));
+			}
+		}
+	}
+}
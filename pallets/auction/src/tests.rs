@@ -0,0 +1,797 @@
+#![cfg(test)]
+
+use crate::mock::{Event, *};
+use crate::{AuctionKind, Error, SealedBid};
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::{
+	traits::{BlakeTwo256, Hash},
+	Perbill,
+};
+
+const ENGLISH: AuctionKind<u64> = AuctionKind::English { reserve_price: 100 };
+const DUTCH: AuctionKind<u64> = AuctionKind::Dutch {
+	start_price: 500,
+	end_price: 100,
+};
+const SEALED: AuctionKind<u64> = AuctionKind::Sealed { reserve_price: 100 };
+
+/// Commit a sealed bid of `amount` with a salt unique to `bidder`
+fn commit(bidder: AccountId, token: (u32, u32), amount: u64, deposit: u64) -> [u8; 32] {
+	let salt = [bidder as u8; 32];
+	assert_ok!(Auction::commit_bid(
+		Origin::signed(bidder),
+		token,
+		Auction::commitment(amount, &salt),
+		deposit
+	));
+	salt
+}
+
+/// Token minted to `owner` in a new class
+fn mint_token(owner: &AccountId) -> (u32, u32) {
+	let class_id = BaseNft::create_class(owner, vec![1], ()).unwrap();
+	let token_id = BaseNft::mint(owner, class_id, vec![1], ()).unwrap();
+	(class_id, token_id)
+}
+
+#[test]
+fn create_auction_should_work() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+
+		assert_ok!(Auction::create_auction(
+			Origin::signed(ALICE),
+			token,
+			ENGLISH,
+			20
+		));
+		assert_eq!(Auction::auctions(token.0, token.1).unwrap().end, 21);
+		assert_eq!(Auction::auction_end_time(21, token), Some(()));
+		assert!(BaseNft::is_locked(token));
+		assert_eq!(
+			last_event(),
+			Event::pallet_auction(crate::Event::AuctionCreated(ALICE, token, 21))
+		);
+
+		// the token cannot leave the seller while auctioned
+		assert_noop!(
+			BaseNft::transfer(&ALICE, &BOB, token, 10),
+			base_nft::Error::<Runtime>::TokenLocked
+		);
+		assert_noop!(
+			BaseNft::burn(&ALICE, token),
+			base_nft::Error::<Runtime>::TokenLocked
+		);
+	});
+}
+
+#[test]
+fn create_auction_should_fail() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+
+		assert_noop!(
+			Auction::create_auction(Origin::signed(ALICE), token, ENGLISH, 9),
+			Error::<Runtime>::InvalidDuration
+		);
+		assert_noop!(
+			Auction::create_auction(Origin::signed(ALICE), token, ENGLISH, 101),
+			Error::<Runtime>::InvalidDuration
+		);
+		assert_noop!(
+			Auction::create_auction(
+				Origin::signed(ALICE),
+				token,
+				AuctionKind::Dutch {
+					start_price: 100,
+					end_price: 500
+				},
+				20
+			),
+			Error::<Runtime>::InvalidPrice
+		);
+		assert_noop!(
+			Auction::create_auction(Origin::signed(BOB), token, ENGLISH, 20),
+			Error::<Runtime>::NotWholeTokenOwner
+		);
+
+		assert_ok!(BaseNft::transfer(&ALICE, &BOB, token, 1));
+		assert_noop!(
+			Auction::create_auction(Origin::signed(ALICE), token, ENGLISH, 20),
+			Error::<Runtime>::NotWholeTokenOwner
+		);
+		assert_ok!(BaseNft::transfer(&BOB, &ALICE, token, 1));
+
+		assert_ok!(Auction::create_auction(
+			Origin::signed(ALICE),
+			token,
+			ENGLISH,
+			20
+		));
+		assert_noop!(
+			Auction::create_auction(Origin::signed(ALICE), token, DUTCH, 20),
+			base_nft::Error::<Runtime>::TokenLocked
+		);
+	});
+}
+
+#[test]
+fn bids_are_reserved_and_refunded() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+		assert_ok!(Auction::create_auction(
+			Origin::signed(ALICE),
+			token,
+			ENGLISH,
+			20
+		));
+
+		assert_noop!(
+			Auction::bid(Origin::signed(ALICE), token, 100),
+			Error::<Runtime>::BidderIsSeller
+		);
+		assert_noop!(
+			Auction::bid(Origin::signed(BOB), token, 99),
+			Error::<Runtime>::BidTooLow
+		);
+
+		assert_ok!(Auction::bid(Origin::signed(BOB), token, 100));
+		assert_eq!(Balances::reserved_balance(BOB), 100);
+		assert_eq!(
+			last_event(),
+			Event::pallet_auction(crate::Event::BidPlaced(BOB, token, 100))
+		);
+
+		// a bid must raise the highest bid by 10%
+		assert_noop!(
+			Auction::bid(Origin::signed(CHARLIE), token, 109),
+			Error::<Runtime>::BidTooLow
+		);
+		assert_ok!(Auction::bid(Origin::signed(CHARLIE), token, 110));
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Balances::reserved_balance(CHARLIE), 110);
+
+		// raising one's own bid only reserves the difference
+		assert_ok!(Auction::bid(Origin::signed(CHARLIE), token, 200));
+		assert_eq!(Balances::reserved_balance(CHARLIE), 200);
+		assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE - 200);
+
+		assert_noop!(
+			Auction::bid(Origin::signed(BOB), token, INITIAL_BALANCE * 2),
+			pallet_balances::Error::<Runtime>::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+fn english_auction_settles_to_highest_bidder() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+		assert_ok!(Auction::create_auction(
+			Origin::signed(ALICE),
+			token,
+			ENGLISH,
+			20
+		));
+		assert_ok!(Auction::bid(Origin::signed(BOB), token, 100));
+		assert_ok!(Auction::bid(Origin::signed(CHARLIE), token, 150));
+
+		run_to_block(20);
+		assert!(Auction::auctions(token.0, token.1).is_some());
+
+		run_to_block(21);
+		assert_eq!(Auction::auctions(token.0, token.1), None);
+		assert_eq!(Auction::auction_end_time(21, token), None);
+		assert!(!BaseNft::is_locked(token));
+		assert_eq!(BaseNft::shares_of(&CHARLIE, token), SharesPerToken::get());
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 150);
+		assert_eq!(Balances::reserved_balance(CHARLIE), 0);
+		assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE - 150);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE);
+		assert_eq!(
+			last_event(),
+			Event::pallet_auction(crate::Event::AuctionSold(ALICE, CHARLIE, token, 150))
+		);
+	});
+}
+
+#[test]
+fn settlement_to_dead_seller_leaves_proceeds_to_claim() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&EVE);
+		assert_ok!(Auction::create_auction(
+			Origin::signed(EVE),
+			token,
+			ENGLISH,
+			20
+		));
+		assert_ok!(Auction::bid(Origin::signed(BOB), token, 100));
+
+		run_to_block(21);
+		assert_eq!(BaseNft::shares_of(&BOB, token), SharesPerToken::get());
+		assert_eq!(Balances::free_balance(EVE), 0);
+		assert_eq!(BaseNft::unclaimed_proceeds(EVE, BOB), 100);
+		assert_eq!(Balances::reserved_balance(BOB), 100);
+
+		assert_ok!(Balances::transfer(Origin::signed(ALICE), EVE, 1));
+		assert_ok!(BaseNft::claim_proceeds(&EVE, &BOB));
+		assert_eq!(Balances::free_balance(EVE), 101);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+	});
+}
+
+#[test]
+fn settlement_pays_royalties() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+		assert_ok!(BaseNft::set_class_royalty(
+			&ALICE,
+			token.0,
+			vec![(DAVE, Perbill::from_percent(10))]
+		));
+		assert_ok!(Auction::create_auction(
+			Origin::signed(ALICE),
+			token,
+			ENGLISH,
+			20
+		));
+		assert_ok!(Auction::bid(Origin::signed(BOB), token, 300));
+
+		run_to_block(21);
+		assert_eq!(BaseNft::shares_of(&BOB, token), SharesPerToken::get());
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE - 300);
+		assert_eq!(Balances::free_balance(DAVE), INITIAL_BALANCE + 30);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 270);
+
+		// Dutch auctions pay the royalty from the buyer
+		assert_ok!(Auction::create_auction(
+			Origin::signed(BOB),
+			token,
+			DUTCH,
+			20
+		));
+		assert_ok!(Auction::buy(Origin::signed(CHARLIE), token, 500));
+		assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE - 500);
+		assert_eq!(Balances::free_balance(DAVE), INITIAL_BALANCE + 80);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE - 300 + 450);
+	});
+}
+
+#[test]
+fn settlement_pays_royalty_of_auction() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+		assert_ok!(BaseNft::set_class_royalty(
+			&ALICE,
+			token.0,
+			vec![(DAVE, Perbill::from_percent(10))]
+		));
+		assert_ok!(BaseNft::transfer(
+			&ALICE,
+			&BOB,
+			token,
+			SharesPerToken::get()
+		));
+		assert_ok!(Auction::create_auction(
+			Origin::signed(BOB),
+			token,
+			ENGLISH,
+			20
+		));
+		assert_ok!(Auction::bid(Origin::signed(CHARLIE), token, 300));
+
+		// the class owner raises the royalty of a token under auction
+		assert_ok!(BaseNft::set_class_royalty(
+			&ALICE,
+			token.0,
+			vec![(DAVE, Perbill::from_percent(50))]
+		));
+
+		run_to_block(21);
+		assert_eq!(BaseNft::shares_of(&CHARLIE, token), SharesPerToken::get());
+		assert_eq!(Balances::free_balance(DAVE), INITIAL_BALANCE + 30);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE + 270);
+	});
+}
+
+#[test]
+fn english_auction_without_bids_closes() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+		assert_ok!(Auction::create_auction(
+			Origin::signed(ALICE),
+			token,
+			ENGLISH,
+			20
+		));
+
+		run_to_block(21);
+		assert_eq!(Auction::auctions(token.0, token.1), None);
+		assert!(!BaseNft::is_locked(token));
+		assert_eq!(BaseNft::shares_of(&ALICE, token), SharesPerToken::get());
+		assert_eq!(
+			last_event(),
+			Event::pallet_auction(crate::Event::AuctionClosed(token))
+		);
+	});
+}
+
+#[test]
+fn late_bids_extend_auction() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+		assert_ok!(Auction::create_auction(
+			Origin::signed(ALICE),
+			token,
+			ENGLISH,
+			20
+		));
+
+		// 6 blocks before the end, no extension
+		run_to_block(15);
+		assert_ok!(Auction::bid(Origin::signed(BOB), token, 100));
+		assert_eq!(Auction::auctions(token.0, token.1).unwrap().end, 21);
+
+		run_to_block(18);
+		assert_ok!(Auction::bid(Origin::signed(CHARLIE), token, 110));
+		assert_eq!(Auction::auctions(token.0, token.1).unwrap().end, 23);
+		assert_eq!(Auction::auction_end_time(21, token), None);
+		assert_eq!(Auction::auction_end_time(23, token), Some(()));
+		assert_eq!(
+			last_event(),
+			Event::pallet_auction(crate::Event::AuctionExtended(token, 23))
+		);
+
+		run_to_block(22);
+		assert!(Auction::auctions(token.0, token.1).is_some());
+		run_to_block(23);
+		assert_eq!(BaseNft::shares_of(&CHARLIE, token), SharesPerToken::get());
+	});
+}
+
+#[test]
+fn auctions_per_block_are_bounded() {
+	new_test_ext().execute_with(|| {
+		let tokens: Vec<(u32, u32)> = (0..4).map(|_| mint_token(&ALICE)).collect();
+		assert_ok!(Auction::create_auction(
+			Origin::signed(ALICE),
+			tokens[0],
+			ENGLISH,
+			20
+		));
+		assert_ok!(Auction::create_auction(
+			Origin::signed(ALICE),
+			tokens[1],
+			ENGLISH,
+			20
+		));
+		assert_eq!(Auction::auction_end_count(21), 2);
+		assert_noop!(
+			Auction::create_auction(Origin::signed(ALICE), tokens[2], ENGLISH, 20),
+			Error::<Runtime>::TooManyAuctionsEnding
+		);
+		assert_ok!(Auction::create_auction(
+			Origin::signed(ALICE),
+			tokens[2],
+			ENGLISH,
+			21
+		));
+		assert_ok!(Auction::create_auction(
+			Origin::signed(ALICE),
+			tokens[3],
+			ENGLISH,
+			21
+		));
+
+		// a late bid does not move the auction into a full block
+		run_to_block(17);
+		assert_ok!(Auction::bid(Origin::signed(BOB), tokens[0], 100));
+		assert_eq!(Auction::auctions(tokens[0].0, tokens[0].1).unwrap().end, 21);
+		assert_eq!(
+			last_event(),
+			Event::pallet_auction(crate::Event::BidPlaced(BOB, tokens[0], 100))
+		);
+
+		// a cancelled auction frees its slot
+		assert_ok!(Auction::cancel_auction(Origin::signed(ALICE), tokens[3]));
+		assert_eq!(Auction::auction_end_count(22), 1);
+		assert_ok!(Auction::bid(Origin::signed(BOB), tokens[1], 100));
+		assert_eq!(Auction::auctions(tokens[1].0, tokens[1].1).unwrap().end, 22);
+		assert_eq!(Auction::auction_end_count(21), 1);
+		assert_eq!(Auction::auction_end_count(22), 2);
+
+		run_to_block(21);
+		assert_eq!(BaseNft::shares_of(&BOB, tokens[0]), SharesPerToken::get());
+		assert_eq!(Auction::auction_end_count(21), 0);
+		run_to_block(22);
+		assert_eq!(BaseNft::shares_of(&BOB, tokens[1]), SharesPerToken::get());
+		assert_eq!(Auction::auction_end_count(22), 0);
+	});
+}
+
+#[test]
+fn dutch_price_decays_linearly() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+		assert_ok!(Auction::create_auction(
+			Origin::signed(ALICE),
+			token,
+			DUTCH,
+			20
+		));
+
+		assert_eq!(Auction::current_price(token), Some(500));
+		run_to_block(6);
+		assert_eq!(Auction::current_price(token), Some(400));
+		run_to_block(16);
+		assert_eq!(Auction::current_price(token), Some(200));
+
+		assert_noop!(
+			Auction::bid(Origin::signed(BOB), token, 500),
+			Error::<Runtime>::NotEnglishAuction
+		);
+		assert_noop!(
+			Auction::buy(Origin::signed(BOB), token, 199),
+			Error::<Runtime>::PriceTooHigh
+		);
+
+		assert_ok!(Auction::buy(Origin::signed(BOB), token, 200));
+		assert_eq!(BaseNft::shares_of(&BOB, token), SharesPerToken::get());
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 200);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE - 200);
+		assert_eq!(Auction::auctions(token.0, token.1), None);
+		assert_eq!(Auction::auction_end_time(21, token), None);
+		assert!(!BaseNft::is_locked(token));
+		assert_eq!(
+			last_event(),
+			Event::pallet_auction(crate::Event::AuctionSold(ALICE, BOB, token, 200))
+		);
+	});
+}
+
+#[test]
+fn unsold_dutch_auction_closes() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+		assert_ok!(Auction::create_auction(
+			Origin::signed(ALICE),
+			token,
+			DUTCH,
+			20
+		));
+
+		run_to_block(21);
+		assert_eq!(Auction::current_price(token), None);
+		assert_noop!(
+			Auction::buy(Origin::signed(BOB), token, 500),
+			Error::<Runtime>::AuctionNotFound
+		);
+		assert_eq!(BaseNft::shares_of(&ALICE, token), SharesPerToken::get());
+		assert!(!BaseNft::is_locked(token));
+	});
+}
+
+#[test]
+fn cancel_auction_should_work() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+		assert_ok!(Auction::create_auction(
+			Origin::signed(ALICE),
+			token,
+			ENGLISH,
+			20
+		));
+
+		assert_noop!(
+			Auction::cancel_auction(Origin::signed(BOB), token),
+			Error::<Runtime>::NotSeller
+		);
+		assert_ok!(Auction::cancel_auction(Origin::signed(ALICE), token));
+		assert_eq!(Auction::auctions(token.0, token.1), None);
+		assert_eq!(Auction::auction_end_time(21, token), None);
+		assert!(!BaseNft::is_locked(token));
+		assert_eq!(
+			last_event(),
+			Event::pallet_auction(crate::Event::AuctionCancelled(token))
+		);
+
+		assert_ok!(Auction::create_auction(
+			Origin::signed(ALICE),
+			token,
+			ENGLISH,
+			20
+		));
+		assert_ok!(Auction::bid(Origin::signed(BOB), token, 100));
+		assert_noop!(
+			Auction::cancel_auction(Origin::signed(ALICE), token),
+			Error::<Runtime>::AuctionHasBids
+		);
+	});
+}
+
+#[test]
+fn commit_bid_should_work() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+		assert_ok!(Auction::create_auction(
+			Origin::signed(ALICE),
+			token,
+			SEALED,
+			20
+		));
+		// settled after the reveal period
+		assert_eq!(Auction::auction_end_time(21, token), None);
+		assert_eq!(Auction::auction_end_time(31, token), Some(()));
+
+		let salt = [7; 32];
+		let commitment = Auction::commitment(300, &salt);
+		assert_eq!(commitment, BlakeTwo256::hash_of(&(300_u64, salt)));
+		assert_ok!(Auction::commit_bid(
+			Origin::signed(BOB),
+			token,
+			commitment,
+			500
+		));
+		assert_eq!(
+			Auction::sealed_bids(token, BOB),
+			Some(SealedBid {
+				commitment,
+				deposit: 500
+			})
+		);
+		assert_eq!(Auction::sealed_bid_count(token), 1);
+		assert_eq!(Balances::reserved_balance(BOB), 500);
+		assert_eq!(
+			last_event(),
+			Event::pallet_auction(crate::Event::BidCommitted(BOB, token, 500))
+		);
+
+		assert_noop!(
+			Auction::bid(Origin::signed(CHARLIE), token, 500),
+			Error::<Runtime>::NotEnglishAuction
+		);
+		assert_noop!(
+			Auction::cancel_auction(Origin::signed(ALICE), token),
+			Error::<Runtime>::AuctionHasBids
+		);
+	});
+}
+
+#[test]
+fn commit_bid_should_fail() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+		let commitment = Auction::commitment(100, &[0; 32]);
+
+		assert_noop!(
+			Auction::commit_bid(Origin::signed(BOB), token, commitment, 100),
+			Error::<Runtime>::AuctionNotFound
+		);
+		assert_ok!(Auction::create_auction(
+			Origin::signed(ALICE),
+			token,
+			ENGLISH,
+			20
+		));
+		assert_noop!(
+			Auction::commit_bid(Origin::signed(BOB), token, commitment, 100),
+			Error::<Runtime>::NotSealedAuction
+		);
+		assert_ok!(Auction::cancel_auction(Origin::signed(ALICE), token));
+
+		assert_ok!(Auction::create_auction(
+			Origin::signed(ALICE),
+			token,
+			SEALED,
+			20
+		));
+		assert_noop!(
+			Auction::commit_bid(Origin::signed(ALICE), token, commitment, 100),
+			Error::<Runtime>::BidderIsSeller
+		);
+		assert_noop!(
+			Auction::commit_bid(Origin::signed(BOB), token, commitment, 99),
+			Error::<Runtime>::BidTooLow
+		);
+
+		commit(BOB, token, 100, 100);
+		assert_noop!(
+			Auction::commit_bid(Origin::signed(BOB), token, commitment, 100),
+			Error::<Runtime>::AlreadyCommitted
+		);
+		commit(CHARLIE, token, 100, 100);
+		commit(DAVE, token, 100, 100);
+		assert_eq!(Auction::sealed_bid_count(token), 3);
+		assert_noop!(
+			Auction::commit_bid(Origin::signed(ALICE + 10), token, commitment, 100),
+			Error::<Runtime>::TooManyBids
+		);
+
+		run_to_block(21);
+		assert_noop!(
+			Auction::commit_bid(Origin::signed(ALICE + 10), token, commitment, 100),
+			Error::<Runtime>::NotCommitPhase
+		);
+	});
+}
+
+#[test]
+fn reveal_bid_should_fail() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+		assert_ok!(Auction::create_auction(
+			Origin::signed(ALICE),
+			token,
+			SEALED,
+			20
+		));
+		let salt = commit(BOB, token, 300, 500);
+		let over_deposit = commit(CHARLIE, token, 600, 500);
+
+		assert_noop!(
+			Auction::reveal_bid(Origin::signed(BOB), token, 300, salt),
+			Error::<Runtime>::NotRevealPhase
+		);
+
+		run_to_block(21);
+		assert_noop!(
+			Auction::reveal_bid(Origin::signed(DAVE), token, 300, salt),
+			Error::<Runtime>::CommitmentNotFound
+		);
+		assert_noop!(
+			Auction::reveal_bid(Origin::signed(BOB), token, 301, salt),
+			Error::<Runtime>::InvalidReveal
+		);
+		assert_noop!(
+			Auction::reveal_bid(Origin::signed(BOB), token, 300, [0; 32]),
+			Error::<Runtime>::InvalidReveal
+		);
+		assert_noop!(
+			Auction::reveal_bid(Origin::signed(CHARLIE), token, 600, over_deposit),
+			Error::<Runtime>::BidExceedsDeposit
+		);
+	});
+}
+
+#[test]
+fn highest_revealed_bid_wins() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+		assert_ok!(Auction::create_auction(
+			Origin::signed(ALICE),
+			token,
+			SEALED,
+			20
+		));
+		let bob_salt = commit(BOB, token, 200, 500);
+		let charlie_salt = commit(CHARLIE, token, 300, 400);
+		let dave_salt = commit(DAVE, token, 250, 250);
+
+		run_to_block(21);
+		assert!(BaseNft::is_locked(token));
+
+		// the best bid so far keeps its amount reserved and gets the rest of its deposit back
+		assert_ok!(Auction::reveal_bid(
+			Origin::signed(BOB),
+			token,
+			200,
+			bob_salt
+		));
+		assert_eq!(Auction::sealed_bids(token, BOB), None);
+		assert_eq!(
+			Auction::auctions(token.0, token.1).unwrap().bid,
+			Some((BOB, 200))
+		);
+		assert_eq!(Balances::reserved_balance(BOB), 200);
+		assert_eq!(
+			last_event(),
+			Event::pallet_auction(crate::Event::BidRevealed(BOB, token, 200))
+		);
+
+		assert_ok!(Auction::reveal_bid(
+			Origin::signed(CHARLIE),
+			token,
+			300,
+			charlie_salt
+		));
+		assert_eq!(
+			Auction::auctions(token.0, token.1).unwrap().bid,
+			Some((CHARLIE, 300))
+		);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Balances::reserved_balance(CHARLIE), 300);
+
+		// a lower bid is refunded on reveal
+		assert_ok!(Auction::reveal_bid(
+			Origin::signed(DAVE),
+			token,
+			250,
+			dave_salt
+		));
+		assert_eq!(Balances::reserved_balance(DAVE), 0);
+
+		run_to_block(30);
+		assert!(Auction::auctions(token.0, token.1).is_some());
+
+		run_to_block(31);
+		assert_eq!(Auction::auctions(token.0, token.1), None);
+		assert_eq!(Auction::sealed_bid_count(token), 0);
+		assert!(!BaseNft::is_locked(token));
+		assert_eq!(BaseNft::shares_of(&CHARLIE, token), SharesPerToken::get());
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 300);
+		assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE - 300);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE);
+		assert_eq!(Balances::free_balance(DAVE), INITIAL_BALANCE);
+		assert_eq!(
+			last_event(),
+			Event::pallet_auction(crate::Event::AuctionSold(ALICE, CHARLIE, token, 300))
+		);
+	});
+}
+
+#[test]
+fn unrevealed_deposits_are_slashed() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+		assert_ok!(Auction::create_auction(
+			Origin::signed(ALICE),
+			token,
+			SEALED,
+			20
+		));
+		let bob_salt = commit(BOB, token, 50, 150);
+		commit(CHARLIE, token, 300, 400);
+
+		run_to_block(21);
+		// below the reserve price, refunded without winning
+		assert_ok!(Auction::reveal_bid(
+			Origin::signed(BOB),
+			token,
+			50,
+			bob_salt
+		));
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Auction::auctions(token.0, token.1).unwrap().bid, None);
+
+		let issuance = Balances::total_issuance();
+		run_to_block(31);
+		assert_eq!(Auction::sealed_bids(token, CHARLIE), None);
+		assert_eq!(Balances::reserved_balance(CHARLIE), 0);
+		assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE - 400);
+		// the mock burns slashed deposits
+		assert_eq!(Balances::total_issuance(), issuance - 400);
+		assert_eq!(BaseNft::shares_of(&ALICE, token), SharesPerToken::get());
+		assert!(!BaseNft::is_locked(token));
+		assert!(System::events().iter().any(|record| record.event
+			== Event::pallet_auction(crate::Event::DepositSlashed(CHARLIE, token, 400))));
+		assert_eq!(
+			last_event(),
+			Event::pallet_auction(crate::Event::AuctionClosed(token))
+		);
+	});
+}
+
+#[test]
+fn inactive_tokens_are_not_auctioned() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+		assert_ok!(Auction::create_auction(
+			Origin::signed(ALICE),
+			token,
+			ENGLISH,
+			10
+		));
+		assert_ok!(BaseNft::set_token_metadata(&ALICE, token, vec![]));
+
+		assert_noop!(
+			Auction::bid(Origin::signed(BOB), token, 100),
+			Error::<Runtime>::TokenInactive
+		);
+		assert_ok!(Auction::cancel_auction(Origin::signed(ALICE), token));
+		assert_noop!(
+			Auction::create_auction(Origin::signed(ALICE), token, DUTCH, 10),
+			Error::<Runtime>::TokenInactive
+		);
+	});
+}
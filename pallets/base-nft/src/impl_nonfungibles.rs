@@ -0,0 +1,118 @@
+//! Implementations for the `anmol_utils::nonfungibles` traits.
+
+use super::*;
+use anmol_utils::nonfungibles::{Create, Destroy, Inspect, InspectEnumerable, Mutate, Transfer};
+
+impl<T: Config> Inspect<T::AccountId> for Pallet<T> {
+	type ClassId = T::ClassId;
+	type InstanceId = T::TokenId;
+	type Shares = T::Shares;
+
+	fn total_shares() -> T::Shares {
+		T::SharesPerToken::get()
+	}
+
+	fn owners(class: &T::ClassId, instance: &T::TokenId) -> Vec<T::AccountId> {
+		Tokens::<T>::get(class, instance).map_or_else(Vec::new, |info| Self::owner_accounts(&info))
+	}
+
+	fn shares_of(class: &T::ClassId, instance: &T::TokenId, who: &T::AccountId) -> T::Shares {
+		Self::shares_of(who, (*class, *instance))
+	}
+
+	fn class_owner(class: &T::ClassId) -> Option<T::AccountId> {
+		Classes::<T>::get(class).map(|info| info.owner)
+	}
+
+	fn class_metadata(class: &T::ClassId) -> Option<Vec<u8>> {
+		Classes::<T>::get(class).map(|info| info.metadata)
+	}
+
+	fn metadata(class: &T::ClassId, instance: &T::TokenId) -> Option<Vec<u8>> {
+		Tokens::<T>::get(class, instance).map(|info| info.metadata)
+	}
+
+	fn can_transfer(class: &T::ClassId, instance: &T::TokenId) -> bool {
+		!Self::is_locked((*class, *instance))
+	}
+}
+
+impl<T: Config> InspectEnumerable<T::AccountId> for Pallet<T> {
+	fn classes() -> Box<dyn Iterator<Item = T::ClassId>> {
+		Box::new(Classes::<T>::iter().map(|(class, _)| class))
+	}
+
+	fn instances(class: &T::ClassId) -> Box<dyn Iterator<Item = T::TokenId>> {
+		Box::new(Tokens::<T>::iter_prefix(class).map(|(instance, _)| instance))
+	}
+
+	fn owned(who: &T::AccountId) -> Box<dyn Iterator<Item = (T::ClassId, T::TokenId, T::Shares)>> {
+		Box::new(
+			Self::owned_tokens(who).map(|((class, instance), shares)| (class, instance, shares)),
+		)
+	}
+
+	fn owned_in_class(
+		class: &T::ClassId,
+		who: &T::AccountId,
+	) -> Box<dyn Iterator<Item = (T::TokenId, T::Shares)>> {
+		let class = *class;
+		Box::new(
+			Self::owned_tokens(who)
+				.filter(move |((token_class, _), _)| *token_class == class)
+				.map(|((_, instance), shares)| (instance, shares)),
+		)
+	}
+}
+
+impl<T: Config> Create<T::AccountId> for Pallet<T> {
+	fn create_class(owner: &T::AccountId, metadata: Vec<u8>) -> Result<T::ClassId, DispatchError> {
+		Self::create_class(owner, metadata, Default::default())
+	}
+}
+
+impl<T: Config> Mutate<T::AccountId> for Pallet<T> {
+	fn mint_into(
+		class: &T::ClassId,
+		who: &T::AccountId,
+		metadata: Vec<u8>,
+	) -> Result<T::TokenId, DispatchError> {
+		Self::mint(who, *class, metadata, Default::default())
+	}
+
+	/// The approvers must hold at least `BurnApprovalThreshold` of the shares
+	fn burn_from(
+		class: &T::ClassId,
+		instance: &T::TokenId,
+		approvers: &[T::AccountId],
+	) -> DispatchResult {
+		Self::burn_with_approvals(approvers, (*class, *instance))
+	}
+
+	fn set_metadata(
+		class: &T::ClassId,
+		instance: &T::TokenId,
+		who: &T::AccountId,
+		metadata: Vec<u8>,
+	) -> DispatchResult {
+		Self::set_token_metadata(who, (*class, *instance), metadata)
+	}
+}
+
+impl<T: Config> Transfer<T::AccountId> for Pallet<T> {
+	fn transfer(
+		class: &T::ClassId,
+		instance: &T::TokenId,
+		source: &T::AccountId,
+		destination: &T::AccountId,
+		shares: T::Shares,
+	) -> DispatchResult {
+		Self::transfer(source, destination, (*class, *instance), shares)
+	}
+}
+
+impl<T: Config> Destroy<T::AccountId> for Pallet<T> {
+	fn destroy(class: &T::ClassId, owner: &T::AccountId) -> DispatchResult {
+		Self::destroy_class(owner, *class)
+	}
+}
@@ -16,22 +16,71 @@
 //! - `transfer` - Transfer NFT(non fungible token) to another account.
 //! - `mint` - Mint NFT(non fungible token)
 //! - `burn` - Burn NFT(non fungible token)
+//! - `burn_with_approvals` - Burn co-owned NFT(non fungible token) approved by its owners
 //! - `destroy_class` - Destroy NFT(non fungible token) class
+//! - `set_class_metadata` - Update NFT(non fungible token) class metadata
+//! - `set_token_metadata` - Update NFT(non fungible token) metadata
+//!
+//! - `lock` / `unlock` - Lock NFT(non fungible token) against transfers and burns,
+//!   `transfer_locked` lets the pallet holding the lock move shares
+//! - `set_class_royalty` / `set_token_royalty` - Update royalties of NFT(non fungible token)
+//!   sales
+//!
+//! `Config::OnTokenChange` is notified after every transfer and burn.
+//!
+//! ### Fractional ownership
+//!
+//! Every token is split into `Config::SharesPerToken` shares which all belong to the minter.
+//! `transfer` moves any amount of shares, an account holding at least one share is an owner.
+//! A token has at most `Config::MaxOwnersPerToken` owners.
+//!
+//! Shares are indexed by owner in `TokensByOwner`. With the `disable-tokens-by-owner` feature
+//! the index is not maintained, every owner is stored with its shares in `TokenInfo::owners`
+//! instead, which makes `tokens_of_owner` iterate over all tokens.
+//!
+//! ### Storage deposits
+//!
+//! Creating a class or minting a token reserves a deposit growing with the metadata length,
+//! it is refunded by `destroy_class` and `burn`.
+//!
+//! ### Royalties
+//!
+//! A class owner sets royalty beneficiaries with their `Perbill` rate of the sale price, for the
+//! whole class or overridden for a single token. Sales pallets pay them from the buyer with
+//! `pay_royalties` or `repatriate_royalties`, `royalties_for` reports what is owed for a price.
+//! A sale set up ahead of time, e.g. a listing, keeps the `royalty_of` the token when it was set
+//! up, so the class owner cannot change the terms under the seller.
+//!
+//! Sellers are paid their part of a reserved price with `repatriate_proceeds`. A payment that
+//! fails, e.g. to a seller without an account, stays reserved by the buyer in
+//! `UnclaimedProceeds` until the seller takes it with `claim_proceeds`.
+//!
+//! ### Generic access
+//!
+//! The pallet implements the `anmol_utils::nonfungibles` traits so other pallets can work
+//! with any fractional NFT implementation.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
 
-use anmol_utils;
 use codec::{Decode, Encode};
-use frame_support::{ensure, pallet_prelude::*, Parameter};
+use frame_support::{
+	ensure,
+	pallet_prelude::*,
+	traits::{BalanceStatus, Currency, ExistenceRequirement, ReservableCurrency},
+	transactional, Parameter,
+};
 use sp_runtime::{
 	traits::{
-		AtLeast32BitUnsigned, CheckedAdd, CheckedSub, MaybeSerializeDeserialize, Member, One, Zero,
+		AtLeast32BitUnsigned, CheckedAdd, CheckedSub, MaybeSerializeDeserialize, Member, One,
+		Saturating, Zero,
 	},
-	DispatchError, DispatchResult, RuntimeDebug,
+	DispatchError, DispatchResult, Perbill, RuntimeDebug,
 };
-use sp_std::vec::Vec;
+use sp_std::{boxed::Box, vec::Vec};
 
+mod impl_nonfungibles;
+pub mod migrations;
 mod mock;
 mod tests;
 
@@ -53,12 +102,52 @@ pub struct ClassInfo<TokenId, AccountId, Data> {
 pub struct TokenInfo<AccountId, Data> {
 	/// Token metadata
 	pub metadata: Vec<u8>,
-	/// Token owner
+	/// Token owners sorted by account, with their shares when `disable-tokens-by-owner` is enabled
 	pub owners: Vec<AccountId>,
 	/// Token Properties
 	pub data: Data,
 }
 
+/// Handler of token ownership changes, e.g. to invalidate marketplace listings
+///
+/// The weight of the handler is not known to the callers of `transfer` and `burn`, they add the
+/// bounds given by `on_transfer_weight` and `on_burn_weight` to their own.
+pub trait OnTokenChange<AccountId, ClassId, TokenId, Shares> {
+	/// Called after `shares` of `token` moved from `from` to `to`
+	fn on_transfer(from: &AccountId, to: &AccountId, token: (ClassId, TokenId), shares: Shares);
+	/// Called after `token` was burned with the shares its owners held
+	fn on_burn(token: (ClassId, TokenId), owners: &[(AccountId, Shares)]);
+	/// Upper bound of the weight of `on_transfer`
+	fn on_transfer_weight() -> Weight;
+	/// Upper bound of the weight of `on_burn` for a token with `owners` owners
+	fn on_burn_weight(owners: u32) -> Weight;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+impl<AccountId, ClassId: Copy, TokenId: Copy, Shares: Copy>
+	OnTokenChange<AccountId, ClassId, TokenId, Shares> for Tuple
+{
+	fn on_transfer(from: &AccountId, to: &AccountId, token: (ClassId, TokenId), shares: Shares) {
+		for_tuples!( #( Tuple::on_transfer(from, to, token, shares); )* );
+	}
+
+	fn on_burn(token: (ClassId, TokenId), owners: &[(AccountId, Shares)]) {
+		for_tuples!( #( Tuple::on_burn(token, owners); )* );
+	}
+
+	fn on_transfer_weight() -> Weight {
+		let mut weight: Weight = 0;
+		for_tuples!( #( weight = weight.saturating_add(Tuple::on_transfer_weight()); )* );
+		weight
+	}
+
+	fn on_burn_weight(owners: u32) -> Weight {
+		let mut weight: Weight = 0;
+		for_tuples!( #( weight = weight.saturating_add(Tuple::on_burn_weight(owners)); )* );
+		weight
+	}
+}
+
 pub use module::*;
 
 #[frame_support::pallet]
@@ -67,6 +156,8 @@ pub mod module {
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
+		/// The overarching event type
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 		/// The class ID type
 		type ClassId: Parameter + Member + AtLeast32BitUnsigned + Default + Copy;
 		/// The token ID type
@@ -75,15 +166,80 @@ pub mod module {
 		type ClassData: Parameter + Member + MaybeSerializeDeserialize + Default;
 		/// The token properties type
 		type TokenData: Parameter + Member + MaybeSerializeDeserialize + Default;
+		/// The type used to account fractional ownership of a token
+		type Shares: Parameter
+			+ Member
+			+ AtLeast32BitUnsigned
+			+ MaybeSerializeDeserialize
+			+ Default
+			+ Copy;
+		/// Total shares of a token, all of them belong to the minter
+		///
+		/// Should be a multiple of 100 so percentages held before the `Shares`
+		/// upgrade convert exactly.
+		#[pallet::constant]
+		type SharesPerToken: Get<Self::Shares>;
+		/// Part of the token shares whose owners must approve burning a co-owned token
+		#[pallet::constant]
+		type BurnApprovalThreshold: Get<Perbill>;
+		/// The currency storage deposits are reserved in
+		type Currency: ReservableCurrency<Self::AccountId>;
+		/// Maximum length of class and token metadata
+		#[pallet::constant]
+		type MaxMetadataLength: Get<u32>;
+		/// Maximum number of accounts holding shares of a token
+		#[pallet::constant]
+		type MaxOwnersPerToken: Get<u32>;
+		/// Deposit reserved from the owner to create a class
+		#[pallet::constant]
+		type ClassDeposit: Get<BalanceOf<Self>>;
+		/// Deposit reserved from the minter to mint a token
+		#[pallet::constant]
+		type TokenDeposit: Get<BalanceOf<Self>>;
+		/// Deposit reserved for each byte of class or token metadata
+		#[pallet::constant]
+		type DepositPerByte: Get<BalanceOf<Self>>;
+		/// Maximum number of royalty beneficiaries of a class or token
+		#[pallet::constant]
+		type MaxRoyaltyBeneficiaries: Get<u32>;
+		/// Maximum sum of the royalty rates of a class or token
+		#[pallet::constant]
+		type MaxRoyalty: Get<Perbill>;
+		/// Handler notified of transfers and burns
+		type OnTokenChange: OnTokenChange<
+			Self::AccountId,
+			Self::ClassId,
+			Self::TokenId,
+			Self::Shares,
+		>;
 	}
 
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+	/// Royalty beneficiaries with their rate of the sale price
+	pub type RoyaltyOf<T> = Vec<(<T as frame_system::Config>::AccountId, Perbill)>;
+
 	pub type ClassInfoOf<T> = ClassInfo<
 		<T as Config>::TokenId,
 		<T as frame_system::Config>::AccountId,
 		<T as Config>::ClassData,
 	>;
-	pub type TokenInfoOf<T> =
-		TokenInfo<<T as frame_system::Config>::AccountId, <T as Config>::TokenData>;
+	/// Entry of `TokenInfo::owners`
+	#[cfg(not(feature = "disable-tokens-by-owner"))]
+	pub type TokenOwnerOf<T> = <T as frame_system::Config>::AccountId;
+	/// Entry of `TokenInfo::owners`, shares are kept next to the owner without `TokensByOwner`
+	#[cfg(feature = "disable-tokens-by-owner")]
+	pub type TokenOwnerOf<T> = (
+		<T as frame_system::Config>::AccountId,
+		<T as Config>::Shares,
+	);
+	pub type TokenInfoOf<T> = TokenInfo<TokenOwnerOf<T>, <T as Config>::TokenData>;
+	/// Token held by an owner with the shares of the owner
+	pub type OwnedTokenOf<T> = (
+		(<T as Config>::ClassId, <T as Config>::TokenId),
+		<T as Config>::Shares,
+	);
 
 	pub type GenesisTokenData<T> = (
 		<T as frame_system::Config>::AccountId, // Token owner
@@ -119,6 +275,82 @@ pub mod module {
 		SenderInsufficientPercentage,
 		/// Wrong arguments
 		WrongArguments,
+		/// No approval exists for the token
+		ApprovalNotFound,
+		/// Owners approving the burn do not hold enough shares of the token
+		BurnNotApproved,
+		/// Metadata is longer than `MaxMetadataLength`
+		MetadataTooLong,
+		/// Token already has `MaxOwnersPerToken` owners
+		TooManyOwners,
+		/// Token is locked, e.g. while it is auctioned
+		TokenLocked,
+		/// Royalty has more than `MaxRoyaltyBeneficiaries` beneficiaries
+		TooManyRoyaltyBeneficiaries,
+		/// Royalty rates add up to more than `MaxRoyalty`
+		RoyaltyTooHigh,
+		/// Buyer owes the seller no unclaimed proceeds
+		NoUnclaimedProceeds,
+	}
+
+	#[pallet::event]
+	#[pallet::metadata(
+		T::AccountId = "AccountId",
+		T::ClassId = "ClassId",
+		T::TokenId = "TokenId",
+		T::Shares = "Shares",
+		BalanceOf<T> = "Balance"
+	)]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Class was created. \[owner, class_id\]
+		ClassCreated(T::AccountId, T::ClassId),
+		/// Class was destroyed. \[owner, class_id\]
+		ClassDestroyed(T::AccountId, T::ClassId),
+		/// Class metadata was updated. \[class_id\]
+		ClassMetadataUpdated(T::ClassId),
+		/// Token was minted with all of its shares. \[owner, token\]
+		Minted(T::AccountId, (T::ClassId, T::TokenId)),
+		/// Token was burned and removed from all of its owners. \[token\]
+		Burned((T::ClassId, T::TokenId)),
+		/// Token metadata was updated. \[token\]
+		TokenMetadataUpdated((T::ClassId, T::TokenId)),
+		/// Token shares were transferred.
+		/// \[from, to, token, shares, sender_shares_after, recipient_shares_after\]
+		Transferred(
+			T::AccountId,
+			T::AccountId,
+			(T::ClassId, T::TokenId),
+			T::Shares,
+			T::Shares,
+			T::Shares,
+		),
+		/// Token was locked against transfers and burns. \[token\]
+		Locked((T::ClassId, T::TokenId)),
+		/// Token was unlocked. \[token\]
+		Unlocked((T::ClassId, T::TokenId)),
+		/// Class royalty was updated. \[class_id\]
+		ClassRoyaltyUpdated(T::ClassId),
+		/// Token royalty override was updated. \[token\]
+		TokenRoyaltyUpdated((T::ClassId, T::TokenId)),
+		/// Royalty of a token sale was paid. \[token, beneficiary, amount\]
+		RoyaltyPaid((T::ClassId, T::TokenId), T::AccountId, BalanceOf<T>),
+		/// Owner approved a delegate to transfer their share of a token. \[owner, token, delegate\]
+		Approved(T::AccountId, (T::ClassId, T::TokenId), T::AccountId),
+		/// Owner cancelled the approval for a token. \[owner, token\]
+		ApprovalCancelled(T::AccountId, (T::ClassId, T::TokenId)),
+		/// Owner (dis)allowed an operator for all their tokens. \[owner, operator, approved\]
+		OperatorSet(T::AccountId, T::AccountId, bool),
+		/// Proceeds of a token sale could not be paid and were left reserved by the buyer.
+		/// \[token, seller, buyer, amount\]
+		ProceedsUnclaimed(
+			(T::ClassId, T::TokenId),
+			T::AccountId,
+			T::AccountId,
+			BalanceOf<T>,
+		),
+		/// Seller claimed the proceeds a buyer owed them. \[seller, buyer, amount\]
+		ProceedsClaimed(T::AccountId, T::AccountId, BalanceOf<T>),
 	}
 
 	/// Next available class ID.
@@ -148,14 +380,36 @@ pub mod module {
 		StorageDoubleMap<_, Twox64Concat, T::ClassId, Twox64Concat, T::TokenId, TokenInfoOf<T>>;
 
 	#[derive(Default, Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
-	pub struct TokenByOwnerData {
-		pub percent_owned: u8,
+	pub struct TokenByOwnerData<Shares> {
+		pub shares: Shares,
 	}
 
+	pub type TokenByOwnerDataOf<T> = TokenByOwnerData<<T as Config>::Shares>;
+
+	/// Storage layout version of the pallet.
+	#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug)]
+	pub enum Releases {
+		/// `TokensByOwner` stores an `u8` percentage
+		V1_0_0,
+		/// `TokensByOwner` stores `T::Shares`
+		V2_0_0,
+	}
+
+	impl Default for Releases {
+		fn default() -> Self {
+			Releases::V1_0_0
+		}
+	}
+
+	/// Storage version of the pallet.
+	///
+	/// New networks start with the last version.
+	#[pallet::storage]
+	pub(super) type StorageVersion<T: Config> = StorageValue<_, Releases, ValueQuery>;
+
 	/// Token existence check by owner and class ID.
 	// TODO: pallet macro doesn't support conditional compiling. Always having `TokensByOwner` storage doesn't hurt but
-	// it could be removed once conditional compiling supported.
-	// #[cfg(not(feature = "disable-tokens-by-owner"))]
+	// it could be removed once conditional compiling supported. With `disable-tokens-by-owner` it stays empty.
 	#[pallet::storage]
 	#[pallet::getter(fn tokens_by_owner)]
 	pub type TokensByOwner<T: Config> = StorageDoubleMap<
@@ -164,7 +418,81 @@ pub mod module {
 		T::AccountId,
 		Twox64Concat,
 		(T::ClassId, T::TokenId),
-		TokenByOwnerData,
+		TokenByOwnerDataOf<T>,
+		ValueQuery,
+	>;
+
+	/// Deposit reserved from the class owner.
+	#[pallet::storage]
+	#[pallet::getter(fn class_deposits)]
+	pub type ClassDeposits<T: Config> =
+		StorageMap<_, Twox64Concat, T::ClassId, BalanceOf<T>, ValueQuery>;
+
+	/// Depositor and deposit reserved for a token.
+	#[pallet::storage]
+	#[pallet::getter(fn token_deposits)]
+	pub type TokenDeposits<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::ClassId,
+		Twox64Concat,
+		T::TokenId,
+		(T::AccountId, BalanceOf<T>),
+	>;
+
+	/// Account approved by an owner to transfer their share of a token.
+	#[pallet::storage]
+	#[pallet::getter(fn approvals)]
+	pub type Approvals<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		(T::ClassId, T::TokenId),
+		T::AccountId,
+	>;
+
+	/// Operators allowed to transfer any token share held by an owner.
+	#[pallet::storage]
+	#[pallet::getter(fn operators)]
+	pub type Operators<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		T::AccountId,
+		bool,
+		ValueQuery,
+	>;
+
+	/// Tokens locked against transfers and burns.
+	#[pallet::storage]
+	#[pallet::getter(fn locked_tokens)]
+	pub type LockedTokens<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::ClassId, Twox64Concat, T::TokenId, bool, ValueQuery>;
+
+	/// Royalty paid on sales of the tokens of a class.
+	#[pallet::storage]
+	#[pallet::getter(fn class_royalties)]
+	pub type ClassRoyalties<T: Config> =
+		StorageMap<_, Twox64Concat, T::ClassId, RoyaltyOf<T>, ValueQuery>;
+
+	/// Royalty of a token overriding the royalty of its class.
+	#[pallet::storage]
+	#[pallet::getter(fn token_royalties)]
+	pub type TokenRoyalties<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::ClassId, Twox64Concat, T::TokenId, RoyaltyOf<T>>;
+
+	/// Sale proceeds left reserved by a buyer until the seller claims them.
+	#[pallet::storage]
+	#[pallet::getter(fn unclaimed_proceeds)]
+	pub type UnclaimedProceeds<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		T::AccountId,
+		BalanceOf<T>,
 		ValueQuery,
 	>;
 
@@ -183,15 +511,18 @@ pub mod module {
 	#[pallet::genesis_build]
 	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
 		fn build(&self) {
+			StorageVersion::<T>::put(Releases::V2_0_0);
+
+			// genesis storage has no transactions, see `do_create_class` and `do_mint`
 			self.tokens.iter().for_each(|token_class| {
-				let class_id = Pallet::<T>::create_class(
+				let class_id = Pallet::<T>::do_create_class(
 					&token_class.0,
 					token_class.1.to_vec(),
 					token_class.2.clone(),
 				)
 				.expect("Create class cannot fail while building genesis");
 				for (account_id, token_metadata, token_data) in &token_class.3 {
-					Pallet::<T>::mint(
+					Pallet::<T>::do_mint(
 						&account_id,
 						class_id,
 						token_metadata.to_vec(),
@@ -207,7 +538,15 @@ pub mod module {
 	pub struct Pallet<T>(PhantomData<T>);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::<T>::get() == Releases::V1_0_0 {
+				migrations::migrate_to_v2::<T>()
+			} else {
+				0
+			}
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {}
@@ -215,11 +554,23 @@ pub mod module {
 
 impl<T: Config> Pallet<T> {
 	/// Create NFT(non fungible token) class
+	#[transactional]
 	pub fn create_class(
 		owner: &T::AccountId,
 		metadata: Vec<u8>,
 		data: T::ClassData,
 	) -> Result<T::ClassId, DispatchError> {
+		Self::do_create_class(owner, metadata, data)
+	}
+
+	/// Create a class without a storage transaction, storage is left changed on failure
+	fn do_create_class(
+		owner: &T::AccountId,
+		metadata: Vec<u8>,
+		data: T::ClassData,
+	) -> Result<T::ClassId, DispatchError> {
+		Self::ensure_metadata_length(&metadata)?;
+
 		let class_id = NextClassId::<T>::try_mutate(|id| -> Result<T::ClassId, DispatchError> {
 			let current_id = *id;
 			*id = id
@@ -228,6 +579,10 @@ impl<T: Config> Pallet<T> {
 			Ok(current_id)
 		})?;
 
+		let deposit = Self::metadata_deposit(T::ClassDeposit::get(), &metadata);
+		T::Currency::reserve(owner, deposit)?;
+		ClassDeposits::<T>::insert(class_id, deposit);
+
 		let info = ClassInfo {
 			metadata,
 			total_issuance: Default::default(),
@@ -236,67 +591,84 @@ impl<T: Config> Pallet<T> {
 		};
 		Classes::<T>::insert(class_id, info);
 
+		Self::deposit_event(Event::ClassCreated(owner.clone(), class_id));
 		Ok(class_id)
 	}
 
 	/// Transfer NFT(non fungible token) `from` account `to` account
+	#[transactional]
 	pub fn transfer(
 		from: &T::AccountId,
 		to: &T::AccountId,
 		token: (T::ClassId, T::TokenId),
-		percentage: u8,
+		shares: T::Shares,
+	) -> DispatchResult {
+		if from == to {
+			return Ok(());
+		}
+
+		ensure!(!Self::is_locked(token), Error::<T>::TokenLocked);
+
+		Self::transfer_locked(from, to, token, shares)
+	}
+
+	/// Transfer shares of `token` even while it is locked, for the pallet holding the lock
+	#[transactional]
+	pub fn transfer_locked(
+		from: &T::AccountId,
+		to: &T::AccountId,
+		token: (T::ClassId, T::TokenId),
+		shares: T::Shares,
 	) -> DispatchResult {
 		if from == to {
 			return Ok(());
 		}
 
-		ensure!(percentage > 0, Error::<T>::WrongArguments);
+		ensure!(!shares.is_zero(), Error::<T>::WrongArguments);
 
 		Tokens::<T>::try_mutate(token.0, token.1, |token_info| -> DispatchResult {
 			let token_info_value = token_info.as_mut().ok_or(Error::<T>::TokenNotFound)?;
 
 			ensure!(
-				token_info_value.owners.contains(from),
+				Self::owner_position(token_info_value, from).is_ok(),
 				Error::<T>::NoPermission
 			);
 
-			TokensByOwner::<T>::try_mutate_exists(from, token, |sender_token| -> DispatchResult {
-				let sender_token_value = sender_token
-					.as_mut()
-					.ok_or(Error::<T>::SenderInsufficientPercentage)?;
-
-				ensure!(
-					sender_token_value.percent_owned >= percentage,
-					Error::<T>::SenderInsufficientPercentage
-				);
-
-				sender_token_value.percent_owned -= percentage;
-				if sender_token_value.percent_owned == 0 {
-					// remove sender from TokensByOwner if precent_owned is 0
-					*sender_token = None;
-					// remove sender from token.owners
-					anmol_utils::remove_vector_item(&mut token_info_value.owners, from)?;
-				}
+			Self::move_shares(token_info_value, from, to, token, shares)
+		})?;
 
-				TokensByOwner::<T>::mutate(to, token, |recipient_token| -> DispatchResult {
-					recipient_token.percent_owned += percentage;
-					if let Err(pos) = token_info_value.owners.binary_search(&to) {
-						let owners_token = to.clone();
-						token_info_value.owners.insert(pos, owners_token)
-					}
-					Ok(())
-				})
-			})
-		})
+		Self::deposit_event(Event::Transferred(
+			from.clone(),
+			to.clone(),
+			token,
+			shares,
+			Self::shares_of(from, token),
+			Self::shares_of(to, token),
+		));
+		T::OnTokenChange::on_transfer(from, to, token, shares);
+		Ok(())
 	}
 
 	/// Mint NFT(non fungible token) to `owner`
+	#[transactional]
 	pub fn mint(
 		owner: &T::AccountId,
 		class_id: T::ClassId,
 		metadata: Vec<u8>,
 		data: T::TokenData,
 	) -> Result<T::TokenId, DispatchError> {
+		Self::do_mint(owner, class_id, metadata, data)
+	}
+
+	/// Mint a token without a storage transaction, storage is left changed on failure
+	fn do_mint(
+		owner: &T::AccountId,
+		class_id: T::ClassId,
+		metadata: Vec<u8>,
+		data: T::TokenData,
+	) -> Result<T::TokenId, DispatchError> {
+		Self::ensure_metadata_length(&metadata)?;
+
 		NextTokenId::<T>::try_mutate(class_id, |id| -> Result<T::TokenId, DispatchError> {
 			let token_id = *id;
 			*id = id
@@ -312,9 +684,19 @@ impl<T: Config> Pallet<T> {
 				Ok(())
 			})?;
 
+			let deposit = Self::metadata_deposit(T::TokenDeposit::get(), &metadata);
+			T::Currency::reserve(owner, deposit)?;
+			TokenDeposits::<T>::insert(class_id, token_id, (owner.clone(), deposit));
+
+			// By default, minter gets 100% ownership
+			#[cfg(not(feature = "disable-tokens-by-owner"))]
+			let owners = [owner.clone()].to_vec();
+			#[cfg(feature = "disable-tokens-by-owner")]
+			let owners = [(owner.clone(), T::SharesPerToken::get())].to_vec();
+
 			let token_info = TokenInfo {
 				metadata,
-				owners: [owner.clone()].to_vec(),
+				owners,
 				data,
 			};
 
@@ -323,34 +705,98 @@ impl<T: Config> Pallet<T> {
 			TokensByOwner::<T>::insert(
 				owner,
 				(class_id, token_id),
-				// By default, minter gets 100% ownership
-				TokenByOwnerData { percent_owned: 100 },
+				TokenByOwnerData {
+					shares: T::SharesPerToken::get(),
+				},
 			);
 
+			Self::deposit_event(Event::Minted(owner.clone(), (class_id, token_id)));
 			Ok(token_id)
 		})
 	}
 
 	/// Burn NFT(non fungible token) from `owner`
+	///
+	/// `owner` must hold every share of the token, co-owned tokens are burned with
+	/// `burn_with_approvals`.
 	pub fn burn(owner: &T::AccountId, token: (T::ClassId, T::TokenId)) -> DispatchResult {
-		Tokens::<T>::try_mutate_exists(token.0, token.1, |token_info| -> DispatchResult {
-			let t = token_info.take().ok_or(Error::<T>::TokenNotFound)?;
-			ensure!(t.owners.contains(owner), Error::<T>::NoPermission);
+		let t = Tokens::<T>::get(token.0, token.1).ok_or(Error::<T>::TokenNotFound)?;
+		ensure!(
+			Self::owner_position(&t, owner).is_ok(),
+			Error::<T>::NoPermission
+		);
+		ensure!(
+			Self::shares_of(owner, token) == T::SharesPerToken::get(),
+			Error::<T>::BurnNotApproved
+		);
 
-			Classes::<T>::try_mutate(token.0, |class_info| -> DispatchResult {
-				let info = class_info.as_mut().ok_or(Error::<T>::ClassNotFound)?;
-				info.total_issuance = info
-					.total_issuance
-					.checked_sub(&One::one())
-					.ok_or(Error::<T>::NumOverflow)?;
-				Ok(())
-			})?;
+		Self::do_burn(token, t)
+	}
 
-			#[cfg(not(feature = "disable-tokens-by-owner"))]
-			TokensByOwner::<T>::remove(owner, token);
+	/// Burn co-owned NFT(non fungible token) approved by `approvers`
+	///
+	/// The approvers must together hold at least `BurnApprovalThreshold` of the token shares.
+	pub fn burn_with_approvals(
+		approvers: &[T::AccountId],
+		token: (T::ClassId, T::TokenId),
+	) -> DispatchResult {
+		let t = Tokens::<T>::get(token.0, token.1).ok_or(Error::<T>::TokenNotFound)?;
+		ensure!(
+			Self::is_burn_approved(approvers, token),
+			Error::<T>::BurnNotApproved
+		);
 
+		Self::do_burn(token, t)
+	}
+
+	/// Whether `approvers` hold enough shares of `token` to burn it
+	pub fn is_burn_approved(approvers: &[T::AccountId], token: (T::ClassId, T::TokenId)) -> bool {
+		let mut approvers = approvers.to_vec();
+		approvers.sort();
+		approvers.dedup();
+
+		let approved_shares = approvers.iter().fold(Zero::zero(), |acc: T::Shares, who| {
+			acc.saturating_add(Self::shares_of(who, token))
+		});
+
+		!approved_shares.is_zero()
+			&& approved_shares >= T::BurnApprovalThreshold::get() * T::SharesPerToken::get()
+	}
+
+	/// Remove the token and the ownership records of all of its owners
+	fn do_burn(token: (T::ClassId, T::TokenId), token_info: TokenInfoOf<T>) -> DispatchResult {
+		ensure!(!Self::is_locked(token), Error::<T>::TokenLocked);
+
+		let owners: Vec<(T::AccountId, T::Shares)> = token_info
+			.owners
+			.iter()
+			.map(|owner| Self::owner_with_shares(owner.clone(), token))
+			.collect();
+
+		Classes::<T>::try_mutate(token.0, |class_info| -> DispatchResult {
+			let info = class_info.as_mut().ok_or(Error::<T>::ClassNotFound)?;
+			info.total_issuance = info
+				.total_issuance
+				.checked_sub(&One::one())
+				.ok_or(Error::<T>::NumOverflow)?;
 			Ok(())
-		})
+		})?;
+
+		Tokens::<T>::remove(token.0, token.1);
+		TokenRoyalties::<T>::remove(token.0, token.1);
+		if let Some((depositor, deposit)) = TokenDeposits::<T>::take(token.0, token.1) {
+			T::Currency::unreserve(&depositor, deposit);
+		}
+		for owner in token_info.owners.iter() {
+			let account = Self::owner_account(owner);
+			#[cfg(not(feature = "disable-tokens-by-owner"))]
+			TokensByOwner::<T>::remove(account, token);
+			Approvals::<T>::remove(account, token);
+		}
+
+		Self::deposit_event(Event::Burned(token));
+		T::OnTokenChange::on_burn(token, &owners);
+		Ok(())
 	}
 
 	/// Destroy NFT(non fungible token) class
@@ -364,16 +810,583 @@ impl<T: Config> Pallet<T> {
 			);
 
 			NextTokenId::<T>::remove(class_id);
+			ClassRoyalties::<T>::remove(class_id);
+			T::Currency::unreserve(owner, ClassDeposits::<T>::take(class_id));
 
+			Self::deposit_event(Event::ClassDestroyed(owner.clone(), class_id));
 			Ok(())
 		})
 	}
 
-	pub fn is_owner(account: &T::AccountId, token: (T::ClassId, T::TokenId)) -> bool {
-		#[cfg(feature = "disable-tokens-by-owner")]
-		return Tokens::<T>::get(token.0, token.1).map_or(false, |token| token.owner == *account);
+	/// Update metadata of the class owned by `owner`
+	///
+	/// The class deposit is adjusted to the new metadata length.
+	#[transactional]
+	pub fn set_class_metadata(
+		owner: &T::AccountId,
+		class_id: T::ClassId,
+		metadata: Vec<u8>,
+	) -> DispatchResult {
+		Self::ensure_metadata_length(&metadata)?;
+
+		Classes::<T>::try_mutate(class_id, |class_info| -> DispatchResult {
+			let info = class_info.as_mut().ok_or(Error::<T>::ClassNotFound)?;
+			ensure!(info.owner == *owner, Error::<T>::NoPermission);
+
+			let old_deposit = ClassDeposits::<T>::get(class_id);
+			let new_deposit = Self::metadata_deposit(T::ClassDeposit::get(), &metadata);
+			if new_deposit > old_deposit {
+				T::Currency::reserve(owner, new_deposit - old_deposit)?;
+			} else {
+				T::Currency::unreserve(owner, old_deposit - new_deposit);
+			}
+			ClassDeposits::<T>::insert(class_id, new_deposit);
+
+			info.metadata = metadata;
+			Ok(())
+		})?;
+
+		Self::deposit_event(Event::ClassMetadataUpdated(class_id));
+		Ok(())
+	}
+
+	/// Update metadata of the token, `owner` must hold every share of it
+	///
+	/// `owner` becomes the depositor of the token, the previous deposit is refunded.
+	#[transactional]
+	pub fn set_token_metadata(
+		owner: &T::AccountId,
+		token: (T::ClassId, T::TokenId),
+		metadata: Vec<u8>,
+	) -> DispatchResult {
+		Self::ensure_metadata_length(&metadata)?;
+
+		Tokens::<T>::try_mutate(token.0, token.1, |token_info| -> DispatchResult {
+			let info = token_info.as_mut().ok_or(Error::<T>::TokenNotFound)?;
+			ensure!(
+				Self::shares_of(owner, token) == T::SharesPerToken::get(),
+				Error::<T>::NoPermission
+			);
+
+			if let Some((depositor, deposit)) = TokenDeposits::<T>::take(token.0, token.1) {
+				T::Currency::unreserve(&depositor, deposit);
+			}
+			let deposit = Self::metadata_deposit(T::TokenDeposit::get(), &metadata);
+			T::Currency::reserve(owner, deposit)?;
+			TokenDeposits::<T>::insert(token.0, token.1, (owner.clone(), deposit));
+
+			info.metadata = metadata;
+			Ok(())
+		})?;
+
+		Self::deposit_event(Event::TokenMetadataUpdated(token));
+		Ok(())
+	}
+
+	/// Set the royalty of the tokens of the class owned by `owner`, an empty royalty removes it
+	pub fn set_class_royalty(
+		owner: &T::AccountId,
+		class_id: T::ClassId,
+		royalty: RoyaltyOf<T>,
+	) -> DispatchResult {
+		Self::ensure_valid_royalty(&royalty)?;
+
+		let info = Classes::<T>::get(class_id).ok_or(Error::<T>::ClassNotFound)?;
+		ensure!(info.owner == *owner, Error::<T>::NoPermission);
+
+		if royalty.is_empty() {
+			ClassRoyalties::<T>::remove(class_id);
+		} else {
+			ClassRoyalties::<T>::insert(class_id, royalty);
+		}
+
+		Self::deposit_event(Event::ClassRoyaltyUpdated(class_id));
+		Ok(())
+	}
+
+	/// Override the royalty of `token` in the class owned by `owner`
+	///
+	/// `None` removes the override, the class royalty applies again.
+	pub fn set_token_royalty(
+		owner: &T::AccountId,
+		token: (T::ClassId, T::TokenId),
+		royalty: Option<RoyaltyOf<T>>,
+	) -> DispatchResult {
+		if let Some(royalty) = &royalty {
+			Self::ensure_valid_royalty(royalty)?;
+		}
+
+		let info = Classes::<T>::get(token.0).ok_or(Error::<T>::ClassNotFound)?;
+		ensure!(info.owner == *owner, Error::<T>::NoPermission);
+		ensure!(
+			Tokens::<T>::contains_key(token.0, token.1),
+			Error::<T>::TokenNotFound
+		);
+
+		match royalty {
+			Some(royalty) => TokenRoyalties::<T>::insert(token.0, token.1, royalty),
+			None => TokenRoyalties::<T>::remove(token.0, token.1),
+		}
+
+		Self::deposit_event(Event::TokenRoyaltyUpdated(token));
+		Ok(())
+	}
+
+	/// Royalty paid on sales of `token`
+	pub fn royalty_of(token: (T::ClassId, T::TokenId)) -> RoyaltyOf<T> {
+		TokenRoyalties::<T>::get(token.0, token.1)
+			.unwrap_or_else(|| ClassRoyalties::<T>::get(token.0))
+	}
+
+	/// Royalties owed to each beneficiary when `token` is sold for `price`
+	pub fn royalties_for(
+		token: (T::ClassId, T::TokenId),
+		price: BalanceOf<T>,
+	) -> Vec<(T::AccountId, BalanceOf<T>)> {
+		Self::royalty_amounts(&Self::royalty_of(token), price)
+	}
+
+	/// Amount owed to each beneficiary of `royalty` for `price`
+	fn royalty_amounts(
+		royalty: &[(T::AccountId, Perbill)],
+		price: BalanceOf<T>,
+	) -> Vec<(T::AccountId, BalanceOf<T>)> {
+		royalty
+			.iter()
+			.map(|(beneficiary, rate)| (beneficiary.clone(), *rate * price))
+			.filter(|(_, amount)| !amount.is_zero())
+			.collect()
+	}
+
+	/// Pay `royalty` of a sale of `token` for `price` from the free balance of `buyer`
+	///
+	/// Returns the amount paid, the seller is owed the rest of the price.
+	#[transactional]
+	pub fn pay_royalties(
+		buyer: &T::AccountId,
+		token: (T::ClassId, T::TokenId),
+		royalty: &[(T::AccountId, Perbill)],
+		price: BalanceOf<T>,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		let mut paid: BalanceOf<T> = Zero::zero();
+		for (beneficiary, amount) in Self::royalty_amounts(royalty, price) {
+			if !Self::can_receive(&beneficiary, amount) {
+				continue;
+			}
+			T::Currency::transfer(buyer, &beneficiary, amount, ExistenceRequirement::KeepAlive)?;
+			paid = paid.saturating_add(amount);
+			Self::deposit_event(Event::RoyaltyPaid(token, beneficiary, amount));
+		}
+		Ok(paid)
+	}
+
+	/// Pay `royalty` of a sale of `token` for `price` from the reserved balance of `buyer`
+	///
+	/// Returns the amount paid, the seller is owed the rest of the price.
+	pub fn repatriate_royalties(
+		buyer: &T::AccountId,
+		token: (T::ClassId, T::TokenId),
+		royalty: &[(T::AccountId, Perbill)],
+		price: BalanceOf<T>,
+	) -> BalanceOf<T> {
+		let mut paid: BalanceOf<T> = Zero::zero();
+		for (beneficiary, amount) in Self::royalty_amounts(royalty, price) {
+			if !Self::can_receive(&beneficiary, amount) {
+				continue;
+			}
+			let amount = match T::Currency::repatriate_reserved(
+				buyer,
+				&beneficiary,
+				amount,
+				BalanceStatus::Free,
+			) {
+				Ok(unpaid) => amount.saturating_sub(unpaid),
+				Err(_) => continue,
+			};
+			paid = paid.saturating_add(amount);
+			Self::deposit_event(Event::RoyaltyPaid(token, beneficiary, amount));
+		}
+		paid
+	}
+
+	/// Pay `seller` the `amount` reserved by `buyer` for shares of `token`
+	///
+	/// Proceeds that cannot be paid, e.g. when they are too small to create the account of
+	/// `seller`, stay reserved by `buyer` until `seller` claims them.
+	pub fn repatriate_proceeds(
+		buyer: &T::AccountId,
+		seller: &T::AccountId,
+		token: (T::ClassId, T::TokenId),
+		amount: BalanceOf<T>,
+	) {
+		if amount.is_zero() {
+			return;
+		}
+		if T::Currency::repatriate_reserved(buyer, seller, amount, BalanceStatus::Free).is_err() {
+			UnclaimedProceeds::<T>::mutate(seller, buyer, |unclaimed| {
+				*unclaimed = unclaimed.saturating_add(amount)
+			});
+			Self::deposit_event(Event::ProceedsUnclaimed(
+				token,
+				seller.clone(),
+				buyer.clone(),
+				amount,
+			));
+		}
+	}
+
+	/// Pay `seller` the proceeds `buyer` could not pay them, e.g. once `seller` has an account
+	pub fn claim_proceeds(seller: &T::AccountId, buyer: &T::AccountId) -> DispatchResult {
+		let amount = UnclaimedProceeds::<T>::get(seller, buyer);
+		ensure!(!amount.is_zero(), Error::<T>::NoUnclaimedProceeds);
+		let unpaid = T::Currency::repatriate_reserved(buyer, seller, amount, BalanceStatus::Free)?;
+		UnclaimedProceeds::<T>::remove(seller, buyer);
+		Self::deposit_event(Event::ProceedsClaimed(
+			seller.clone(),
+			buyer.clone(),
+			amount.saturating_sub(unpaid),
+		));
+		Ok(())
+	}
+
+	/// Royalties too small to create the account of their beneficiary are left to the seller
+	fn can_receive(beneficiary: &T::AccountId, amount: BalanceOf<T>) -> bool {
+		amount >= T::Currency::minimum_balance()
+			|| !T::Currency::total_balance(beneficiary).is_zero()
+	}
+
+	fn ensure_valid_royalty(royalty: &[(T::AccountId, Perbill)]) -> DispatchResult {
+		ensure!(
+			royalty.len() as u32 <= T::MaxRoyaltyBeneficiaries::get(),
+			Error::<T>::TooManyRoyaltyBeneficiaries
+		);
+		let total = royalty
+			.iter()
+			.map(|(_, rate)| rate.deconstruct() as u64)
+			.sum::<u64>();
+		ensure!(
+			total <= T::MaxRoyalty::get().deconstruct() as u64,
+			Error::<T>::RoyaltyTooHigh
+		);
+		Ok(())
+	}
+
+	fn ensure_metadata_length(metadata: &[u8]) -> DispatchResult {
+		ensure!(
+			metadata.len() as u32 <= T::MaxMetadataLength::get(),
+			Error::<T>::MetadataTooLong
+		);
+		Ok(())
+	}
+
+	/// Deposit reserved for storing `metadata` on top of `base`
+	fn metadata_deposit(base: BalanceOf<T>, metadata: &[u8]) -> BalanceOf<T> {
+		let bytes: BalanceOf<T> = (metadata.len() as u32).into();
+		base.saturating_add(T::DepositPerByte::get().saturating_mul(bytes))
+	}
+
+	/// Move `shares` of `token` from `from` to `to`, updating `TokensByOwner`
+	#[cfg(not(feature = "disable-tokens-by-owner"))]
+	fn move_shares(
+		token_info: &mut TokenInfoOf<T>,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		token: (T::ClassId, T::TokenId),
+		shares: T::Shares,
+	) -> DispatchResult {
+		TokensByOwner::<T>::try_mutate_exists(from, token, |sender_token| -> DispatchResult {
+			let sender_token_value = sender_token
+				.as_mut()
+				.ok_or(Error::<T>::SenderInsufficientPercentage)?;
+
+			ensure!(
+				sender_token_value.shares >= shares,
+				Error::<T>::SenderInsufficientPercentage
+			);
+
+			sender_token_value.shares -= shares;
+			if sender_token_value.shares.is_zero() {
+				// remove sender from TokensByOwner if shares is 0
+				*sender_token = None;
+				// approvals granted by the sender are void once nothing is left to transfer
+				Approvals::<T>::remove(from, token);
+				// remove sender from token.owners
+				anmol_utils::remove_vector_item(&mut token_info.owners, from)?;
+			}
+
+			TokensByOwner::<T>::try_mutate(to, token, |recipient_token| -> DispatchResult {
+				recipient_token.shares = recipient_token
+					.shares
+					.checked_add(&shares)
+					.ok_or(Error::<T>::NumOverflow)?;
+				if let Err(pos) = token_info.owners.binary_search(&to) {
+					ensure!(
+						(token_info.owners.len() as u32) < T::MaxOwnersPerToken::get(),
+						Error::<T>::TooManyOwners
+					);
+					let owners_token = to.clone();
+					token_info.owners.insert(pos, owners_token)
+				}
+				Ok(())
+			})
+		})
+	}
+
+	/// Move `shares` of `token` from `from` to `to` inside `token_info`
+	#[cfg(feature = "disable-tokens-by-owner")]
+	fn move_shares(
+		token_info: &mut TokenInfoOf<T>,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		token: (T::ClassId, T::TokenId),
+		shares: T::Shares,
+	) -> DispatchResult {
+		let sender_pos = Self::owner_position(token_info, from)
+			.map_err(|_| Error::<T>::SenderInsufficientPercentage)?;
+		let sender_shares = &mut token_info.owners[sender_pos].1;
+
+		ensure!(
+			*sender_shares >= shares,
+			Error::<T>::SenderInsufficientPercentage
+		);
+
+		*sender_shares -= shares;
+		if sender_shares.is_zero() {
+			token_info.owners.remove(sender_pos);
+			// approvals granted by the sender are void once nothing is left to transfer
+			Approvals::<T>::remove(from, token);
+		}
+
+		match Self::owner_position(token_info, to) {
+			Ok(pos) => {
+				let recipient_shares = &mut token_info.owners[pos].1;
+				*recipient_shares = recipient_shares
+					.checked_add(&shares)
+					.ok_or(Error::<T>::NumOverflow)?;
+			}
+			Err(pos) => {
+				ensure!(
+					(token_info.owners.len() as u32) < T::MaxOwnersPerToken::get(),
+					Error::<T>::TooManyOwners
+				);
+				token_info.owners.insert(pos, (to.clone(), shares));
+			}
+		}
+		Ok(())
+	}
+
+	/// Account of an entry of `TokenInfo::owners`
+	#[cfg(not(feature = "disable-tokens-by-owner"))]
+	fn owner_account(owner: &TokenOwnerOf<T>) -> &T::AccountId {
+		owner
+	}
+
+	/// Account of an entry of `TokenInfo::owners`
+	#[cfg(feature = "disable-tokens-by-owner")]
+	fn owner_account(owner: &TokenOwnerOf<T>) -> &T::AccountId {
+		&owner.0
+	}
+
+	/// Position of `account` in the sorted `TokenInfo::owners`, or where it would be inserted
+	fn owner_position(token_info: &TokenInfoOf<T>, account: &T::AccountId) -> Result<usize, usize> {
+		token_info
+			.owners
+			.binary_search_by(|owner| Self::owner_account(owner).cmp(account))
+	}
+
+	/// Accounts holding shares of `token_info`
+	pub fn owner_accounts(token_info: &TokenInfoOf<T>) -> Vec<T::AccountId> {
+		token_info
+			.owners
+			.iter()
+			.map(|owner| Self::owner_account(owner).clone())
+			.collect()
+	}
+
+	/// Shares of `token` held by `account`
+	#[cfg(not(feature = "disable-tokens-by-owner"))]
+	pub fn shares_of(account: &T::AccountId, token: (T::ClassId, T::TokenId)) -> T::Shares {
+		TokensByOwner::<T>::get(account, token).shares
+	}
+
+	/// Shares of `token` held by `account`
+	#[cfg(feature = "disable-tokens-by-owner")]
+	pub fn shares_of(account: &T::AccountId, token: (T::ClassId, T::TokenId)) -> T::Shares {
+		Tokens::<T>::get(token.0, token.1)
+			.and_then(|info| {
+				Self::owner_position(&info, account)
+					.ok()
+					.map(|pos| info.owners[pos].1)
+			})
+			.unwrap_or_else(Zero::zero)
+	}
+
+	/// Owners of `token` with their shares, `limit` owners skipping the first `offset`
+	pub fn owners_of_token(
+		token: (T::ClassId, T::TokenId),
+		offset: u32,
+		limit: u32,
+	) -> Vec<(T::AccountId, T::Shares)> {
+		Tokens::<T>::get(token.0, token.1).map_or_else(Vec::new, |info| {
+			info.owners
+				.into_iter()
+				.skip(offset as usize)
+				.take(limit as usize)
+				.map(|owner| Self::owner_with_shares(owner, token))
+				.collect()
+		})
+	}
+
+	#[cfg(not(feature = "disable-tokens-by-owner"))]
+	fn owner_with_shares(
+		owner: TokenOwnerOf<T>,
+		token: (T::ClassId, T::TokenId),
+	) -> (T::AccountId, T::Shares) {
+		let shares = Self::shares_of(&owner, token);
+		(owner, shares)
+	}
+
+	#[cfg(feature = "disable-tokens-by-owner")]
+	fn owner_with_shares(
+		owner: TokenOwnerOf<T>,
+		_token: (T::ClassId, T::TokenId),
+	) -> (T::AccountId, T::Shares) {
+		owner
+	}
+
+	/// Tokens held by `owner` with their shares, `limit` tokens skipping the first `offset`
+	pub fn tokens_of_owner(owner: &T::AccountId, offset: u32, limit: u32) -> Vec<OwnedTokenOf<T>> {
+		Self::owned_tokens(owner)
+			.skip(offset as usize)
+			.take(limit as usize)
+			.collect()
+	}
+
+	/// Tokens held by `owner` with their shares
+	#[cfg(not(feature = "disable-tokens-by-owner"))]
+	pub fn owned_tokens(owner: &T::AccountId) -> Box<dyn Iterator<Item = OwnedTokenOf<T>>> {
+		Box::new(TokensByOwner::<T>::iter_prefix(owner).map(|(token, data)| (token, data.shares)))
+	}
 
-		#[cfg(not(feature = "disable-tokens-by-owner"))]
+	/// Tokens held by `owner` with their shares
+	///
+	/// Iterates over every token as there is no `TokensByOwner` index.
+	#[cfg(feature = "disable-tokens-by-owner")]
+	pub fn owned_tokens(owner: &T::AccountId) -> Box<dyn Iterator<Item = OwnedTokenOf<T>>> {
+		let owner = owner.clone();
+		Box::new(
+			Tokens::<T>::iter().filter_map(move |(class_id, token_id, info)| {
+				Self::owner_position(&info, &owner)
+					.ok()
+					.map(|pos| ((class_id, token_id), info.owners[pos].1))
+			}),
+		)
+	}
+
+	#[cfg(not(feature = "disable-tokens-by-owner"))]
+	pub fn is_owner(account: &T::AccountId, token: (T::ClassId, T::TokenId)) -> bool {
 		TokensByOwner::<T>::contains_key(account, token)
 	}
+
+	#[cfg(feature = "disable-tokens-by-owner")]
+	pub fn is_owner(account: &T::AccountId, token: (T::ClassId, T::TokenId)) -> bool {
+		matches!(
+			Tokens::<T>::get(token.0, token.1),
+			Some(info) if Self::owner_position(&info, account).is_ok()
+		)
+	}
+
+	/// Approve `delegate` to transfer the share of `token` held by `owner`
+	pub fn approve(
+		owner: &T::AccountId,
+		token: (T::ClassId, T::TokenId),
+		delegate: &T::AccountId,
+	) -> DispatchResult {
+		ensure!(owner != delegate, Error::<T>::WrongArguments);
+		ensure!(
+			Tokens::<T>::contains_key(token.0, token.1),
+			Error::<T>::TokenNotFound
+		);
+		ensure!(Self::is_owner(owner, token), Error::<T>::NoPermission);
+
+		Approvals::<T>::insert(owner, token, delegate);
+
+		Self::deposit_event(Event::Approved(owner.clone(), token, delegate.clone()));
+		Ok(())
+	}
+
+	/// Cancel the approval `owner` granted for `token`
+	pub fn cancel_approval(
+		owner: &T::AccountId,
+		token: (T::ClassId, T::TokenId),
+	) -> DispatchResult {
+		ensure!(
+			Approvals::<T>::contains_key(owner, token),
+			Error::<T>::ApprovalNotFound
+		);
+
+		Approvals::<T>::remove(owner, token);
+
+		Self::deposit_event(Event::ApprovalCancelled(owner.clone(), token));
+		Ok(())
+	}
+
+	/// Allow or disallow `operator` to transfer any token share held by `owner`
+	pub fn set_operator(
+		owner: &T::AccountId,
+		operator: &T::AccountId,
+		approved: bool,
+	) -> DispatchResult {
+		ensure!(owner != operator, Error::<T>::WrongArguments);
+
+		if approved {
+			Operators::<T>::insert(owner, operator, true);
+		} else {
+			Operators::<T>::remove(owner, operator);
+		}
+
+		Self::deposit_event(Event::OperatorSet(
+			owner.clone(),
+			operator.clone(),
+			approved,
+		));
+		Ok(())
+	}
+
+	/// Whether `who` may move the share of `token` held by `owner`
+	pub fn is_approved_or_owner(
+		who: &T::AccountId,
+		owner: &T::AccountId,
+		token: (T::ClassId, T::TokenId),
+	) -> bool {
+		who == owner
+			|| Operators::<T>::get(owner, who)
+			|| Approvals::<T>::get(owner, token).as_ref() == Some(who)
+	}
+
+	/// Lock `token` against transfers and burns
+	pub fn lock(token: (T::ClassId, T::TokenId)) -> DispatchResult {
+		ensure!(
+			Tokens::<T>::contains_key(token.0, token.1),
+			Error::<T>::TokenNotFound
+		);
+		ensure!(!Self::is_locked(token), Error::<T>::TokenLocked);
+
+		LockedTokens::<T>::insert(token.0, token.1, true);
+
+		Self::deposit_event(Event::Locked(token));
+		Ok(())
+	}
+
+	/// Unlock `token`
+	pub fn unlock(token: (T::ClassId, T::TokenId)) {
+		if LockedTokens::<T>::take(token.0, token.1) {
+			Self::deposit_event(Event::Unlocked(token));
+		}
+	}
+
+	/// Whether `token` is locked against transfers and burns
+	pub fn is_locked(token: (T::ClassId, T::TokenId)) -> bool {
+		LockedTokens::<T>::get(token.0, token.1)
+	}
 }
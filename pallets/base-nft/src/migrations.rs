@@ -0,0 +1,46 @@
+//! Storage migrations for the base NFT module.
+
+use super::*;
+#[cfg(not(feature = "disable-tokens-by-owner"))]
+use sp_runtime::Perbill;
+
+/// `TokensByOwner` value before the `Shares` upgrade.
+#[cfg(not(feature = "disable-tokens-by-owner"))]
+#[derive(Decode)]
+struct OldTokenByOwnerData {
+	percent_owned: u8,
+}
+
+/// Convert the `u8` percentages of `TokensByOwner` into `T::Shares`.
+///
+/// A percentage is scaled to `T::SharesPerToken`, e.g. 20% of 1_000_000_000 shares becomes
+/// 200_000_000 shares.
+///
+/// Without the `TokensByOwner` index there is nothing to translate, the version is only bumped.
+pub fn migrate_to_v2<T: Config>() -> Weight {
+	#[cfg(not(feature = "disable-tokens-by-owner"))]
+	let translated = translate_percentages::<T>();
+	#[cfg(feature = "disable-tokens-by-owner")]
+	let translated: Weight = 0;
+
+	StorageVersion::<T>::put(Releases::V2_0_0);
+
+	T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+}
+
+/// Translate `TokensByOwner` values, returns the number of translated values
+#[cfg(not(feature = "disable-tokens-by-owner"))]
+fn translate_percentages<T: Config>() -> Weight {
+	let shares_per_token = T::SharesPerToken::get();
+	let mut translated: Weight = 0;
+
+	TokensByOwner::<T>::translate::<OldTokenByOwnerData, _>(|_, _, old| {
+		translated += 1;
+		let percent = old.percent_owned.min(100);
+		Some(TokenByOwnerData {
+			shares: Perbill::from_percent(percent.into()) * shares_per_token,
+		})
+	});
+
+	translated
+}
@@ -12,6 +12,16 @@ use crate as nft;
 
 parameter_types! {
 	pub const BlockHashCount: u64 = 250;
+	pub const SharesPerToken: u64 = 100;
+	pub const BurnApprovalThreshold: Perbill = Perbill::from_percent(75);
+	pub const ExistentialDeposit: u64 = 1;
+	pub const MaxMetadataLength: u32 = 16;
+	pub const MaxOwnersPerToken: u32 = 3;
+	pub const ClassDeposit: u64 = 10;
+	pub const TokenDeposit: u64 = 5;
+	pub const DepositPerByte: u64 = 1;
+	pub const MaxRoyaltyBeneficiaries: u32 = 2;
+	pub const MaxRoyalty: Perbill = Perbill::from_percent(50);
 }
 
 pub type AccountId = u128;
@@ -33,7 +43,7 @@ impl frame_system::Config for Runtime {
 	type BlockLength = ();
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type DbWeight = ();
@@ -42,11 +52,34 @@ impl frame_system::Config for Runtime {
 	type SS58Prefix = ();
 }
 
+impl pallet_balances::Config for Runtime {
+	type MaxLocks = ();
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
 impl Config for Runtime {
+	type Event = Event;
 	type ClassId = u64;
 	type TokenId = u64;
 	type ClassData = ();
 	type TokenData = ();
+	type Shares = u64;
+	type SharesPerToken = SharesPerToken;
+	type BurnApprovalThreshold = BurnApprovalThreshold;
+	type Currency = Balances;
+	type MaxMetadataLength = MaxMetadataLength;
+	type MaxOwnersPerToken = MaxOwnersPerToken;
+	type ClassDeposit = ClassDeposit;
+	type TokenDeposit = TokenDeposit;
+	type DepositPerByte = DepositPerByte;
+	type MaxRoyaltyBeneficiaries = MaxRoyaltyBeneficiaries;
+	type MaxRoyalty = MaxRoyalty;
+	type OnTokenChange = ();
 }
 
 pub type Block = sp_runtime::generic::Block<Header, UncheckedExtrinsic>;
@@ -59,18 +92,29 @@ construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Module, Call, Storage, Config, Event<T>},
-		NonFungibleTokenModule: nft::{Module, Storage, Config<T>},
+		Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
+		NonFungibleTokenModule: nft::{Module, Storage, Config<T>, Event<T>},
 
 	}
 );
 
 pub const ALICE: AccountId = 1;
 pub const BOB: AccountId = 2;
+pub const CHARLIE: AccountId = 3;
+pub const DAVE: AccountId = 4;
+pub const INITIAL_BALANCE: u64 = 100;
 pub const CLASS_ID: <Runtime as Config>::ClassId = 0;
 pub const CLASS_ID_NOT_EXIST: <Runtime as Config>::ClassId = 100;
 pub const TOKEN_ID: <Runtime as Config>::TokenId = 0;
 pub const TOKEN_ID_NOT_EXIST: <Runtime as Config>::TokenId = 100;
 
+pub fn last_event() -> Event {
+	frame_system::Pallet::<Runtime>::events()
+		.pop()
+		.expect("Event expected")
+		.event
+}
+
 pub struct ExtBuilder;
 
 impl Default for ExtBuilder {
@@ -81,10 +125,20 @@ impl Default for ExtBuilder {
 
 impl ExtBuilder {
 	pub fn build(self) -> sp_io::TestExternalities {
-		let t = frame_system::GenesisConfig::default()
+		let mut t = frame_system::GenesisConfig::default()
 			.build_storage::<Runtime>()
 			.unwrap();
 
+		pallet_balances::GenesisConfig::<Runtime> {
+			balances: vec![
+				(ALICE, INITIAL_BALANCE),
+				(BOB, INITIAL_BALANCE),
+				(CHARLIE, INITIAL_BALANCE),
+			],
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
 		let mut ext = sp_io::TestExternalities::new(t);
 		ext.execute_with(|| System::set_block_number(1));
 		ext
@@ -4,7 +4,37 @@
 
 use super::*;
 use frame_support::{assert_noop, assert_ok};
-use mock::*;
+use mock::{Event, *};
+
+#[test]
+fn genesis_config_should_work() {
+	let mut t = frame_system::GenesisConfig::default()
+		.build_storage::<Runtime>()
+		.unwrap();
+	pallet_balances::GenesisConfig::<Runtime> {
+		balances: vec![(ALICE, INITIAL_BALANCE), (BOB, INITIAL_BALANCE)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	crate::GenesisConfig::<Runtime> {
+		tokens: vec![(ALICE, vec![1], (), vec![(BOB, vec![2], ())])],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+
+	sp_io::TestExternalities::new(t).execute_with(|| {
+		assert_eq!(
+			NonFungibleTokenModule::classes(CLASS_ID).unwrap().owner,
+			ALICE
+		);
+		assert_eq!(
+			NonFungibleTokenModule::tokens(CLASS_ID, TOKEN_ID)
+				.unwrap()
+				.metadata,
+			vec![2]
+		);
+	});
+}
 
 #[test]
 fn create_class_should_work() {
@@ -301,3 +331,937 @@ fn destroy_class_should_fail() {
 		assert_eq!(Classes::<Runtime>::contains_key(CLASS_ID), false);
 	});
 }
+
+#[test]
+fn approve_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], ()));
+
+		assert_ok!(NonFungibleTokenModule::approve(
+			&BOB,
+			(CLASS_ID, TOKEN_ID),
+			&ALICE
+		));
+		assert_eq!(
+			NonFungibleTokenModule::approvals(BOB, (CLASS_ID, TOKEN_ID)),
+			Some(ALICE)
+		);
+		assert_eq!(
+			last_event(),
+			Event::nft(crate::Event::Approved(BOB, (CLASS_ID, TOKEN_ID), ALICE))
+		);
+		assert!(NonFungibleTokenModule::is_approved_or_owner(
+			&ALICE,
+			&BOB,
+			(CLASS_ID, TOKEN_ID)
+		));
+
+		assert_ok!(NonFungibleTokenModule::cancel_approval(
+			&BOB,
+			(CLASS_ID, TOKEN_ID)
+		));
+		assert_eq!(
+			last_event(),
+			Event::nft(crate::Event::ApprovalCancelled(BOB, (CLASS_ID, TOKEN_ID)))
+		);
+		assert_eq!(
+			NonFungibleTokenModule::is_approved_or_owner(&ALICE, &BOB, (CLASS_ID, TOKEN_ID)),
+			false
+		);
+	});
+}
+
+#[test]
+fn approve_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], ()));
+
+		assert_noop!(
+			NonFungibleTokenModule::approve(&BOB, (CLASS_ID, TOKEN_ID_NOT_EXIST), &ALICE),
+			Error::<Runtime>::TokenNotFound
+		);
+		assert_noop!(
+			NonFungibleTokenModule::approve(&ALICE, (CLASS_ID, TOKEN_ID), &BOB),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			NonFungibleTokenModule::approve(&BOB, (CLASS_ID, TOKEN_ID), &BOB),
+			Error::<Runtime>::WrongArguments
+		);
+		assert_noop!(
+			NonFungibleTokenModule::cancel_approval(&BOB, (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::ApprovalNotFound
+		);
+	});
+}
+
+#[test]
+fn approvals_cleared_when_share_is_gone() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::approve(
+			&BOB,
+			(CLASS_ID, TOKEN_ID),
+			&ALICE
+		));
+
+		assert_ok!(NonFungibleTokenModule::transfer(
+			&BOB,
+			&ALICE,
+			(CLASS_ID, TOKEN_ID),
+			40
+		));
+		assert!(Approvals::<Runtime>::contains_key(
+			BOB,
+			(CLASS_ID, TOKEN_ID)
+		));
+
+		assert_ok!(NonFungibleTokenModule::transfer(
+			&BOB,
+			&ALICE,
+			(CLASS_ID, TOKEN_ID),
+			60
+		));
+		assert_eq!(
+			Approvals::<Runtime>::contains_key(BOB, (CLASS_ID, TOKEN_ID)),
+			false
+		);
+	});
+}
+
+#[test]
+fn set_operator_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], ()));
+
+		assert_noop!(
+			NonFungibleTokenModule::set_operator(&BOB, &BOB, true),
+			Error::<Runtime>::WrongArguments
+		);
+
+		assert_ok!(NonFungibleTokenModule::set_operator(&BOB, &ALICE, true));
+		assert!(NonFungibleTokenModule::operators(BOB, ALICE));
+		assert_eq!(
+			last_event(),
+			Event::nft(crate::Event::OperatorSet(BOB, ALICE, true))
+		);
+		assert!(NonFungibleTokenModule::is_approved_or_owner(
+			&ALICE,
+			&BOB,
+			(CLASS_ID, TOKEN_ID)
+		));
+
+		assert_ok!(NonFungibleTokenModule::set_operator(&BOB, &ALICE, false));
+		assert_eq!(NonFungibleTokenModule::operators(BOB, ALICE), false);
+		assert_eq!(
+			last_event(),
+			Event::nft(crate::Event::OperatorSet(BOB, ALICE, false))
+		);
+	});
+}
+
+#[test]
+#[cfg(not(feature = "disable-tokens-by-owner"))]
+fn mint_assigns_all_shares_to_minter() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], ()));
+		assert_eq!(
+			NonFungibleTokenModule::tokens_by_owner(BOB, (CLASS_ID, TOKEN_ID)).shares,
+			SharesPerToken::get()
+		);
+	});
+}
+
+#[test]
+#[cfg(not(feature = "disable-tokens-by-owner"))]
+fn migrate_to_v2_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], ()));
+
+		// write the token shares in the v1 layout, a single `u8` percentage
+		let key = TokensByOwner::<Runtime>::hashed_key_for(BOB, (CLASS_ID, TOKEN_ID));
+		sp_io::storage::set(&key, &30_u8.encode());
+		let key = TokensByOwner::<Runtime>::hashed_key_for(ALICE, (CLASS_ID, TOKEN_ID));
+		sp_io::storage::set(&key, &70_u8.encode());
+		StorageVersion::<Runtime>::kill();
+
+		NonFungibleTokenModule::on_runtime_upgrade();
+
+		assert_eq!(
+			NonFungibleTokenModule::tokens_by_owner(BOB, (CLASS_ID, TOKEN_ID)).shares,
+			30
+		);
+		assert_eq!(
+			NonFungibleTokenModule::tokens_by_owner(ALICE, (CLASS_ID, TOKEN_ID)).shares,
+			70
+		);
+		assert_eq!(StorageVersion::<Runtime>::get(), Releases::V2_0_0);
+	});
+}
+
+#[test]
+fn burn_co_owned_token_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::transfer(
+			&BOB,
+			&ALICE,
+			(CLASS_ID, TOKEN_ID),
+			30
+		));
+
+		assert_noop!(
+			NonFungibleTokenModule::burn(&BOB, (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::BurnNotApproved
+		);
+		assert_noop!(
+			NonFungibleTokenModule::burn_with_approvals(&[ALICE], (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::BurnNotApproved
+		);
+		// counting an approver twice doesn't add up their shares
+		assert_noop!(
+			NonFungibleTokenModule::burn_with_approvals(
+				&[ALICE, ALICE, ALICE],
+				(CLASS_ID, TOKEN_ID)
+			),
+			Error::<Runtime>::BurnNotApproved
+		);
+	});
+}
+
+#[test]
+fn burn_with_approvals_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::transfer(
+			&BOB,
+			&ALICE,
+			(CLASS_ID, TOKEN_ID),
+			20
+		));
+		assert_ok!(NonFungibleTokenModule::transfer(
+			&BOB,
+			&CHARLIE,
+			(CLASS_ID, TOKEN_ID),
+			10
+		));
+
+		assert!(!NonFungibleTokenModule::is_burn_approved(
+			&[BOB],
+			(CLASS_ID, TOKEN_ID)
+		));
+		assert_ok!(NonFungibleTokenModule::burn_with_approvals(
+			&[BOB, CHARLIE],
+			(CLASS_ID, TOKEN_ID)
+		));
+
+		assert_eq!(Tokens::<Runtime>::contains_key(CLASS_ID, TOKEN_ID), false);
+		for owner in [ALICE, BOB, CHARLIE].iter() {
+			assert_eq!(
+				TokensByOwner::<Runtime>::contains_key(owner, (CLASS_ID, TOKEN_ID)),
+				false
+			);
+		}
+		assert_eq!(
+			NonFungibleTokenModule::classes(CLASS_ID)
+				.unwrap()
+				.total_issuance,
+			0
+		);
+	});
+}
+
+#[test]
+fn set_metadata_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], ()));
+
+		assert_ok!(NonFungibleTokenModule::set_class_metadata(
+			&ALICE,
+			CLASS_ID,
+			vec![2]
+		));
+		assert_eq!(
+			NonFungibleTokenModule::classes(CLASS_ID).unwrap().metadata,
+			vec![2]
+		);
+
+		assert_ok!(NonFungibleTokenModule::set_token_metadata(
+			&BOB,
+			(CLASS_ID, TOKEN_ID),
+			vec![3]
+		));
+		assert_eq!(
+			NonFungibleTokenModule::tokens(CLASS_ID, TOKEN_ID)
+				.unwrap()
+				.metadata,
+			vec![3]
+		);
+	});
+}
+
+#[test]
+fn set_metadata_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], ()));
+
+		assert_noop!(
+			NonFungibleTokenModule::set_class_metadata(&ALICE, CLASS_ID_NOT_EXIST, vec![2]),
+			Error::<Runtime>::ClassNotFound
+		);
+		assert_noop!(
+			NonFungibleTokenModule::set_class_metadata(&BOB, CLASS_ID, vec![2]),
+			Error::<Runtime>::NoPermission
+		);
+
+		assert_noop!(
+			NonFungibleTokenModule::set_token_metadata(
+				&BOB,
+				(CLASS_ID, TOKEN_ID_NOT_EXIST),
+				vec![3]
+			),
+			Error::<Runtime>::TokenNotFound
+		);
+		assert_ok!(NonFungibleTokenModule::transfer(
+			&BOB,
+			&ALICE,
+			(CLASS_ID, TOKEN_ID),
+			1
+		));
+		assert_noop!(
+			NonFungibleTokenModule::set_token_metadata(&BOB, (CLASS_ID, TOKEN_ID), vec![3]),
+			Error::<Runtime>::NoPermission
+		);
+	});
+}
+
+#[test]
+fn state_changes_deposit_events() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
+		assert_eq!(
+			last_event(),
+			Event::nft(crate::Event::ClassCreated(ALICE, CLASS_ID))
+		);
+
+		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], ()));
+		assert_eq!(
+			last_event(),
+			Event::nft(crate::Event::Minted(BOB, (CLASS_ID, TOKEN_ID)))
+		);
+
+		assert_ok!(NonFungibleTokenModule::transfer(
+			&BOB,
+			&ALICE,
+			(CLASS_ID, TOKEN_ID),
+			30
+		));
+		assert_eq!(
+			last_event(),
+			Event::nft(crate::Event::Transferred(
+				BOB,
+				ALICE,
+				(CLASS_ID, TOKEN_ID),
+				30,
+				70,
+				30
+			))
+		);
+
+		assert_ok!(NonFungibleTokenModule::transfer(
+			&ALICE,
+			&BOB,
+			(CLASS_ID, TOKEN_ID),
+			30
+		));
+		assert_ok!(NonFungibleTokenModule::burn(&BOB, (CLASS_ID, TOKEN_ID)));
+		assert_eq!(
+			last_event(),
+			Event::nft(crate::Event::Burned((CLASS_ID, TOKEN_ID)))
+		);
+
+		assert_ok!(NonFungibleTokenModule::destroy_class(&ALICE, CLASS_ID));
+		assert_eq!(
+			last_event(),
+			Event::nft(crate::Event::ClassDestroyed(ALICE, CLASS_ID))
+		);
+	});
+}
+
+#[test]
+fn ownership_queries_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::transfer(
+			&BOB,
+			&ALICE,
+			(CLASS_ID, TOKEN_ID),
+			30
+		));
+
+		assert_eq!(
+			NonFungibleTokenModule::owners_of_token((CLASS_ID, TOKEN_ID), 0, 10),
+			vec![(ALICE, 30), (BOB, 70)]
+		);
+		assert_eq!(
+			NonFungibleTokenModule::owners_of_token((CLASS_ID, TOKEN_ID), 1, 10),
+			vec![(BOB, 70)]
+		);
+		assert_eq!(
+			NonFungibleTokenModule::owners_of_token((CLASS_ID, TOKEN_ID_NOT_EXIST), 0, 10),
+			vec![]
+		);
+
+		let mut bob_tokens = NonFungibleTokenModule::tokens_of_owner(&BOB, 0, 10);
+		bob_tokens.sort();
+		assert_eq!(
+			bob_tokens,
+			vec![((CLASS_ID, TOKEN_ID), 70), ((CLASS_ID, TOKEN_ID + 1), 100)]
+		);
+		assert_eq!(NonFungibleTokenModule::tokens_of_owner(&BOB, 0, 1).len(), 1);
+		assert_eq!(NonFungibleTokenModule::tokens_of_owner(&BOB, 2, 10), vec![]);
+	});
+}
+
+#[test]
+fn metadata_length_is_bounded() {
+	ExtBuilder::default().build().execute_with(|| {
+		let too_long = vec![1; MaxMetadataLength::get() as usize + 1];
+		assert_noop!(
+			NonFungibleTokenModule::create_class(&ALICE, too_long.clone(), ()),
+			Error::<Runtime>::MetadataTooLong
+		);
+
+		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
+		assert_noop!(
+			NonFungibleTokenModule::mint(&BOB, CLASS_ID, too_long.clone(), ()),
+			Error::<Runtime>::MetadataTooLong
+		);
+		assert_noop!(
+			NonFungibleTokenModule::set_class_metadata(&ALICE, CLASS_ID, too_long.clone()),
+			Error::<Runtime>::MetadataTooLong
+		);
+
+		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], ()));
+		assert_noop!(
+			NonFungibleTokenModule::set_token_metadata(&BOB, (CLASS_ID, TOKEN_ID), too_long),
+			Error::<Runtime>::MetadataTooLong
+		);
+	});
+}
+
+#[test]
+fn owners_per_token_are_bounded() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::mint(&ALICE, CLASS_ID, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::transfer(
+			&ALICE,
+			&BOB,
+			(CLASS_ID, TOKEN_ID),
+			10
+		));
+		assert_ok!(NonFungibleTokenModule::transfer(
+			&ALICE,
+			&CHARLIE,
+			(CLASS_ID, TOKEN_ID),
+			10
+		));
+
+		assert_noop!(
+			NonFungibleTokenModule::transfer(&ALICE, &DAVE, (CLASS_ID, TOKEN_ID), 10),
+			Error::<Runtime>::TooManyOwners
+		);
+
+		// the sender leaving the token makes room for the recipient
+		assert_ok!(NonFungibleTokenModule::transfer(
+			&BOB,
+			&DAVE,
+			(CLASS_ID, TOKEN_ID),
+			10
+		));
+		assert!(NonFungibleTokenModule::is_owner(
+			&DAVE,
+			(CLASS_ID, TOKEN_ID)
+		));
+	});
+}
+
+#[test]
+fn deposits_are_reserved_and_refunded() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1, 2], ()));
+		assert_eq!(Balances::reserved_balance(ALICE), 12);
+		assert_eq!(NonFungibleTokenModule::class_deposits(CLASS_ID), 12);
+
+		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], ()));
+		assert_eq!(Balances::reserved_balance(BOB), 6);
+		assert_eq!(
+			NonFungibleTokenModule::token_deposits(CLASS_ID, TOKEN_ID),
+			Some((BOB, 6))
+		);
+
+		// the deposit follows the metadata length
+		assert_ok!(NonFungibleTokenModule::set_class_metadata(
+			&ALICE,
+			CLASS_ID,
+			vec![1, 2, 3, 4]
+		));
+		assert_eq!(Balances::reserved_balance(ALICE), 14);
+		assert_ok!(NonFungibleTokenModule::set_class_metadata(
+			&ALICE,
+			CLASS_ID,
+			vec![]
+		));
+		assert_eq!(Balances::reserved_balance(ALICE), 10);
+
+		// the deposit moves to whoever updates the token metadata
+		assert_ok!(NonFungibleTokenModule::transfer(
+			&BOB,
+			&CHARLIE,
+			(CLASS_ID, TOKEN_ID),
+			100
+		));
+		assert_ok!(NonFungibleTokenModule::set_token_metadata(
+			&CHARLIE,
+			(CLASS_ID, TOKEN_ID),
+			vec![1, 2]
+		));
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Balances::reserved_balance(CHARLIE), 7);
+
+		assert_ok!(NonFungibleTokenModule::burn(&CHARLIE, (CLASS_ID, TOKEN_ID)));
+		assert_eq!(Balances::reserved_balance(CHARLIE), 0);
+		assert_eq!(
+			NonFungibleTokenModule::token_deposits(CLASS_ID, TOKEN_ID),
+			None
+		);
+
+		assert_ok!(NonFungibleTokenModule::destroy_class(&ALICE, CLASS_ID));
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE);
+	});
+}
+
+#[test]
+fn deposits_require_free_balance() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			NonFungibleTokenModule::create_class(&DAVE, vec![1], ()),
+			pallet_balances::Error::<Runtime>::InsufficientBalance
+		);
+
+		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
+		assert_noop!(
+			NonFungibleTokenModule::mint(&DAVE, CLASS_ID, vec![1], ()),
+			pallet_balances::Error::<Runtime>::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+fn nonfungibles_traits_should_work() {
+	use anmol_utils::nonfungibles::{
+		Create, Destroy, Inspect, InspectEnumerable, Mutate, Transfer,
+	};
+	type Nft = NonFungibleTokenModule;
+
+	ExtBuilder::default().build().execute_with(|| {
+		let class_id = <Nft as Create<AccountId>>::create_class(&ALICE, vec![1]).unwrap();
+		assert_eq!(
+			<Nft as Inspect<AccountId>>::class_owner(&class_id),
+			Some(ALICE)
+		);
+		assert_eq!(
+			<Nft as Inspect<AccountId>>::class_metadata(&class_id),
+			Some(vec![1])
+		);
+
+		let token_id = <Nft as Mutate<AccountId>>::mint_into(&class_id, &BOB, vec![2]).unwrap();
+		assert_eq!(
+			<Nft as Inspect<AccountId>>::metadata(&class_id, &token_id),
+			Some(vec![2])
+		);
+		assert_eq!(
+			<Nft as Inspect<AccountId>>::shares_of(&class_id, &token_id, &BOB),
+			<Nft as Inspect<AccountId>>::total_shares()
+		);
+
+		assert_ok!(<Nft as Transfer<AccountId>>::transfer(
+			&class_id, &token_id, &BOB, &ALICE, 30
+		));
+		assert_eq!(
+			<Nft as Inspect<AccountId>>::owners(&class_id, &token_id),
+			vec![ALICE, BOB]
+		);
+		assert_eq!(
+			<Nft as Inspect<AccountId>>::shares_of(&class_id, &token_id, &ALICE),
+			30
+		);
+		assert!(<Nft as Inspect<AccountId>>::can_transfer(
+			&class_id, &token_id
+		));
+
+		assert_eq!(
+			<Nft as InspectEnumerable<AccountId>>::classes().collect::<Vec<_>>(),
+			vec![class_id]
+		);
+		assert_eq!(
+			<Nft as InspectEnumerable<AccountId>>::instances(&class_id).collect::<Vec<_>>(),
+			vec![token_id]
+		);
+		assert_eq!(
+			<Nft as InspectEnumerable<AccountId>>::owned(&ALICE).collect::<Vec<_>>(),
+			vec![(class_id, token_id, 30)]
+		);
+		assert_eq!(
+			<Nft as InspectEnumerable<AccountId>>::owned_in_class(&class_id, &BOB)
+				.collect::<Vec<_>>(),
+			vec![(token_id, 70)]
+		);
+		assert_eq!(
+			<Nft as InspectEnumerable<AccountId>>::owned_in_class(&CLASS_ID_NOT_EXIST, &BOB)
+				.count(),
+			0
+		);
+
+		// only the holder of every share may update the metadata
+		assert_noop!(
+			<Nft as Mutate<AccountId>>::set_metadata(&class_id, &token_id, &BOB, vec![3]),
+			Error::<Runtime>::NoPermission
+		);
+
+		assert_ok!(<Nft as Transfer<AccountId>>::transfer_all(
+			&class_id, &token_id, &BOB, &ALICE
+		));
+		assert_eq!(
+			<Nft as Inspect<AccountId>>::owners(&class_id, &token_id),
+			vec![ALICE]
+		);
+		assert_ok!(<Nft as Mutate<AccountId>>::set_metadata(
+			&class_id,
+			&token_id,
+			&ALICE,
+			vec![3]
+		));
+		assert_eq!(
+			<Nft as Inspect<AccountId>>::metadata(&class_id, &token_id),
+			Some(vec![3])
+		);
+
+		assert_noop!(
+			<Nft as Destroy<AccountId>>::destroy(&class_id, &ALICE),
+			Error::<Runtime>::CannotDestroyClass
+		);
+		assert_ok!(<Nft as Mutate<AccountId>>::burn_from(
+			&class_id,
+			&token_id,
+			&[ALICE]
+		));
+		assert!(<Nft as Inspect<AccountId>>::owners(&class_id, &token_id).is_empty());
+		assert_ok!(<Nft as Destroy<AccountId>>::destroy(&class_id, &ALICE));
+		assert_eq!(<Nft as Inspect<AccountId>>::class_owner(&class_id), None);
+	});
+}
+
+#[test]
+fn nonfungibles_burn_requires_co_owner_approval() {
+	use anmol_utils::nonfungibles::{Mutate, Transfer};
+	type Nft = NonFungibleTokenModule;
+
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Nft::create_class(&ALICE, vec![1], ()));
+		assert_ok!(Nft::mint(&ALICE, CLASS_ID, vec![1], ()));
+		assert_ok!(<Nft as Transfer<AccountId>>::transfer(
+			&CLASS_ID, &TOKEN_ID, &ALICE, &BOB, 40
+		));
+
+		assert_noop!(
+			<Nft as Mutate<AccountId>>::burn_from(&CLASS_ID, &TOKEN_ID, &[ALICE]),
+			Error::<Runtime>::BurnNotApproved
+		);
+		assert_ok!(<Nft as Mutate<AccountId>>::burn_from(
+			&CLASS_ID,
+			&TOKEN_ID,
+			&[ALICE, BOB]
+		));
+		assert_eq!(Tokens::<Runtime>::get(CLASS_ID, TOKEN_ID), None);
+	});
+}
+
+#[test]
+#[cfg(not(feature = "disable-tokens-by-owner"))]
+fn shares_are_indexed_by_owner() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::transfer(
+			&BOB,
+			&ALICE,
+			(CLASS_ID, TOKEN_ID),
+			30
+		));
+
+		assert_eq!(
+			Tokens::<Runtime>::get(CLASS_ID, TOKEN_ID).unwrap().owners,
+			vec![ALICE, BOB]
+		);
+		assert_eq!(
+			NonFungibleTokenModule::tokens_by_owner(ALICE, (CLASS_ID, TOKEN_ID)).shares,
+			30
+		);
+		assert_eq!(
+			NonFungibleTokenModule::tokens_by_owner(BOB, (CLASS_ID, TOKEN_ID)).shares,
+			70
+		);
+	});
+}
+
+#[test]
+#[cfg(feature = "disable-tokens-by-owner")]
+fn shares_are_stored_in_token_info() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], ()));
+		assert_eq!(
+			Tokens::<Runtime>::get(CLASS_ID, TOKEN_ID).unwrap().owners,
+			vec![(BOB, SharesPerToken::get())]
+		);
+
+		assert_ok!(NonFungibleTokenModule::transfer(
+			&BOB,
+			&ALICE,
+			(CLASS_ID, TOKEN_ID),
+			30
+		));
+		assert_eq!(
+			Tokens::<Runtime>::get(CLASS_ID, TOKEN_ID).unwrap().owners,
+			vec![(ALICE, 30), (BOB, 70)]
+		);
+
+		assert_ok!(NonFungibleTokenModule::transfer(
+			&BOB,
+			&ALICE,
+			(CLASS_ID, TOKEN_ID),
+			70
+		));
+		assert_eq!(
+			Tokens::<Runtime>::get(CLASS_ID, TOKEN_ID).unwrap().owners,
+			vec![(ALICE, 100)]
+		);
+		assert!(!NonFungibleTokenModule::is_owner(
+			&BOB,
+			(CLASS_ID, TOKEN_ID)
+		));
+
+		// the index is never written
+		assert_eq!(TokensByOwner::<Runtime>::iter().count(), 0);
+	});
+}
+
+#[test]
+#[cfg(feature = "disable-tokens-by-owner")]
+fn migrate_to_v2_bumps_version_without_index() {
+	ExtBuilder::default().build().execute_with(|| {
+		StorageVersion::<Runtime>::kill();
+
+		NonFungibleTokenModule::on_runtime_upgrade();
+
+		assert_eq!(StorageVersion::<Runtime>::get(), Releases::V2_0_0);
+	});
+}
+
+#[test]
+fn locked_token_cannot_move() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], ()));
+
+		assert_noop!(
+			NonFungibleTokenModule::lock((CLASS_ID, TOKEN_ID_NOT_EXIST)),
+			Error::<Runtime>::TokenNotFound
+		);
+		assert_ok!(NonFungibleTokenModule::lock((CLASS_ID, TOKEN_ID)));
+		assert_eq!(
+			last_event(),
+			Event::nft(crate::Event::Locked((CLASS_ID, TOKEN_ID)))
+		);
+		assert_noop!(
+			NonFungibleTokenModule::lock((CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::TokenLocked
+		);
+
+		assert_noop!(
+			NonFungibleTokenModule::transfer(&BOB, &ALICE, (CLASS_ID, TOKEN_ID), 10),
+			Error::<Runtime>::TokenLocked
+		);
+		assert_noop!(
+			NonFungibleTokenModule::burn(&BOB, (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::TokenLocked
+		);
+		assert_noop!(
+			NonFungibleTokenModule::burn_with_approvals(&[BOB], (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::TokenLocked
+		);
+
+		NonFungibleTokenModule::unlock((CLASS_ID, TOKEN_ID));
+		assert_eq!(
+			last_event(),
+			Event::nft(crate::Event::Unlocked((CLASS_ID, TOKEN_ID)))
+		);
+		assert!(!NonFungibleTokenModule::is_locked((CLASS_ID, TOKEN_ID)));
+		assert_ok!(NonFungibleTokenModule::transfer(
+			&BOB,
+			&ALICE,
+			(CLASS_ID, TOKEN_ID),
+			10
+		));
+	});
+}
+
+#[test]
+fn royalties_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let token = (CLASS_ID, TOKEN_ID);
+		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], ()));
+
+		let royalty = vec![
+			(ALICE, Perbill::from_percent(10)),
+			(CHARLIE, Perbill::from_percent(5)),
+		];
+		assert_noop!(
+			NonFungibleTokenModule::set_class_royalty(&BOB, CLASS_ID, royalty.clone()),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			NonFungibleTokenModule::set_class_royalty(&ALICE, CLASS_ID_NOT_EXIST, royalty.clone()),
+			Error::<Runtime>::ClassNotFound
+		);
+		assert_noop!(
+			NonFungibleTokenModule::set_class_royalty(
+				&ALICE,
+				CLASS_ID,
+				vec![
+					(ALICE, Perbill::from_percent(1)),
+					(BOB, Perbill::from_percent(1)),
+					(CHARLIE, Perbill::from_percent(1)),
+				]
+			),
+			Error::<Runtime>::TooManyRoyaltyBeneficiaries
+		);
+		assert_noop!(
+			NonFungibleTokenModule::set_class_royalty(
+				&ALICE,
+				CLASS_ID,
+				vec![
+					(ALICE, Perbill::from_percent(30)),
+					(CHARLIE, Perbill::from_percent(21)),
+				]
+			),
+			Error::<Runtime>::RoyaltyTooHigh
+		);
+
+		assert_ok!(NonFungibleTokenModule::set_class_royalty(
+			&ALICE,
+			CLASS_ID,
+			royalty.clone()
+		));
+		assert_eq!(NonFungibleTokenModule::royalty_of(token), royalty);
+		assert_eq!(
+			NonFungibleTokenModule::royalties_for(token, 100),
+			vec![(ALICE, 10), (CHARLIE, 5)]
+		);
+		assert!(NonFungibleTokenModule::royalties_for(token, 0).is_empty());
+
+		// the token override replaces the class royalty, even when empty
+		assert_noop!(
+			NonFungibleTokenModule::set_token_royalty(&ALICE, (CLASS_ID, TOKEN_ID_NOT_EXIST), None),
+			Error::<Runtime>::TokenNotFound
+		);
+		assert_noop!(
+			NonFungibleTokenModule::set_token_royalty(&BOB, token, Some(vec![])),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(NonFungibleTokenModule::set_token_royalty(
+			&ALICE,
+			token,
+			Some(vec![])
+		));
+		assert!(NonFungibleTokenModule::royalties_for(token, 100).is_empty());
+		assert_ok!(NonFungibleTokenModule::set_token_royalty(
+			&ALICE, token, None
+		));
+		assert_eq!(NonFungibleTokenModule::royalty_of(token), royalty);
+
+		assert_ok!(NonFungibleTokenModule::set_token_royalty(
+			&ALICE,
+			token,
+			Some(vec![(DAVE, Perbill::from_percent(20))])
+		));
+		assert_ok!(NonFungibleTokenModule::burn(&BOB, token));
+		assert_eq!(
+			NonFungibleTokenModule::token_royalties(CLASS_ID, TOKEN_ID),
+			None
+		);
+
+		assert_ok!(NonFungibleTokenModule::destroy_class(&ALICE, CLASS_ID));
+		assert!(NonFungibleTokenModule::class_royalties(CLASS_ID).is_empty());
+	});
+}
+
+#[test]
+fn royalties_are_paid() {
+	ExtBuilder::default().build().execute_with(|| {
+		let token = (CLASS_ID, TOKEN_ID);
+		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::mint(&ALICE, CLASS_ID, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::set_class_royalty(
+			&ALICE,
+			CLASS_ID,
+			vec![
+				(ALICE, Perbill::from_percent(10)),
+				(DAVE, Perbill::from_percent(5)),
+			]
+		));
+		let alice_balance = Balances::free_balance(ALICE);
+		let royalty = NonFungibleTokenModule::royalty_of(token);
+
+		assert_eq!(
+			NonFungibleTokenModule::pay_royalties(&BOB, token, &royalty, 40),
+			Ok(6)
+		);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE - 6);
+		assert_eq!(Balances::free_balance(ALICE), alice_balance + 4);
+		assert_eq!(Balances::free_balance(DAVE), 2);
+		let royalty_paid = Event::nft(crate::Event::RoyaltyPaid(token, DAVE, 2));
+		assert_eq!(last_event(), royalty_paid);
+
+		assert_noop!(
+			NonFungibleTokenModule::pay_royalties(&BOB, token, &royalty, 1_000),
+			pallet_balances::Error::<Runtime>::InsufficientBalance
+		);
+
+		// paid from a reserved bid
+		assert_ok!(Balances::reserve(&CHARLIE, 40));
+		assert_eq!(
+			NonFungibleTokenModule::repatriate_royalties(&CHARLIE, token, &royalty, 40),
+			6
+		);
+		assert_eq!(Balances::reserved_balance(CHARLIE), 34);
+		assert_eq!(Balances::free_balance(ALICE), alice_balance + 8);
+		assert_eq!(Balances::free_balance(DAVE), 4);
+	});
+}
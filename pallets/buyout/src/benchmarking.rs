@@ -0,0 +1,99 @@
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::traits::{Currency, OnInitialize};
+use frame_system::RawOrigin;
+use sp_runtime::{traits::Bounded, Perbill};
+use sp_std::{boxed::Box, vec, vec::Vec};
+
+fn funded_account<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+	let who: T::AccountId = account(name, index, 0);
+	<T as base_nft::Config>::Currency::make_free_balance_be(
+		&who,
+		BalanceOf::<T>::max_value() / 2_u32.into(),
+	);
+	who
+}
+
+/// Token held by a funded owner and `o - 1` funded holders of a single share, with a royalty
+/// paid to `r` funded beneficiaries
+fn co_owned_token<T: Config>(o: u32, r: u32) -> (T::AccountId, (T::ClassId, T::TokenId)) {
+	let owner = funded_account::<T>("owner", 0);
+	let class_id = BaseNft::<T>::create_class(&owner, vec![1_u8], Default::default()).unwrap();
+	let token_id = BaseNft::<T>::mint(&owner, class_id, vec![1_u8], Default::default()).unwrap();
+	let token = (class_id, token_id);
+
+	for i in 1..o {
+		let holder = funded_account::<T>("holder", i);
+		BaseNft::<T>::transfer(&owner, &holder, token, 1_u32.into()).unwrap();
+	}
+
+	let rate = Perbill::from_parts(
+		T::MaxRoyalty::get().deconstruct() / T::MaxRoyaltyBeneficiaries::get().max(1),
+	);
+	let royalty = (0..r)
+		.map(|i| (funded_account::<T>("beneficiary", i), rate))
+		.collect();
+	BaseNft::<T>::set_class_royalty(&owner, class_id, royalty).unwrap();
+	(owner, token)
+}
+
+benchmarks! {
+	propose_buyout {
+		let (owner, token) = co_owned_token::<T>(1, 0);
+
+		let caller: T::AccountId = whitelisted_caller();
+		<T as base_nft::Config>::Currency::make_free_balance_be(
+			&caller,
+			BalanceOf::<T>::max_value() / 2_u32.into(),
+		);
+		BaseNft::<T>::transfer(&owner, &caller, token, 1_u32.into())?;
+	}: _(RawOrigin::Signed(caller), token, 1_000_000_u32.into())
+
+	accept_buyout {
+		let r in 0 .. T::MaxRoyaltyBeneficiaries::get();
+
+		let (owner, token) = co_owned_token::<T>(2, r);
+		Pallet::<T>::propose_buyout(
+			RawOrigin::Signed(owner).into(),
+			token,
+			1_000_000_u32.into(),
+		)?;
+		let holder = funded_account::<T>("holder", 1);
+		// countering in a later block moves the end of the buyout
+		frame_system::Pallet::<T>::set_block_number(1u32.into());
+	}: _(RawOrigin::Signed(holder), token)
+
+	counter_buyout {
+		let (owner, token) = co_owned_token::<T>(2, 0);
+		Pallet::<T>::propose_buyout(
+			RawOrigin::Signed(owner).into(),
+			token,
+			1_000_000_u32.into(),
+		)?;
+		let holder = funded_account::<T>("holder", 1);
+		// countering in a later block moves the end of the buyout
+		frame_system::Pallet::<T>::set_block_number(1u32.into());
+	}: _(RawOrigin::Signed(holder), token)
+
+	complete {
+		let o in 2 .. T::MaxOwnersPerToken::get();
+		let r in 0 .. T::MaxRoyaltyBeneficiaries::get();
+
+		let (owner, token) = co_owned_token::<T>(o, r);
+		Pallet::<T>::propose_buyout(
+			RawOrigin::Signed(owner.clone()).into(),
+			token,
+			1_000_000_u32.into(),
+		)?;
+		let end = Pallet::<T>::buyouts(token.0, token.1).unwrap().end;
+	}: {
+		<Pallet<T> as OnInitialize<T::BlockNumber>>::on_initialize(end);
+	}
+	verify {
+		assert_eq!(BaseNft::<T>::shares_of(&owner, token), T::SharesPerToken::get());
+	}
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Runtime);
@@ -0,0 +1,409 @@
+//! # Buyout
+//! Shotgun buyouts of fractionally owned `base_nft` tokens.
+//!
+//! Any holder of a co-owned token proposes to buy the shares of everyone else at a price for
+//! the whole token and reserves the value of those shares. The token is locked with
+//! `base_nft::Pallet::lock` for `Config::BuyoutPeriod`, during which the other holders either
+//! accept and sell their shares right away for the pro-rata part of the price, or counter by
+//! taking over the buyout at the same price, which makes the previous buyer a seller and
+//! restarts the period so that they can counter in turn.
+//!
+//! At the end of the period the buyer is sold the shares of every remaining holder in
+//! `on_initialize` and becomes the only owner of the token. Every holder is paid on its own, a
+//! payment that fails, e.g. one too small to create the account of the holder, is left reserved
+//! by the buyer until the holder claims it with `base_nft::Pallet::claim_proceeds`. Royalties
+//! of the token when the buyout was proposed are paid out of every sale. At most
+//! `Config::MaxBuyoutsPerBlock` buyouts complete in a block, proposing a buyout that would end
+//! in a full block fails.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use base_nft::{BalanceOf, Module as BaseNft, OnTokenChange};
+use codec::{Decode, Encode};
+use frame_support::{
+	debug, dispatch::DispatchResultWithPostInfo, pallet_prelude::*, traits::ReservableCurrency,
+	transactional,
+};
+use frame_system::pallet_prelude::*;
+use sp_runtime::{
+	helpers_128bit::multiply_by_rational,
+	traits::{SaturatedConversion, Saturating, Zero},
+	DispatchError, DispatchResult, Perbill, RuntimeDebug,
+};
+use sp_std::vec::Vec;
+
+pub use pallet::*;
+
+mod mock;
+mod tests;
+
+mod benchmarking;
+pub mod weights;
+pub use weights::WeightInfo;
+
+/// Buyout info
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
+pub struct BuyoutInfo<AccountId, Balance, BlockNumber> {
+	/// Holder buying the shares of everyone else, the proposer unless countered
+	pub buyer: AccountId,
+	/// Price of the whole token
+	pub price: Balance,
+	/// Value of the shares left to buy, reserved from the buyer
+	pub reserved: Balance,
+	/// Block the buyout completes at
+	pub end: BlockNumber,
+	/// Royalty of the token when the buyout was proposed, paid on every sale
+	pub royalty: Vec<(AccountId, Perbill)>,
+}
+
+pub type BuyoutInfoOf<T> = BuyoutInfo<
+	<T as frame_system::Config>::AccountId,
+	BalanceOf<T>,
+	<T as frame_system::Config>::BlockNumber,
+>;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + base_nft::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		type WeightInfo: WeightInfo;
+		/// Blocks other holders have to accept or counter a buyout
+		#[pallet::constant]
+		type BuyoutPeriod: Get<Self::BlockNumber>;
+		/// Maximum buyouts completed in a block
+		#[pallet::constant]
+		type MaxBuyoutsPerBlock: Get<u32>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	/// Live buyouts.
+	#[pallet::storage]
+	#[pallet::getter(fn buyouts)]
+	pub type Buyouts<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::ClassId, Twox64Concat, T::TokenId, BuyoutInfoOf<T>>;
+
+	/// Buyouts by the block they complete at.
+	#[pallet::storage]
+	#[pallet::getter(fn buyout_end_time)]
+	pub type BuyoutEndTime<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		Twox64Concat,
+		(T::ClassId, T::TokenId),
+		(),
+	>;
+
+	/// Number of buyouts completed at a block, at most `MaxBuyoutsPerBlock`.
+	#[pallet::storage]
+	#[pallet::getter(fn buyout_end_count)]
+	pub type BuyoutEndCount<T: Config> =
+		StorageMap<_, Twox64Concat, T::BlockNumber, u32, ValueQuery>;
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Buyout price must not be zero
+		ZeroPrice,
+		/// Token already has a live buyout
+		BuyoutInProgress,
+		/// Token has no live buyout
+		BuyoutNotFound,
+		/// Operation requires a holder of the token
+		NotHolder,
+		/// Caller already holds every share of the token
+		AlreadyWholeOwner,
+		/// Operation is not available to the buyer
+		CallerIsBuyer,
+		/// `MaxBuyoutsPerBlock` buyouts already complete at the block the buyout would end at
+		TooManyBuyoutsEnding,
+	}
+
+	#[pallet::event]
+	#[pallet::metadata(
+		T::AccountId = "AccountId",
+		BalanceOf<T> = "Balance",
+		T::BlockNumber = "BlockNumber"
+	)]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Holder proposed to buy out a token. \[buyer, token, price, end\]
+		BuyoutProposed(
+			T::AccountId,
+			(T::ClassId, T::TokenId),
+			BalanceOf<T>,
+			T::BlockNumber,
+		),
+		/// Holder took over a buyout, which now ends later. \[buyer, previous_buyer, token, end\]
+		BuyoutCountered(
+			T::AccountId,
+			T::AccountId,
+			(T::ClassId, T::TokenId),
+			T::BlockNumber,
+		),
+		/// Holder sold its shares to the buyer. \[seller, buyer, token, shares, amount\]
+		SharesSold(
+			T::AccountId,
+			T::AccountId,
+			(T::ClassId, T::TokenId),
+			T::Shares,
+			BalanceOf<T>,
+		),
+		/// Buyer became the only owner of the token. \[buyer, token, price\]
+		BuyoutCompleted(T::AccountId, (T::ClassId, T::TokenId), BalanceOf<T>),
+		/// Buyout could not buy every share, what was left reserved was refunded.
+		/// \[buyer, token\]
+		BuyoutFailed(T::AccountId, (T::ClassId, T::TokenId)),
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			// removing the count and the last read of the drained prefix
+			let mut weight: Weight = T::DbWeight::get().reads_writes(1, 1);
+			BuyoutEndCount::<T>::remove(now);
+			for (token, _) in BuyoutEndTime::<T>::drain_prefix(now) {
+				// draining the entry and taking the buyout
+				weight = weight.saturating_add(T::DbWeight::get().reads_writes(2, 2));
+				if let Some(buyout) = Buyouts::<T>::take(token.0, token.1) {
+					weight = weight
+						.saturating_add(T::WeightInfo::complete(
+							T::MaxOwnersPerToken::get(),
+							T::MaxRoyaltyBeneficiaries::get(),
+						))
+						.saturating_add(
+							T::OnTokenChange::on_transfer_weight()
+								.saturating_mul(T::MaxOwnersPerToken::get() as Weight),
+						);
+					Self::complete(token, buyout);
+				}
+			}
+			weight
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Propose to buy the shares of every other holder of `token` at `price` for the whole
+		/// token
+		///
+		/// The value of those shares is reserved and the token is locked until the buyout
+		/// completes.
+		#[pallet::weight(T::WeightInfo::propose_buyout())]
+		#[transactional]
+		pub fn propose_buyout(
+			origin: OriginFor<T>,
+			token: (T::ClassId, T::TokenId),
+			price: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let buyer = ensure_signed(origin)?;
+
+			ensure!(!price.is_zero(), Error::<T>::ZeroPrice);
+			ensure!(
+				!Buyouts::<T>::contains_key(token.0, token.1),
+				Error::<T>::BuyoutInProgress
+			);
+			let held = BaseNft::<T>::shares_of(&buyer, token);
+			ensure!(!held.is_zero(), Error::<T>::NotHolder);
+			ensure!(
+				held < T::SharesPerToken::get(),
+				Error::<T>::AlreadyWholeOwner
+			);
+
+			let reserved = Self::value_of(price, T::SharesPerToken::get() - held);
+			BaseNft::<T>::lock(token)?;
+			<T as base_nft::Config>::Currency::reserve(&buyer, reserved)?;
+
+			let end =
+				frame_system::Pallet::<T>::block_number().saturating_add(T::BuyoutPeriod::get());
+			Self::schedule(token, end)?;
+			Buyouts::<T>::insert(
+				token.0,
+				token.1,
+				BuyoutInfo {
+					buyer: buyer.clone(),
+					price,
+					reserved,
+					end,
+					royalty: BaseNft::<T>::royalty_of(token),
+				},
+			);
+
+			Self::deposit_event(Event::BuyoutProposed(buyer, token, price, end));
+			Ok(().into())
+		}
+
+		/// Sell every share of `token` held by the caller to the buyer for the pro-rata part of
+		/// the buyout price
+		#[pallet::weight(
+			T::WeightInfo::accept_buyout(T::MaxRoyaltyBeneficiaries::get())
+				.saturating_add(T::OnTokenChange::on_transfer_weight())
+		)]
+		#[transactional]
+		pub fn accept_buyout(
+			origin: OriginFor<T>,
+			token: (T::ClassId, T::TokenId),
+		) -> DispatchResultWithPostInfo {
+			let seller = ensure_signed(origin)?;
+
+			Buyouts::<T>::try_mutate(token.0, token.1, |maybe_buyout| -> DispatchResult {
+				let buyout = maybe_buyout.as_mut().ok_or(Error::<T>::BuyoutNotFound)?;
+				ensure!(seller != buyout.buyer, Error::<T>::CallerIsBuyer);
+				let shares = BaseNft::<T>::shares_of(&seller, token);
+				ensure!(!shares.is_zero(), Error::<T>::NotHolder);
+
+				Self::sell(token, &seller, buyout, shares)
+			})?;
+			Ok(().into())
+		}
+
+		/// Take over the buyout of `token` at the same price, the previous buyer is refunded
+		/// and sells its shares at the end like any other holder
+		///
+		/// The buyout period restarts so that the previous buyer has as long to counter back as
+		/// the other holders had.
+		#[pallet::weight(T::WeightInfo::counter_buyout())]
+		#[transactional]
+		pub fn counter_buyout(
+			origin: OriginFor<T>,
+			token: (T::ClassId, T::TokenId),
+		) -> DispatchResultWithPostInfo {
+			let buyer = ensure_signed(origin)?;
+
+			let end =
+				frame_system::Pallet::<T>::block_number().saturating_add(T::BuyoutPeriod::get());
+			let previous = Buyouts::<T>::try_mutate(
+				token.0,
+				token.1,
+				|maybe_buyout| -> Result<T::AccountId, DispatchError> {
+					let buyout = maybe_buyout.as_mut().ok_or(Error::<T>::BuyoutNotFound)?;
+					ensure!(buyer != buyout.buyer, Error::<T>::CallerIsBuyer);
+					let held = BaseNft::<T>::shares_of(&buyer, token);
+					ensure!(!held.is_zero(), Error::<T>::NotHolder);
+
+					let reserved = Self::value_of(buyout.price, T::SharesPerToken::get() - held);
+					<T as base_nft::Config>::Currency::reserve(&buyer, reserved)?;
+					<T as base_nft::Config>::Currency::unreserve(&buyout.buyer, buyout.reserved);
+
+					if buyout.end != end {
+						Self::unschedule(token, buyout.end);
+						Self::schedule(token, end)?;
+						buyout.end = end;
+					}
+					buyout.reserved = reserved;
+					Ok(sp_std::mem::replace(&mut buyout.buyer, buyer.clone()))
+				},
+			)?;
+
+			Self::deposit_event(Event::BuyoutCountered(buyer, previous, token, end));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Part of `price` paid for `shares` of a token
+	fn value_of(price: BalanceOf<T>, shares: T::Shares) -> BalanceOf<T> {
+		multiply_by_rational(
+			price.saturated_into(),
+			shares.saturated_into(),
+			T::SharesPerToken::get().saturated_into(),
+		)
+		.map(SaturatedConversion::saturated_into)
+		.unwrap_or_else(|_| Zero::zero())
+	}
+
+	/// Complete the buyout of `token` at `end`
+	fn schedule(token: (T::ClassId, T::TokenId), end: T::BlockNumber) -> DispatchResult {
+		BuyoutEndCount::<T>::try_mutate(end, |count| -> DispatchResult {
+			ensure!(
+				*count < T::MaxBuyoutsPerBlock::get(),
+				Error::<T>::TooManyBuyoutsEnding
+			);
+			*count += 1;
+			Ok(())
+		})?;
+		BuyoutEndTime::<T>::insert(end, token, ());
+		Ok(())
+	}
+
+	/// Stop completing the buyout of `token` at `end`
+	fn unschedule(token: (T::ClassId, T::TokenId), end: T::BlockNumber) {
+		BuyoutEndTime::<T>::remove(end, token);
+		BuyoutEndCount::<T>::mutate_exists(end, |count| {
+			*count = count
+				.and_then(|count| count.checked_sub(1))
+				.filter(|count| *count > 0)
+		});
+	}
+
+	/// Move `shares` of `seller` to the buyer and pay them from the reserve of the buyout
+	///
+	/// A payment that fails is left reserved by the buyer for `seller` to claim.
+	#[transactional]
+	fn sell(
+		token: (T::ClassId, T::TokenId),
+		seller: &T::AccountId,
+		buyout: &mut BuyoutInfoOf<T>,
+		shares: T::Shares,
+	) -> DispatchResult {
+		let amount = Self::value_of(buyout.price, shares).min(buyout.reserved);
+
+		BaseNft::<T>::transfer_locked(seller, &buyout.buyer, token, shares)?;
+		let royalties =
+			BaseNft::<T>::repatriate_royalties(&buyout.buyer, token, &buyout.royalty, amount);
+		BaseNft::<T>::repatriate_proceeds(
+			&buyout.buyer,
+			seller,
+			token,
+			amount.saturating_sub(royalties),
+		);
+		buyout.reserved = buyout.reserved.saturating_sub(amount);
+
+		Self::deposit_event(Event::SharesSold(
+			seller.clone(),
+			buyout.buyer.clone(),
+			token,
+			shares,
+			amount,
+		));
+		Ok(())
+	}
+
+	/// Sell the shares of every remaining holder to the buyer and unlock the token
+	///
+	/// Holders are sold to one at a time, a holder whose sale fails keeps its shares and the
+	/// buyer is refunded what was reserved for them.
+	fn complete(token: (T::ClassId, T::TokenId), mut buyout: BuyoutInfoOf<T>) {
+		let mut completed = true;
+		for (seller, shares) in BaseNft::<T>::owners_of_token(token, 0, T::MaxOwnersPerToken::get())
+		{
+			if seller == buyout.buyer {
+				continue;
+			}
+			if let Err(e) = Self::sell(token, &seller, &mut buyout, shares) {
+				debug::error!(
+					"Selling {:?} of {:?} to the buyer failed: {:?}",
+					seller,
+					token,
+					e
+				);
+				completed = false;
+			}
+		}
+		BaseNft::<T>::unlock(token);
+
+		// rounding of the pro-rata parts leaves dust reserved
+		<T as base_nft::Config>::Currency::unreserve(&buyout.buyer, buyout.reserved);
+		if completed {
+			Self::deposit_event(Event::BuyoutCompleted(buyout.buyer, token, buyout.price));
+		} else {
+			Self::deposit_event(Event::BuyoutFailed(buyout.buyer, token));
+		}
+	}
+}
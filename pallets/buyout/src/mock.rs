@@ -0,0 +1,150 @@
+#![cfg(test)]
+
+use crate as pallet_buyout;
+use frame_support::{parameter_types, traits::OnInitialize};
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+	Perbill,
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+frame_support::construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Module, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
+		BaseNft: base_nft::{Module, Storage, Event<T>},
+		Buyout: pallet_buyout::{Module, Call, Storage, Event<T>},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const SS58Prefix: u8 = 42;
+	pub const ExistentialDeposit: u64 = 1;
+	pub const SharesPerToken: u64 = 100;
+	pub const BurnApprovalThreshold: Perbill = Perbill::from_percent(75);
+	pub const MaxMetadataLength: u32 = 256;
+	pub const MaxOwnersPerToken: u32 = 10;
+	pub const ClassDeposit: u64 = 0;
+	pub const TokenDeposit: u64 = 0;
+	pub const DepositPerByte: u64 = 0;
+	pub const MaxRoyaltyBeneficiaries: u32 = 3;
+	pub const MaxRoyalty: Perbill = Perbill::from_percent(50);
+	pub const BuyoutPeriod: u64 = 10;
+	pub const MaxBuyoutsPerBlock: u32 = 2;
+}
+
+pub type AccountId = u64;
+
+impl frame_system::Config for Runtime {
+	type BaseCallFilter = ();
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = SS58Prefix;
+}
+
+impl pallet_balances::Config for Runtime {
+	type MaxLocks = ();
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+impl base_nft::Config for Runtime {
+	type Event = Event;
+	type ClassId = u32;
+	type TokenId = u32;
+	type ClassData = ();
+	type TokenData = ();
+	type Shares = u64;
+	type SharesPerToken = SharesPerToken;
+	type BurnApprovalThreshold = BurnApprovalThreshold;
+	type Currency = Balances;
+	type MaxMetadataLength = MaxMetadataLength;
+	type MaxOwnersPerToken = MaxOwnersPerToken;
+	type ClassDeposit = ClassDeposit;
+	type TokenDeposit = TokenDeposit;
+	type DepositPerByte = DepositPerByte;
+	type MaxRoyaltyBeneficiaries = MaxRoyaltyBeneficiaries;
+	type MaxRoyalty = MaxRoyalty;
+	type OnTokenChange = ();
+}
+
+impl pallet_buyout::Config for Runtime {
+	type Event = Event;
+	type WeightInfo = ();
+	type BuyoutPeriod = BuyoutPeriod;
+	type MaxBuyoutsPerBlock = MaxBuyoutsPerBlock;
+}
+
+pub const ALICE: AccountId = 1;
+pub const BOB: AccountId = 2;
+pub const CHARLIE: AccountId = 3;
+pub const DAVE: AccountId = 4;
+/// Account without a balance
+pub const EVE: AccountId = 5;
+pub const INITIAL_BALANCE: u64 = 1_000;
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut storage = frame_system::GenesisConfig::default()
+		.build_storage::<Runtime>()
+		.unwrap();
+
+	pallet_balances::GenesisConfig::<Runtime> {
+		balances: vec![
+			(ALICE, INITIAL_BALANCE),
+			(BOB, INITIAL_BALANCE),
+			(CHARLIE, INITIAL_BALANCE),
+			(DAVE, INITIAL_BALANCE),
+		],
+	}
+	.assimilate_storage(&mut storage)
+	.unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(storage);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+pub fn last_event() -> Event {
+	frame_system::Pallet::<Runtime>::events()
+		.pop()
+		.expect("Event expected")
+		.event
+}
+
+pub fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		System::set_block_number(System::block_number() + 1);
+		Buyout::on_initialize(System::block_number());
+	}
+}
@@ -0,0 +1,273 @@
+#![cfg(test)]
+
+use crate::mock::{Event, *};
+use crate::{BuyoutEndCount, BuyoutInfo, Error};
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::Perbill;
+
+/// Token minted to ALICE with 30 shares moved to BOB and 20 to CHARLIE
+fn co_owned_token() -> (u32, u32) {
+	let class_id = BaseNft::create_class(&ALICE, vec![1], ()).unwrap();
+	let token_id = BaseNft::mint(&ALICE, class_id, vec![1], ()).unwrap();
+	let token = (class_id, token_id);
+	assert_ok!(BaseNft::transfer(&ALICE, &BOB, token, 30));
+	assert_ok!(BaseNft::transfer(&ALICE, &CHARLIE, token, 20));
+	token
+}
+
+#[test]
+fn propose_buyout_should_work() {
+	new_test_ext().execute_with(|| {
+		let token = co_owned_token();
+
+		assert_ok!(Buyout::propose_buyout(Origin::signed(ALICE), token, 1_000));
+
+		assert_eq!(
+			Buyout::buyouts(token.0, token.1),
+			Some(BuyoutInfo {
+				buyer: ALICE,
+				price: 1_000,
+				reserved: 500,
+				end: 11,
+				royalty: vec![],
+			})
+		);
+		assert_eq!(Buyout::buyout_end_time(11, token), Some(()));
+		assert_eq!(Balances::reserved_balance(ALICE), 500);
+		assert!(BaseNft::is_locked(token));
+		assert_eq!(
+			last_event(),
+			Event::pallet_buyout(crate::Event::BuyoutProposed(ALICE, token, 1_000, 11))
+		);
+
+		// shares only move through the buyout
+		assert_noop!(
+			BaseNft::transfer(&CHARLIE, &DAVE, token, 10),
+			base_nft::Error::<Runtime>::TokenLocked
+		);
+	});
+}
+
+#[test]
+fn propose_buyout_should_fail() {
+	new_test_ext().execute_with(|| {
+		let class_id = BaseNft::create_class(&ALICE, vec![1], ()).unwrap();
+		let token_id = BaseNft::mint(&ALICE, class_id, vec![1], ()).unwrap();
+		let token = (class_id, token_id);
+
+		assert_noop!(
+			Buyout::propose_buyout(Origin::signed(ALICE), token, 1_000),
+			Error::<Runtime>::AlreadyWholeOwner
+		);
+
+		assert_ok!(BaseNft::transfer(&ALICE, &BOB, token, 50));
+		assert_noop!(
+			Buyout::propose_buyout(Origin::signed(ALICE), token, 0),
+			Error::<Runtime>::ZeroPrice
+		);
+		assert_noop!(
+			Buyout::propose_buyout(Origin::signed(DAVE), token, 1_000),
+			Error::<Runtime>::NotHolder
+		);
+		assert_noop!(
+			Buyout::propose_buyout(Origin::signed(ALICE), token, INITIAL_BALANCE * 4),
+			pallet_balances::Error::<Runtime>::InsufficientBalance
+		);
+
+		assert_ok!(BaseNft::lock(token));
+		assert_noop!(
+			Buyout::propose_buyout(Origin::signed(ALICE), token, 1_000),
+			base_nft::Error::<Runtime>::TokenLocked
+		);
+		BaseNft::unlock(token);
+
+		assert_ok!(Buyout::propose_buyout(Origin::signed(ALICE), token, 1_000));
+		assert_noop!(
+			Buyout::propose_buyout(Origin::signed(BOB), token, 2_000),
+			Error::<Runtime>::BuyoutInProgress
+		);
+	});
+}
+
+#[test]
+fn accept_buyout_should_work() {
+	new_test_ext().execute_with(|| {
+		let token = co_owned_token();
+		assert_ok!(Buyout::propose_buyout(Origin::signed(ALICE), token, 1_000));
+
+		assert_ok!(Buyout::accept_buyout(Origin::signed(BOB), token));
+
+		assert_eq!(BaseNft::shares_of(&ALICE, token), 80);
+		assert_eq!(BaseNft::shares_of(&BOB, token), 0);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE + 300);
+		assert_eq!(Balances::reserved_balance(ALICE), 200);
+		assert_eq!(Buyout::buyouts(token.0, token.1).unwrap().reserved, 200);
+		assert!(BaseNft::is_locked(token));
+		assert_eq!(
+			last_event(),
+			Event::pallet_buyout(crate::Event::SharesSold(BOB, ALICE, token, 30, 300))
+		);
+
+		assert_noop!(
+			Buyout::accept_buyout(Origin::signed(BOB), token),
+			Error::<Runtime>::NotHolder
+		);
+		assert_noop!(
+			Buyout::accept_buyout(Origin::signed(ALICE), token),
+			Error::<Runtime>::CallerIsBuyer
+		);
+		assert_noop!(
+			Buyout::accept_buyout(Origin::signed(CHARLIE), (token.0, token.1 + 1)),
+			Error::<Runtime>::BuyoutNotFound
+		);
+	});
+}
+
+#[test]
+fn buyout_completes_at_the_end() {
+	new_test_ext().execute_with(|| {
+		let token = co_owned_token();
+		assert_ok!(Buyout::propose_buyout(Origin::signed(ALICE), token, 1_000));
+		assert_ok!(Buyout::accept_buyout(Origin::signed(BOB), token));
+
+		run_to_block(11);
+
+		assert_eq!(BaseNft::owners_of_token(token, 0, 10), vec![(ALICE, 100)]);
+		assert_eq!(BaseNft::shares_of(&CHARLIE, token), 0);
+		assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE + 200);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE - 500);
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert!(!BaseNft::is_locked(token));
+		assert_eq!(Buyout::buyouts(token.0, token.1), None);
+		assert_eq!(Buyout::buyout_end_time(11, token), None);
+		assert_eq!(
+			last_event(),
+			Event::pallet_buyout(crate::Event::BuyoutCompleted(ALICE, token, 1_000))
+		);
+	});
+}
+
+#[test]
+fn buyouts_per_block_are_bounded() {
+	new_test_ext().execute_with(|| {
+		let first = co_owned_token();
+		let second = co_owned_token();
+		let third = co_owned_token();
+		assert_ok!(Buyout::propose_buyout(Origin::signed(ALICE), first, 100));
+		assert_ok!(Buyout::propose_buyout(Origin::signed(ALICE), second, 100));
+		assert_eq!(Buyout::buyout_end_count(11), 2);
+
+		assert_noop!(
+			Buyout::propose_buyout(Origin::signed(ALICE), third, 100),
+			Error::<Runtime>::TooManyBuyoutsEnding
+		);
+		assert!(!BaseNft::is_locked(third));
+
+		run_to_block(2);
+		assert_ok!(Buyout::propose_buyout(Origin::signed(ALICE), third, 100));
+		assert_eq!(Buyout::buyout_end_count(12), 1);
+
+		run_to_block(11);
+		assert_eq!(Buyout::buyout_end_count(11), 0);
+		assert_eq!(BaseNft::shares_of(&ALICE, first), 100);
+		assert_eq!(BaseNft::shares_of(&ALICE, second), 100);
+		assert!(BaseNft::is_locked(third));
+	});
+}
+
+#[test]
+fn counter_buyout_should_work() {
+	new_test_ext().execute_with(|| {
+		let token = co_owned_token();
+		assert_ok!(Buyout::propose_buyout(Origin::signed(ALICE), token, 1_000));
+
+		assert_noop!(
+			Buyout::counter_buyout(Origin::signed(ALICE), token),
+			Error::<Runtime>::CallerIsBuyer
+		);
+		assert_noop!(
+			Buyout::counter_buyout(Origin::signed(DAVE), token),
+			Error::<Runtime>::NotHolder
+		);
+
+		run_to_block(5);
+		assert_ok!(Buyout::counter_buyout(Origin::signed(BOB), token));
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_eq!(Balances::reserved_balance(BOB), 700);
+		assert_eq!(
+			last_event(),
+			Event::pallet_buyout(crate::Event::BuyoutCountered(BOB, ALICE, token, 15))
+		);
+
+		// the period restarts so that the proposer can counter back
+		assert!(!BuyoutEndCount::<Runtime>::contains_key(11));
+		assert_eq!(Buyout::buyout_end_count(15), 1);
+		run_to_block(11);
+		assert!(BaseNft::is_locked(token));
+
+		// the proposer is bought out at its own price
+		run_to_block(15);
+		assert_eq!(BaseNft::shares_of(&BOB, token), 100);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 500);
+		assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE + 200);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE - 700);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+	});
+}
+
+#[test]
+fn buyout_pays_holders_independently() {
+	new_test_ext().execute_with(|| {
+		let token = co_owned_token();
+		assert_ok!(BaseNft::transfer(&CHARLIE, &EVE, token, 10));
+		assert_ok!(Buyout::propose_buyout(Origin::signed(ALICE), token, 1_000));
+
+		// EVE has no account to be paid into, the others are paid regardless
+		run_to_block(11);
+		assert_eq!(BaseNft::shares_of(&ALICE, token), 100);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE + 300);
+		assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE + 100);
+		assert_eq!(Balances::free_balance(EVE), 0);
+		assert_eq!(BaseNft::unclaimed_proceeds(EVE, ALICE), 100);
+		assert_eq!(Balances::reserved_balance(ALICE), 100);
+		assert_eq!(
+			last_event(),
+			Event::pallet_buyout(crate::Event::BuyoutCompleted(ALICE, token, 1_000))
+		);
+
+		// paid once EVE has an account
+		assert_noop!(
+			BaseNft::claim_proceeds(&EVE, &ALICE),
+			pallet_balances::Error::<Runtime>::DeadAccount
+		);
+		assert_ok!(Balances::transfer(Origin::signed(DAVE), EVE, 1));
+		assert_ok!(BaseNft::claim_proceeds(&EVE, &ALICE));
+		assert_eq!(Balances::free_balance(EVE), 101);
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_eq!(BaseNft::unclaimed_proceeds(EVE, ALICE), 0);
+		assert_noop!(
+			BaseNft::claim_proceeds(&EVE, &ALICE),
+			base_nft::Error::<Runtime>::NoUnclaimedProceeds
+		);
+	});
+}
+
+#[test]
+fn buyout_pays_royalties() {
+	new_test_ext().execute_with(|| {
+		let token = co_owned_token();
+		assert_ok!(BaseNft::set_class_royalty(
+			&ALICE,
+			token.0,
+			vec![(DAVE, Perbill::from_percent(10))]
+		));
+		assert_ok!(Buyout::propose_buyout(Origin::signed(ALICE), token, 1_000));
+
+		run_to_block(11);
+
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE + 270);
+		assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE + 180);
+		assert_eq!(Balances::free_balance(DAVE), INITIAL_BALANCE + 50);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE - 500);
+	});
+}
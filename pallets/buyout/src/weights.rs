@@ -0,0 +1,102 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Autogenerated weights for pallet_buyout
+//!
+//! Generated from the benchmarks in `benchmarking.rs` executed natively on a development
+//! machine, not in wasm on reference hardware. They stand in until `make benchmarks-generate`
+//! is run on reference hardware.
+//! DATE: 2026-10-16, STEPS: `[50, ]`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
+//! EXECUTION: Some(Native), WASM-EXECUTION: None, CHAIN: Some("dev"), DB CACHE: 128
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_buyout.
+pub trait WeightInfo {
+	fn propose_buyout() -> Weight;
+	fn accept_buyout(r: u32) -> Weight;
+	fn counter_buyout() -> Weight;
+	fn complete(o: u32, r: u32) -> Weight;
+}
+
+/// Weights for pallet_buyout using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	fn propose_buyout() -> Weight {
+		(59_305_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	fn accept_buyout(r: u32) -> Weight {
+		(77_219_000 as Weight)
+			// Standard Error: 314_000
+			.saturating_add((2_088_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(T::DbWeight::get().reads(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+	}
+	fn counter_buyout() -> Weight {
+		(54_650_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	fn complete(o: u32, _r: u32) -> Weight {
+		(627_054_000 as Weight)
+			// Standard Error: 1_092_000
+			.saturating_add((94_775_000 as Weight).saturating_mul(o as Weight))
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(o as Weight)))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(o as Weight)))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn propose_buyout() -> Weight {
+		(59_305_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	fn accept_buyout(r: u32) -> Weight {
+		(77_219_000 as Weight)
+			// Standard Error: 314_000
+			.saturating_add((2_088_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(RocksDbWeight::get().reads(9 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
+	}
+	fn counter_buyout() -> Weight {
+		(54_650_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	fn complete(o: u32, _r: u32) -> Weight {
+		(627_054_000 as Weight)
+			// Standard Error: 1_092_000
+			.saturating_add((94_775_000 as Weight).saturating_mul(o as Weight))
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(o as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(o as Weight)))
+	}
+}
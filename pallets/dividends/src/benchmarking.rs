@@ -0,0 +1,100 @@
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_system::RawOrigin;
+use sp_runtime::traits::Bounded;
+use sp_std::{boxed::Box, vec, vec::Vec};
+
+fn funded_account<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+	let who: T::AccountId = account(name, index, 0);
+	<T as base_nft::Config>::Currency::make_free_balance_be(
+		&who,
+		BalanceOf::<T>::max_value() / 2_u32.into(),
+	);
+	who
+}
+
+/// Token minted to a funded owner
+fn mint_token<T: Config>(owner: &T::AccountId) -> (T::ClassId, T::TokenId) {
+	let class_id = BaseNft::<T>::create_class(owner, vec![1_u8], Default::default()).unwrap();
+	let token_id = BaseNft::<T>::mint(owner, class_id, vec![1_u8], Default::default()).unwrap();
+	(class_id, token_id)
+}
+
+benchmarks! {
+	deposit_revenue {
+		let owner = funded_account::<T>("owner", 0);
+		let token = mint_token::<T>(&owner);
+
+		let caller: T::AccountId = whitelisted_caller();
+		<T as base_nft::Config>::Currency::make_free_balance_be(
+			&caller,
+			BalanceOf::<T>::max_value() / 2_u32.into(),
+		);
+	}: _(RawOrigin::Signed(caller), token, 1_000_000_u32.into())
+
+	claim {
+		let caller: T::AccountId = whitelisted_caller();
+		<T as base_nft::Config>::Currency::make_free_balance_be(
+			&caller,
+			BalanceOf::<T>::max_value() / 2_u32.into(),
+		);
+		let token = mint_token::<T>(&caller);
+		let depositor = funded_account::<T>("depositor", 0);
+		Pallet::<T>::deposit_revenue(
+			RawOrigin::Signed(depositor).into(),
+			token,
+			1_000_000_u32.into(),
+		)?;
+	}: _(RawOrigin::Signed(caller), token)
+
+	// both holders are settled on revenue deposited since their last checkpoints
+	on_transfer {
+		let seller = funded_account::<T>("seller", 0);
+		let token = mint_token::<T>(&seller);
+		let buyer = funded_account::<T>("buyer", 0);
+		let shares: T::Shares = 1_u32.into();
+		let depositor = funded_account::<T>("depositor", 0);
+		Pallet::<T>::deposit_revenue(
+			RawOrigin::Signed(depositor.clone()).into(),
+			token,
+			1_000_000_u32.into(),
+		)?;
+		BaseNft::<T>::transfer(&seller, &buyer, token, shares)?;
+		Pallet::<T>::deposit_revenue(
+			RawOrigin::Signed(depositor).into(),
+			token,
+			1_000_000_u32.into(),
+		)?;
+		BaseNft::<T>::transfer(&seller, &buyer, token, shares)?;
+	}: {
+		<Pallet<T> as OnTokenChange<_, _, _, _>>::on_transfer(&seller, &buyer, token, shares);
+	}
+
+	// each of the `o` owners is settled on its last shares
+	on_burn {
+		let o in 1 .. T::MaxOwnersPerToken::get();
+
+		let owner = funded_account::<T>("owner", 0);
+		let token = mint_token::<T>(&owner);
+		let mut owners = vec![(owner.clone(), T::SharesPerToken::get())];
+		for i in 1 .. o {
+			let holder = funded_account::<T>("holder", i);
+			BaseNft::<T>::transfer(&owner, &holder, token, 1_u32.into())?;
+			owners.push((holder, 1_u32.into()));
+		}
+		Pallet::<T>::deposit_revenue(
+			RawOrigin::Signed(funded_account::<T>("depositor", 0)).into(),
+			token,
+			1_000_000_u32.into(),
+		)?;
+	}: {
+		<Pallet<T> as OnTokenChange<_, _, _, _>>::on_burn(token, &owners);
+	}
+	verify {
+		assert_eq!(RevenuePerShare::<T>::get(token), 0);
+	}
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Runtime);
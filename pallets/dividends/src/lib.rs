@@ -0,0 +1,328 @@
+//! # Dividends
+//! Revenue distribution to the holders of fractionally owned `base_nft` tokens.
+//!
+//! Anyone deposits currency earned by a token, e.g. rentals or royalties, into the pallet
+//! account. Every holder claims the part of each deposit matching the shares it held when the
+//! deposit was made.
+//!
+//! A deposit only grows an accumulator of the revenue per share of the token, so it costs the
+//! same whatever the number of owners. Holders are settled lazily against a checkpoint of the
+//! accumulator when they claim or when their shares change, the pallet implements
+//! `base_nft::OnTokenChange` to settle both sides of a transfer and every owner of a burned
+//! token. Settled revenue stays claimable after the shares are gone.
+//!
+//! `Config::RevenueFilter` rejects deposits for tokens whose holders cannot claim, e.g. tokens
+//! held by the fractions pallet account.
+//!
+//! The pallet account is endowed with the existential deposit at genesis, or by the runtime
+//! upgrade adding the pallet to a live chain, so it is never reaped by a claim and accepts
+//! deposits of any amount.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use base_nft::{BalanceOf, Module as BaseNft, OnTokenChange};
+use frame_support::{
+	dispatch::DispatchResultWithPostInfo,
+	pallet_prelude::*,
+	traits::{Currency, ExistenceRequirement, Filter, GenesisBuild},
+	transactional,
+};
+use frame_system::pallet_prelude::*;
+use sp_runtime::{
+	helpers_128bit::multiply_by_rational,
+	traits::{AccountIdConversion, SaturatedConversion, Saturating, Zero},
+	DispatchResult, ModuleId, RuntimeDebug,
+};
+
+pub use pallet::*;
+
+mod mock;
+mod tests;
+
+mod benchmarking;
+pub mod weights;
+pub use weights::WeightInfo;
+
+/// Scale of `RevenuePerShare`, keeps deposits smaller than `SharesPerToken` distributable
+pub const ACCUMULATOR_PRECISION: u128 = 1_000_000_000_000_000_000;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + base_nft::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		type WeightInfo: WeightInfo;
+		/// Identifier of the account holding unclaimed revenue
+		#[pallet::constant]
+		type ModuleId: Get<ModuleId>;
+		/// Tokens accepting revenue deposits
+		type RevenueFilter: Filter<(Self::ClassId, Self::TokenId)>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	/// Revenue deposited per share of a token, scaled by `ACCUMULATOR_PRECISION`.
+	#[pallet::storage]
+	#[pallet::getter(fn revenue_per_share)]
+	pub type RevenuePerShare<T: Config> =
+		StorageMap<_, Twox64Concat, (T::ClassId, T::TokenId), u128, ValueQuery>;
+
+	/// `RevenuePerShare` of a token when a holder was last settled.
+	#[pallet::storage]
+	#[pallet::getter(fn checkpoints)]
+	pub type Checkpoints<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		(T::ClassId, T::TokenId),
+		Twox64Concat,
+		T::AccountId,
+		u128,
+		ValueQuery,
+	>;
+
+	/// Revenue of a token settled to a holder and not claimed yet.
+	#[pallet::storage]
+	#[pallet::getter(fn unclaimed)]
+	pub type Unclaimed<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		(T::ClassId, T::TokenId),
+		Twox64Concat,
+		T::AccountId,
+		BalanceOf<T>,
+		ValueQuery,
+	>;
+
+	/// Storage layout version of the pallet.
+	#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug)]
+	pub enum Releases {
+		/// The pallet account may hold less than the existential deposit
+		V1_0_0,
+		/// The pallet account is endowed with the existential deposit
+		V2_0_0,
+	}
+
+	impl Default for Releases {
+		fn default() -> Self {
+			Releases::V1_0_0
+		}
+	}
+
+	/// Storage version of the pallet.
+	///
+	/// New networks start with the last version.
+	#[pallet::storage]
+	pub(super) type StorageVersion<T: Config> = StorageValue<_, Releases, ValueQuery>;
+
+	#[pallet::genesis_config]
+	#[derive(Default)]
+	pub struct GenesisConfig {}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig {
+		fn build(&self) {
+			Pallet::<T>::endow_account();
+			StorageVersion::<T>::put(Releases::V2_0_0);
+		}
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Deposit must not be zero
+		ZeroAmount,
+		/// Holder has no revenue to claim for the token
+		NothingToClaim,
+		/// Token does not accept revenue
+		RevenueRejected,
+	}
+
+	#[pallet::event]
+	#[pallet::metadata(T::AccountId = "AccountId", BalanceOf<T> = "Balance")]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Revenue was deposited for the holders of a token. \[depositor, token, amount\]
+		RevenueDeposited(T::AccountId, (T::ClassId, T::TokenId), BalanceOf<T>),
+		/// Holder claimed its revenue of a token. \[holder, token, amount\]
+		RevenueClaimed(T::AccountId, (T::ClassId, T::TokenId), BalanceOf<T>),
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::<T>::get() == Releases::V1_0_0 {
+				Pallet::<T>::endow_account();
+				StorageVersion::<T>::put(Releases::V2_0_0);
+				T::DbWeight::get().reads_writes(2, 2)
+			} else {
+				0
+			}
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Deposit `amount` to be shared by the current holders of `token` pro rata
+		#[pallet::weight(T::WeightInfo::deposit_revenue())]
+		#[transactional]
+		pub fn deposit_revenue(
+			origin: OriginFor<T>,
+			token: (T::ClassId, T::TokenId),
+			amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let depositor = ensure_signed(origin)?;
+
+			ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+			ensure!(
+				base_nft::Tokens::<T>::contains_key(token.0, token.1),
+				base_nft::Error::<T>::TokenNotFound
+			);
+			ensure!(
+				T::RevenueFilter::filter(&token),
+				Error::<T>::RevenueRejected
+			);
+
+			let per_share = multiply_by_rational(
+				amount.saturated_into(),
+				ACCUMULATOR_PRECISION,
+				T::SharesPerToken::get().saturated_into(),
+			)
+			.map_err(|_| base_nft::Error::<T>::NumOverflow)?;
+			RevenuePerShare::<T>::try_mutate(token, |accumulator| -> DispatchResult {
+				*accumulator = accumulator
+					.checked_add(per_share)
+					.ok_or(base_nft::Error::<T>::NumOverflow)?;
+				Ok(())
+			})?;
+			<T as base_nft::Config>::Currency::transfer(
+				&depositor,
+				&Self::account_id(),
+				amount,
+				ExistenceRequirement::KeepAlive,
+			)?;
+
+			Self::deposit_event(Event::RevenueDeposited(depositor, token, amount));
+			Ok(().into())
+		}
+
+		/// Claim the revenue of `token` owed to the caller
+		#[pallet::weight(T::WeightInfo::claim())]
+		#[transactional]
+		pub fn claim(
+			origin: OriginFor<T>,
+			token: (T::ClassId, T::TokenId),
+		) -> DispatchResultWithPostInfo {
+			let holder = ensure_signed(origin)?;
+
+			let shares = BaseNft::<T>::shares_of(&holder, token);
+			Self::settle(&holder, token, shares, shares);
+			let amount = Unclaimed::<T>::take(token, &holder);
+			ensure!(!amount.is_zero(), Error::<T>::NothingToClaim);
+
+			<T as base_nft::Config>::Currency::transfer(
+				&Self::account_id(),
+				&holder,
+				amount,
+				ExistenceRequirement::KeepAlive,
+			)?;
+
+			Self::deposit_event(Event::RevenueClaimed(holder, token, amount));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Account holding unclaimed revenue and the existential deposit
+	pub fn account_id() -> T::AccountId {
+		T::ModuleId::get().into_account()
+	}
+
+	/// Top up the pallet account to the existential deposit
+	fn endow_account() {
+		let account_id = Self::account_id();
+		let min = <T as base_nft::Config>::Currency::minimum_balance();
+		if <T as base_nft::Config>::Currency::free_balance(&account_id) < min {
+			let _ = <T as base_nft::Config>::Currency::make_free_balance_be(&account_id, min);
+		}
+	}
+
+	/// Revenue of `token` the holder would receive by claiming now
+	pub fn pending_revenue(holder: &T::AccountId, token: (T::ClassId, T::TokenId)) -> BalanceOf<T> {
+		let shares = BaseNft::<T>::shares_of(holder, token);
+		Unclaimed::<T>::get(token, holder).saturating_add(Self::accrued(
+			holder,
+			token,
+			shares,
+			RevenuePerShare::<T>::get(token),
+		))
+	}
+
+	/// Revenue earned by `shares` since the checkpoint of the holder
+	fn accrued(
+		holder: &T::AccountId,
+		token: (T::ClassId, T::TokenId),
+		shares: T::Shares,
+		accumulator: u128,
+	) -> BalanceOf<T> {
+		let delta = accumulator.saturating_sub(Checkpoints::<T>::get(token, holder));
+		multiply_by_rational(shares.saturated_into(), delta, ACCUMULATOR_PRECISION)
+			.map(SaturatedConversion::saturated_into)
+			.unwrap_or_else(|_| Zero::zero())
+	}
+
+	/// Move the revenue earned by the `before` shares of the holder to `Unclaimed` and checkpoint
+	/// the holder for its `after` shares
+	fn settle(
+		holder: &T::AccountId,
+		token: (T::ClassId, T::TokenId),
+		before: T::Shares,
+		after: T::Shares,
+	) {
+		let accumulator = RevenuePerShare::<T>::get(token);
+		let accrued = Self::accrued(holder, token, before, accumulator);
+		if !accrued.is_zero() {
+			Unclaimed::<T>::mutate(token, holder, |unclaimed| {
+				*unclaimed = unclaimed.saturating_add(accrued)
+			});
+		}
+		if after.is_zero() {
+			Checkpoints::<T>::remove(token, holder);
+		} else {
+			Checkpoints::<T>::insert(token, holder, accumulator);
+		}
+	}
+}
+
+impl<T: Config> OnTokenChange<T::AccountId, T::ClassId, T::TokenId, T::Shares> for Pallet<T> {
+	/// Settle both holders on the shares they held before the transfer
+	fn on_transfer(
+		from: &T::AccountId,
+		to: &T::AccountId,
+		token: (T::ClassId, T::TokenId),
+		shares: T::Shares,
+	) {
+		let from_after = BaseNft::<T>::shares_of(from, token);
+		Self::settle(from, token, from_after.saturating_add(shares), from_after);
+		let to_after = BaseNft::<T>::shares_of(to, token);
+		Self::settle(to, token, to_after.saturating_sub(shares), to_after);
+	}
+
+	fn on_burn(token: (T::ClassId, T::TokenId), owners: &[(T::AccountId, T::Shares)]) {
+		for (owner, shares) in owners {
+			Self::settle(owner, token, *shares, Zero::zero());
+		}
+		RevenuePerShare::<T>::remove(token);
+	}
+
+	fn on_transfer_weight() -> Weight {
+		T::WeightInfo::on_transfer()
+	}
+
+	fn on_burn_weight(owners: u32) -> Weight {
+		T::WeightInfo::on_burn(owners)
+	}
+}
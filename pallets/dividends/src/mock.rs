@@ -0,0 +1,172 @@
+#![cfg(test)]
+
+use crate as pallet_dividends;
+use frame_support::{
+	parameter_types,
+	traits::{Filter, GenesisBuild},
+};
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+	ModuleId, Perbill, Storage,
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+frame_support::construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Module, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
+		BaseNft: base_nft::{Module, Storage, Event<T>},
+		Dividends: pallet_dividends::{Module, Call, Storage, Config, Event<T>},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const SS58Prefix: u8 = 42;
+	pub const ExistentialDeposit: u64 = 10;
+	pub const SharesPerToken: u64 = 100;
+	pub const BurnApprovalThreshold: Perbill = Perbill::from_percent(75);
+	pub const MaxMetadataLength: u32 = 256;
+	pub const MaxOwnersPerToken: u32 = 10;
+	pub const ClassDeposit: u64 = 0;
+	pub const TokenDeposit: u64 = 0;
+	pub const DepositPerByte: u64 = 0;
+	pub const MaxRoyaltyBeneficiaries: u32 = 3;
+	pub const MaxRoyalty: Perbill = Perbill::from_percent(50);
+	pub const DividendsModuleId: ModuleId = ModuleId(*b"anm/divi");
+}
+
+pub type AccountId = u64;
+
+impl frame_system::Config for Runtime {
+	type BaseCallFilter = ();
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = SS58Prefix;
+}
+
+impl pallet_balances::Config for Runtime {
+	type MaxLocks = ();
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+impl base_nft::Config for Runtime {
+	type Event = Event;
+	type ClassId = u32;
+	type TokenId = u32;
+	type ClassData = ();
+	type TokenData = ();
+	type Shares = u64;
+	type SharesPerToken = SharesPerToken;
+	type BurnApprovalThreshold = BurnApprovalThreshold;
+	type Currency = Balances;
+	type MaxMetadataLength = MaxMetadataLength;
+	type MaxOwnersPerToken = MaxOwnersPerToken;
+	type ClassDeposit = ClassDeposit;
+	type TokenDeposit = TokenDeposit;
+	type DepositPerByte = DepositPerByte;
+	type MaxRoyaltyBeneficiaries = MaxRoyaltyBeneficiaries;
+	type MaxRoyalty = MaxRoyalty;
+	type OnTokenChange = Dividends;
+}
+
+impl pallet_dividends::Config for Runtime {
+	type Event = Event;
+	type WeightInfo = ();
+	type ModuleId = DividendsModuleId;
+	type RevenueFilter = RevenueFilter;
+}
+
+/// Tokens held by `CUSTODY` do not accept revenue, like fractionalized tokens
+pub struct RevenueFilter;
+
+impl Filter<(u32, u32)> for RevenueFilter {
+	fn filter(token: &(u32, u32)) -> bool {
+		BaseNft::shares_of(&CUSTODY, *token) == 0
+	}
+}
+
+pub const ALICE: AccountId = 1;
+pub const BOB: AccountId = 2;
+pub const CHARLIE: AccountId = 3;
+pub const DAVE: AccountId = 4;
+pub const CUSTODY: AccountId = 5;
+pub const INITIAL_BALANCE: u64 = 1_000;
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut storage = base_storage();
+
+	GenesisBuild::<Runtime>::assimilate_storage(
+		&pallet_dividends::GenesisConfig::default(),
+		&mut storage,
+	)
+	.unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(storage);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Chain the pallet is added to by a runtime upgrade, without its genesis
+pub fn new_upgrade_test_ext() -> sp_io::TestExternalities {
+	let mut ext = sp_io::TestExternalities::new(base_storage());
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+fn base_storage() -> Storage {
+	let mut storage = frame_system::GenesisConfig::default()
+		.build_storage::<Runtime>()
+		.unwrap();
+
+	pallet_balances::GenesisConfig::<Runtime> {
+		balances: vec![
+			(ALICE, INITIAL_BALANCE),
+			(BOB, INITIAL_BALANCE),
+			(CHARLIE, INITIAL_BALANCE),
+			(DAVE, INITIAL_BALANCE),
+		],
+	}
+	.assimilate_storage(&mut storage)
+	.unwrap();
+
+	storage
+}
+
+pub fn last_event() -> Event {
+	frame_system::Pallet::<Runtime>::events()
+		.pop()
+		.expect("Event expected")
+		.event
+}
@@ -0,0 +1,186 @@
+#![cfg(test)]
+
+use crate::mock::{Event, *};
+use crate::{Error, Releases, StorageVersion};
+use frame_support::{assert_noop, assert_ok, traits::OnRuntimeUpgrade};
+
+/// Token minted to `owner` in a new class
+fn mint_token(owner: &AccountId) -> (u32, u32) {
+	let class_id = BaseNft::create_class(owner, vec![1], ()).unwrap();
+	let token_id = BaseNft::mint(owner, class_id, vec![1], ()).unwrap();
+	(class_id, token_id)
+}
+
+#[test]
+fn deposit_and_claim_should_work() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+		assert_ok!(BaseNft::transfer(&ALICE, &BOB, token, 25));
+
+		assert_ok!(Dividends::deposit_revenue(
+			Origin::signed(CHARLIE),
+			token,
+			100
+		));
+		assert_eq!(
+			last_event(),
+			Event::pallet_dividends(crate::Event::RevenueDeposited(CHARLIE, token, 100))
+		);
+		assert_eq!(Balances::free_balance(Dividends::account_id()), 110);
+		assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE - 100);
+		assert_eq!(Dividends::pending_revenue(&ALICE, token), 75);
+		assert_eq!(Dividends::pending_revenue(&BOB, token), 25);
+
+		assert_ok!(Dividends::claim(Origin::signed(BOB), token));
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE + 25);
+		assert_eq!(Dividends::pending_revenue(&BOB, token), 0);
+		assert_eq!(
+			last_event(),
+			Event::pallet_dividends(crate::Event::RevenueClaimed(BOB, token, 25))
+		);
+		assert_noop!(
+			Dividends::claim(Origin::signed(BOB), token),
+			Error::<Runtime>::NothingToClaim
+		);
+		assert_noop!(
+			Dividends::claim(Origin::signed(CHARLIE), token),
+			Error::<Runtime>::NothingToClaim
+		);
+
+		// revenue below a unit per holder accumulates until it can be paid
+		assert_ok!(Dividends::deposit_revenue(
+			Origin::signed(CHARLIE),
+			token,
+			2
+		));
+		assert_eq!(Dividends::pending_revenue(&BOB, token), 0);
+		assert_ok!(Dividends::deposit_revenue(
+			Origin::signed(CHARLIE),
+			token,
+			2
+		));
+		assert_eq!(Dividends::pending_revenue(&BOB, token), 1);
+		assert_eq!(Dividends::pending_revenue(&ALICE, token), 78);
+	});
+}
+
+#[test]
+fn holders_claim_in_turn() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+		assert_ok!(BaseNft::transfer(&ALICE, &BOB, token, 50));
+
+		// a first deposit below the existential deposit is accepted
+		assert_ok!(Dividends::deposit_revenue(Origin::signed(DAVE), token, 4));
+		assert_ok!(Dividends::deposit_revenue(Origin::signed(DAVE), token, 100));
+
+		// the pallet account keeps the existential deposit once every holder claimed
+		assert_ok!(Dividends::claim(Origin::signed(ALICE), token));
+		assert_ok!(Dividends::claim(Origin::signed(BOB), token));
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 52);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE + 52);
+		assert_eq!(
+			Balances::free_balance(Dividends::account_id()),
+			ExistentialDeposit::get()
+		);
+
+		assert_ok!(Dividends::deposit_revenue(Origin::signed(DAVE), token, 2));
+		assert_ok!(Dividends::claim(Origin::signed(BOB), token));
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE + 53);
+	});
+}
+
+#[test]
+fn deposit_revenue_should_fail() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+
+		assert_noop!(
+			Dividends::deposit_revenue(Origin::signed(CHARLIE), token, 0),
+			Error::<Runtime>::ZeroAmount
+		);
+		assert_noop!(
+			Dividends::deposit_revenue(Origin::signed(CHARLIE), (token.0, 1), 100),
+			base_nft::Error::<Runtime>::TokenNotFound
+		);
+		assert_noop!(
+			Dividends::deposit_revenue(Origin::signed(CHARLIE), token, INITIAL_BALANCE * 2),
+			pallet_balances::Error::<Runtime>::InsufficientBalance
+		);
+
+		assert_ok!(BaseNft::transfer(&ALICE, &CUSTODY, token, 1));
+		assert_noop!(
+			Dividends::deposit_revenue(Origin::signed(CHARLIE), token, 100),
+			Error::<Runtime>::RevenueRejected
+		);
+	});
+}
+
+#[test]
+fn transfer_settles_holders() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+		assert_ok!(Dividends::deposit_revenue(Origin::signed(DAVE), token, 100));
+
+		// revenue deposited before a transfer stays with the sender
+		assert_ok!(BaseNft::transfer(&ALICE, &BOB, token, 50));
+		assert_eq!(Dividends::unclaimed(token, ALICE), 100);
+		assert_eq!(Dividends::pending_revenue(&BOB, token), 0);
+
+		assert_ok!(Dividends::deposit_revenue(Origin::signed(DAVE), token, 100));
+		assert_eq!(Dividends::pending_revenue(&ALICE, token), 150);
+		assert_eq!(Dividends::pending_revenue(&BOB, token), 50);
+
+		assert_ok!(BaseNft::transfer(&BOB, &CHARLIE, token, 50));
+		assert_eq!(Dividends::checkpoints(token, BOB), 0);
+		assert_ok!(Dividends::deposit_revenue(Origin::signed(DAVE), token, 10));
+		assert_eq!(Dividends::pending_revenue(&ALICE, token), 155);
+		assert_eq!(Dividends::pending_revenue(&BOB, token), 50);
+		assert_eq!(Dividends::pending_revenue(&CHARLIE, token), 5);
+
+		assert_ok!(Dividends::claim(Origin::signed(ALICE), token));
+		assert_ok!(Dividends::claim(Origin::signed(BOB), token));
+		assert_ok!(Dividends::claim(Origin::signed(CHARLIE), token));
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 155);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE + 50);
+		assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE + 5);
+		assert_eq!(Balances::free_balance(Dividends::account_id()), 10);
+	});
+}
+
+#[test]
+fn burn_keeps_settled_revenue() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+		assert_ok!(BaseNft::transfer(&ALICE, &BOB, token, 40));
+		assert_ok!(Dividends::deposit_revenue(Origin::signed(DAVE), token, 100));
+
+		assert_ok!(BaseNft::burn_with_approvals(&[ALICE, BOB], token));
+
+		assert_eq!(Dividends::revenue_per_share(token), 0);
+		assert_eq!(Dividends::unclaimed(token, ALICE), 60);
+		assert_eq!(Dividends::unclaimed(token, BOB), 40);
+		assert_ok!(Dividends::claim(Origin::signed(BOB), token));
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE + 40);
+	});
+}
+
+#[test]
+fn runtime_upgrade_endows_pallet_account() {
+	new_upgrade_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+		assert_eq!(Balances::free_balance(Dividends::account_id()), 0);
+		assert_noop!(
+			Dividends::deposit_revenue(Origin::signed(DAVE), token, 5),
+			pallet_balances::Error::<Runtime>::ExistentialDeposit
+		);
+
+		Dividends::on_runtime_upgrade();
+
+		assert_eq!(Balances::free_balance(Dividends::account_id()), 10);
+		assert_eq!(StorageVersion::<Runtime>::get(), Releases::V2_0_0);
+		assert_ok!(Dividends::deposit_revenue(Origin::signed(DAVE), token, 5));
+		assert_ok!(Dividends::claim(Origin::signed(ALICE), token));
+		assert_eq!(Balances::free_balance(Dividends::account_id()), 10);
+	});
+}
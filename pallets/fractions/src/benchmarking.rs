@@ -0,0 +1,38 @@
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use base_nft::BalanceOf;
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::traits::Currency;
+use sp_runtime::traits::Bounded;
+use sp_std::{boxed::Box, vec, vec::Vec};
+
+/// Whitelisted caller holding a whole token
+fn token_owner<T: Config>() -> (T::AccountId, (T::ClassId, T::TokenId)) {
+	let caller: T::AccountId = whitelisted_caller();
+	<T as base_nft::Config>::Currency::make_free_balance_be(
+		&caller,
+		BalanceOf::<T>::max_value() / 2_u32.into(),
+	);
+	let class_id = BaseNft::<T>::create_class(&caller, vec![1_u8], Default::default()).unwrap();
+	let token_id = BaseNft::<T>::mint(&caller, class_id, vec![1_u8], Default::default()).unwrap();
+	(caller, (class_id, token_id))
+}
+
+benchmarks! {
+	fractionalize {
+		let (caller, token) = token_owner::<T>();
+	}: _(RawOrigin::Signed(caller), token, Default::default(), 1_000_u32.into())
+
+	redeem {
+		let (caller, token) = token_owner::<T>();
+		Pallet::<T>::fractionalize(
+			RawOrigin::Signed(caller.clone()).into(),
+			token,
+			Default::default(),
+			1_000_u32.into(),
+		)?;
+	}: _(RawOrigin::Signed(caller), Default::default())
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Runtime);
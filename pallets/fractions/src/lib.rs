@@ -0,0 +1,214 @@
+//! # Fractions
+//! Fractionalization of whole `base_nft` tokens into `pallet_assets` fungible assets.
+//!
+//! The holder of every share of a token moves it into the custody of the pallet account, where
+//! it is locked with `base_nft::Pallet::lock`, and receives a fixed supply of a new asset. The
+//! asset trades like any other, e.g. in wallets and AMMs, and whoever holds its entire supply
+//! redeems the token: the supply is burned, the asset destroyed and the token handed over.
+//!
+//! Assets are force created with the pallet account as owner, admin and issuer, so nobody can
+//! mint more of them. `pallet_assets::Config::ForceOrigin` must accept the root origin.
+//!
+//! The pallet account never claims revenue of the tokens it holds. The pallet is a
+//! `Filter` of the tokens that are not fractionalized, for sinks like
+//! `pallet_dividends::Config::RevenueFilter` to reject revenue of fractionalized tokens.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use base_nft::{Module as BaseNft, OnTokenChange};
+use codec::{Decode, Encode};
+use frame_support::{
+	dispatch::DispatchResultWithPostInfo,
+	pallet_prelude::*,
+	traits::{Filter, UnfilteredDispatchable},
+	transactional,
+};
+use frame_system::{pallet_prelude::*, RawOrigin};
+use sp_runtime::{
+	traits::{AccountIdConversion, StaticLookup, Zero},
+	ModuleId, RuntimeDebug,
+};
+
+pub use pallet::*;
+
+mod mock;
+mod tests;
+
+mod benchmarking;
+pub mod weights;
+pub use weights::WeightInfo;
+
+pub type AssetBalanceOf<T> = <T as pallet_assets::Config>::Balance;
+
+/// Token held by the pallet account against an asset
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
+pub struct Fraction<ClassId, TokenId, Balance> {
+	/// Fractionalized token
+	pub token: (ClassId, TokenId),
+	/// Supply of the asset, all of it redeems the token
+	pub supply: Balance,
+}
+
+pub type FractionOf<T> =
+	Fraction<<T as base_nft::Config>::ClassId, <T as base_nft::Config>::TokenId, AssetBalanceOf<T>>;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + base_nft::Config + pallet_assets::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		type WeightInfo: WeightInfo;
+		/// Identifier of the account holding fractionalized tokens
+		#[pallet::constant]
+		type ModuleId: Get<ModuleId>;
+		/// Accounts without a native balance allowed to hold an asset
+		#[pallet::constant]
+		type MaxZombies: Get<u32>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	/// Fractionalized tokens by asset.
+	#[pallet::storage]
+	#[pallet::getter(fn fractions)]
+	pub type Fractions<T: Config> = StorageMap<_, Twox64Concat, T::AssetId, FractionOf<T>>;
+
+	/// Asset of a fractionalized token.
+	#[pallet::storage]
+	#[pallet::getter(fn asset_of)]
+	pub type AssetOf<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::ClassId, Twox64Concat, T::TokenId, T::AssetId>;
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Asset supply must not be zero
+		ZeroSupply,
+		/// Only the holder of every share of a token may fractionalize it
+		NotWholeTokenOwner,
+		/// Asset is not a fractionalized token
+		FractionNotFound,
+		/// Only the holder of the entire asset supply may redeem the token
+		NotWholeSupplyHolder,
+	}
+
+	#[pallet::event]
+	#[pallet::metadata(
+		T::AccountId = "AccountId",
+		T::AssetId = "AssetId",
+		AssetBalanceOf<T> = "Balance"
+	)]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Token was locked against a new asset. \[owner, token, asset_id, supply\]
+		Fractionalized(
+			T::AccountId,
+			(T::ClassId, T::TokenId),
+			T::AssetId,
+			AssetBalanceOf<T>,
+		),
+		/// Asset supply was burned for the token. \[holder, token, asset_id\]
+		Redeemed(T::AccountId, (T::ClassId, T::TokenId), T::AssetId),
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Lock `token` held entirely by the caller and mint `supply` of the new asset
+		/// `asset_id` to the caller
+		#[pallet::weight(
+			<T as Config>::WeightInfo::fractionalize()
+				.saturating_add(<T as base_nft::Config>::OnTokenChange::on_transfer_weight())
+		)]
+		#[transactional]
+		pub fn fractionalize(
+			origin: OriginFor<T>,
+			token: (T::ClassId, T::TokenId),
+			asset_id: T::AssetId,
+			supply: AssetBalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let owner = ensure_signed(origin)?;
+
+			ensure!(!supply.is_zero(), Error::<T>::ZeroSupply);
+			ensure!(
+				BaseNft::<T>::shares_of(&owner, token) == T::SharesPerToken::get(),
+				Error::<T>::NotWholeTokenOwner
+			);
+
+			let account = Self::account_id();
+			BaseNft::<T>::transfer(&owner, &account, token, T::SharesPerToken::get())?;
+			BaseNft::<T>::lock(token)?;
+
+			pallet_assets::Call::<T>::force_create(
+				asset_id,
+				T::Lookup::unlookup(account.clone()),
+				T::MaxZombies::get(),
+				1_u32.into(),
+			)
+			.dispatch_bypass_filter(RawOrigin::Root.into())?;
+			pallet_assets::Call::<T>::mint(asset_id, T::Lookup::unlookup(owner.clone()), supply)
+				.dispatch_bypass_filter(RawOrigin::Signed(account).into())?;
+
+			Fractions::<T>::insert(asset_id, Fraction { token, supply });
+			AssetOf::<T>::insert(token.0, token.1, asset_id);
+
+			Self::deposit_event(Event::Fractionalized(owner, token, asset_id, supply));
+			Ok(().into())
+		}
+
+		/// Burn the entire supply of `asset_id` held by the caller and receive its token
+		#[pallet::weight(
+			<T as Config>::WeightInfo::redeem()
+				.saturating_add(<T as base_nft::Config>::OnTokenChange::on_transfer_weight())
+		)]
+		#[transactional]
+		pub fn redeem(origin: OriginFor<T>, asset_id: T::AssetId) -> DispatchResultWithPostInfo {
+			let holder = ensure_signed(origin)?;
+
+			let fraction = Fractions::<T>::get(asset_id).ok_or(Error::<T>::FractionNotFound)?;
+			ensure!(
+				pallet_assets::Module::<T>::balance(asset_id, holder.clone()) == fraction.supply,
+				Error::<T>::NotWholeSupplyHolder
+			);
+
+			let account = Self::account_id();
+			pallet_assets::Call::<T>::burn(
+				asset_id,
+				T::Lookup::unlookup(holder.clone()),
+				fraction.supply,
+			)
+			.dispatch_bypass_filter(RawOrigin::Signed(account.clone()).into())?;
+			pallet_assets::Call::<T>::force_destroy(asset_id, T::MaxZombies::get())
+				.dispatch_bypass_filter(RawOrigin::Root.into())?;
+
+			let token = fraction.token;
+			BaseNft::<T>::unlock(token);
+			BaseNft::<T>::transfer(&account, &holder, token, T::SharesPerToken::get())?;
+
+			Fractions::<T>::remove(asset_id);
+			AssetOf::<T>::remove(token.0, token.1);
+
+			Self::deposit_event(Event::Redeemed(holder, token, asset_id));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Account holding fractionalized tokens
+	pub fn account_id() -> T::AccountId {
+		T::ModuleId::get().into_account()
+	}
+}
+
+impl<T: Config> Filter<(T::ClassId, T::TokenId)> for Pallet<T> {
+	/// Whether `token` is not fractionalized
+	fn filter(token: &(T::ClassId, T::TokenId)) -> bool {
+		!AssetOf::<T>::contains_key(token.0, token.1)
+	}
+}
@@ -0,0 +1,135 @@
+#![cfg(test)]
+
+use crate::mock::{Event, *};
+use crate::{Error, Fraction};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{Filter, UnfilteredDispatchable},
+};
+
+/// Token minted to `owner` in a new class
+fn mint_token(owner: &AccountId) -> (u32, u32) {
+	let class_id = BaseNft::create_class(owner, vec![1], ()).unwrap();
+	let token_id = BaseNft::mint(owner, class_id, vec![1], ()).unwrap();
+	(class_id, token_id)
+}
+
+#[test]
+fn fractionalize_should_work() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+
+		assert_ok!(Fractions::fractionalize(
+			Origin::signed(ALICE),
+			token,
+			7,
+			1_000
+		));
+		assert_eq!(
+			last_event(),
+			Event::pallet_fractions(crate::Event::Fractionalized(ALICE, token, 7, 1_000))
+		);
+		assert_eq!(Assets::balance(7, ALICE), 1_000);
+		assert_eq!(Assets::total_supply(7), 1_000);
+		assert_eq!(BaseNft::shares_of(&ALICE, token), 0);
+		assert_eq!(BaseNft::shares_of(&Fractions::account_id(), token), 100);
+		assert!(BaseNft::is_locked(token));
+		assert_eq!(
+			Fractions::fractions(7),
+			Some(Fraction {
+				token,
+				supply: 1_000
+			})
+		);
+		assert_eq!(Fractions::asset_of(token.0, token.1), Some(7));
+		assert!(!<Fractions as Filter<_>>::filter(&token));
+
+		// the custody account cannot be minted more of the asset by anyone else
+		assert!(pallet_assets::Call::<Runtime>::mint(7, ALICE, 1)
+			.dispatch_bypass_filter(Origin::signed(ALICE))
+			.is_err());
+	});
+}
+
+#[test]
+fn fractionalize_should_fail() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+		assert_noop!(
+			Fractions::fractionalize(Origin::signed(ALICE), token, 7, 0),
+			Error::<Runtime>::ZeroSupply
+		);
+		assert_noop!(
+			Fractions::fractionalize(Origin::signed(BOB), token, 7, 1_000),
+			Error::<Runtime>::NotWholeTokenOwner
+		);
+
+		assert_ok!(BaseNft::transfer(&ALICE, &BOB, token, 1));
+		assert_noop!(
+			Fractions::fractionalize(Origin::signed(ALICE), token, 7, 1_000),
+			Error::<Runtime>::NotWholeTokenOwner
+		);
+		assert_ok!(BaseNft::transfer(&BOB, &ALICE, token, 1));
+
+		assert_ok!(pallet_assets::Call::<Runtime>::force_create(7, BOB, 10, 1)
+			.dispatch_bypass_filter(Origin::root()));
+		assert_noop!(
+			Fractions::fractionalize(Origin::signed(ALICE), token, 7, 1_000),
+			pallet_assets::Error::<Runtime>::InUse
+		);
+		assert_eq!(BaseNft::shares_of(&ALICE, token), 100);
+		assert!(!BaseNft::is_locked(token));
+	});
+}
+
+#[test]
+fn redeem_should_work() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+		assert_ok!(Fractions::fractionalize(
+			Origin::signed(ALICE),
+			token,
+			7,
+			1_000
+		));
+		assert_ok!(pallet_assets::Call::<Runtime>::transfer(7, BOB, 400)
+			.dispatch_bypass_filter(Origin::signed(ALICE)));
+
+		assert_noop!(
+			Fractions::redeem(Origin::signed(ALICE), 7),
+			Error::<Runtime>::NotWholeSupplyHolder
+		);
+		assert_noop!(
+			Fractions::redeem(Origin::signed(BOB), 7),
+			Error::<Runtime>::NotWholeSupplyHolder
+		);
+
+		assert_ok!(pallet_assets::Call::<Runtime>::transfer(7, BOB, 600)
+			.dispatch_bypass_filter(Origin::signed(ALICE)));
+		assert_ok!(Fractions::redeem(Origin::signed(BOB), 7));
+		assert_eq!(
+			last_event(),
+			Event::pallet_fractions(crate::Event::Redeemed(BOB, token, 7))
+		);
+		assert_eq!(BaseNft::shares_of(&BOB, token), 100);
+		assert_eq!(BaseNft::shares_of(&Fractions::account_id(), token), 0);
+		assert!(!BaseNft::is_locked(token));
+		assert_eq!(Assets::total_supply(7), 0);
+		assert_eq!(Fractions::fractions(7), None);
+		assert_eq!(Fractions::asset_of(token.0, token.1), None);
+		assert!(<Fractions as Filter<_>>::filter(&token));
+
+		// the asset id is free again
+		assert_ok!(Fractions::fractionalize(Origin::signed(BOB), token, 7, 10));
+	});
+}
+
+#[test]
+fn redeem_should_fail() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Fractions::redeem(Origin::signed(ALICE), 7),
+			Error::<Runtime>::FractionNotFound
+		);
+	});
+}
@@ -0,0 +1,301 @@
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_system::RawOrigin;
+use sp_runtime::{traits::Bounded, Perbill};
+use sp_std::{boxed::Box, vec, vec::Vec};
+
+fn funded_account<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+	let who: T::AccountId = account(name, index, 0);
+	<T as base_nft::Config>::Currency::make_free_balance_be(
+		&who,
+		BalanceOf::<T>::max_value() / 2_u32.into(),
+	);
+	who
+}
+
+/// Token minted to a funded seller
+fn mint_token<T: Config>(seller: &T::AccountId) -> (T::ClassId, T::TokenId) {
+	let class_id = BaseNft::<T>::create_class(seller, vec![1_u8], Default::default()).unwrap();
+	let token_id = BaseNft::<T>::mint(seller, class_id, vec![1_u8], Default::default()).unwrap();
+	(class_id, token_id)
+}
+
+/// Class royalty paid to `r` funded beneficiaries sharing `MaxRoyalty`
+fn set_royalty<T: Config>(owner: &T::AccountId, class_id: T::ClassId, r: u32) {
+	let rate = Perbill::from_parts(
+		T::MaxRoyalty::get().deconstruct() / T::MaxRoyaltyBeneficiaries::get().max(1),
+	);
+	let royalty = (0..r)
+		.map(|i| (funded_account::<T>("beneficiary", i), rate))
+		.collect();
+	BaseNft::<T>::set_class_royalty(owner, class_id, royalty).unwrap();
+}
+
+benchmarks! {
+	list {
+		let seller = funded_account::<T>("seller", 0);
+		let token = mint_token::<T>(&seller);
+		let shares = T::SharesPerToken::get();
+	}: _(RawOrigin::Signed(seller), token, shares, 100_u32.into())
+
+	cancel_listing {
+		let seller = funded_account::<T>("seller", 0);
+		let token = mint_token::<T>(&seller);
+		Pallet::<T>::list(
+			RawOrigin::Signed(seller.clone()).into(),
+			token,
+			T::SharesPerToken::get(),
+			100_u32.into(),
+		)?;
+	}: _(RawOrigin::Signed(seller), token)
+
+	buy {
+		let r in 0 .. T::MaxRoyaltyBeneficiaries::get();
+
+		let seller = funded_account::<T>("seller", 0);
+		let token = mint_token::<T>(&seller);
+		set_royalty::<T>(&seller, token.0, r);
+		Pallet::<T>::list(
+			RawOrigin::Signed(seller.clone()).into(),
+			token,
+			T::SharesPerToken::get(),
+			1_000_000_u32.into(),
+		)?;
+
+		let buyer: T::AccountId = whitelisted_caller();
+		<T as base_nft::Config>::Currency::make_free_balance_be(
+			&buyer,
+			BalanceOf::<T>::max_value() / 2_u32.into(),
+		);
+	}: _(RawOrigin::Signed(buyer), token, seller, 1_000_000_u32.into())
+
+	make_offer {
+		let seller = funded_account::<T>("seller", 0);
+		let token = mint_token::<T>(&seller);
+		let buyer = funded_account::<T>("buyer", 0);
+		Pallet::<T>::make_offer(
+			RawOrigin::Signed(buyer.clone()).into(),
+			token,
+			T::SharesPerToken::get(),
+			100_u32.into(),
+			100_u32.into(),
+			false,
+		)?;
+	}: _(RawOrigin::Signed(buyer), token, T::SharesPerToken::get(), 200_u32.into(), 100_u32.into(), false)
+
+	cancel_offer {
+		let seller = funded_account::<T>("seller", 0);
+		let token = mint_token::<T>(&seller);
+		let buyer = funded_account::<T>("buyer", 0);
+		Pallet::<T>::make_offer(
+			RawOrigin::Signed(buyer.clone()).into(),
+			token,
+			T::SharesPerToken::get(),
+			100_u32.into(),
+			100_u32.into(),
+			false,
+		)?;
+	}: _(RawOrigin::Signed(buyer.clone()), token, buyer.clone())
+
+	accept_offer {
+		let r in 0 .. T::MaxRoyaltyBeneficiaries::get();
+
+		let seller = funded_account::<T>("seller", 0);
+		let token = mint_token::<T>(&seller);
+		set_royalty::<T>(&seller, token.0, r);
+		let buyer = funded_account::<T>("buyer", 0);
+		Pallet::<T>::make_offer(
+			RawOrigin::Signed(buyer.clone()).into(),
+			token,
+			T::SharesPerToken::get(),
+			1_000_000_u32.into(),
+			100_u32.into(),
+			false,
+		)?;
+	}: _(RawOrigin::Signed(seller), token, buyer, T::SharesPerToken::get())
+
+	// the last of `o` holders of a single share but the first approves the offer
+	approve_offer {
+		let o in 2 .. T::MaxOwnersPerToken::get();
+		let r in 0 .. T::MaxRoyaltyBeneficiaries::get();
+
+		let seller = funded_account::<T>("seller", 0);
+		let token = mint_token::<T>(&seller);
+		set_royalty::<T>(&seller, token.0, r);
+		for i in 1..o {
+			BaseNft::<T>::transfer(&seller, &funded_account::<T>("holder", i), token, 1_u32.into())?;
+		}
+		let buyer = funded_account::<T>("buyer", 0);
+		Pallet::<T>::make_offer(
+			RawOrigin::Signed(buyer.clone()).into(),
+			token,
+			T::SharesPerToken::get(),
+			1_000_000_u32.into(),
+			100_u32.into(),
+			false,
+		)?;
+		for i in 1..o - 1 {
+			Pallet::<T>::approve_offer(
+				RawOrigin::Signed(funded_account::<T>("holder", i)).into(),
+				token,
+				buyer.clone(),
+			)?;
+		}
+	}: _(RawOrigin::Signed(seller), token, buyer.clone())
+	verify {
+		assert_eq!(BaseNft::<T>::shares_of(&buyer, token), T::SharesPerToken::get());
+	}
+
+	make_floor_offer {
+		let seller = funded_account::<T>("seller", 0);
+		let token = mint_token::<T>(&seller);
+		let buyer = funded_account::<T>("buyer", 0);
+		Pallet::<T>::make_floor_offer(
+			RawOrigin::Signed(buyer.clone()).into(),
+			token.0,
+			100_u32.into(),
+			100_u32.into(),
+		)?;
+	}: _(RawOrigin::Signed(buyer), token.0, 200_u32.into(), 100_u32.into())
+
+	cancel_floor_offer {
+		let seller = funded_account::<T>("seller", 0);
+		let token = mint_token::<T>(&seller);
+		let buyer = funded_account::<T>("buyer", 0);
+		Pallet::<T>::make_floor_offer(
+			RawOrigin::Signed(buyer.clone()).into(),
+			token.0,
+			100_u32.into(),
+			100_u32.into(),
+		)?;
+	}: _(RawOrigin::Signed(buyer.clone()), token.0, buyer.clone())
+
+	accept_floor_offer {
+		let r in 0 .. T::MaxRoyaltyBeneficiaries::get();
+
+		let seller = funded_account::<T>("seller", 0);
+		let token = mint_token::<T>(&seller);
+		set_royalty::<T>(&seller, token.0, r);
+		let buyer = funded_account::<T>("buyer", 0);
+		Pallet::<T>::make_floor_offer(
+			RawOrigin::Signed(buyer.clone()).into(),
+			token.0,
+			1_000_000_u32.into(),
+			100_u32.into(),
+		)?;
+	}: _(RawOrigin::Signed(seller), token, buyer)
+
+	place_order {
+		let n in 0 .. T::MaxOrdersPerToken::get() - 1;
+		let r in 0 .. T::MaxRoyaltyBeneficiaries::get();
+
+		let seller = funded_account::<T>("seller", 0);
+		let token = mint_token::<T>(&seller);
+		set_royalty::<T>(&seller, token.0, r);
+		for _ in 0 .. n {
+			Pallet::<T>::place_order(
+				RawOrigin::Signed(seller.clone()).into(),
+				token,
+				OrderSide::Ask,
+				1_u32.into(),
+				1_000_000_u32.into(),
+			)?;
+		}
+
+		let buyer: T::AccountId = whitelisted_caller();
+		<T as base_nft::Config>::Currency::make_free_balance_be(
+			&buyer,
+			BalanceOf::<T>::max_value() / 2_u32.into(),
+		);
+		// fills every ask and rests on the book for the last share
+	}: _(RawOrigin::Signed(buyer), token, OrderSide::Bid, (n + 1).into(), 1_000_000_u32.into())
+
+	cancel_order {
+		let seller = funded_account::<T>("seller", 0);
+		let token = mint_token::<T>(&seller);
+		let buyer = funded_account::<T>("buyer", 0);
+		Pallet::<T>::place_order(
+			RawOrigin::Signed(buyer.clone()).into(),
+			token,
+			OrderSide::Bid,
+			1_u32.into(),
+			100_u32.into(),
+		)?;
+	}: _(RawOrigin::Signed(buyer), token, 0)
+
+	// the seller no longer holds the shares of its listing and its `n` asks
+	on_transfer {
+		let n in 0 .. T::MaxOrdersPerToken::get();
+
+		let owner = funded_account::<T>("owner", 0);
+		let token = mint_token::<T>(&owner);
+		let seller = funded_account::<T>("seller", 0);
+		let buyer = funded_account::<T>("buyer", 0);
+		Listings::<T>::insert(token, &seller, Listing {
+			shares: T::SharesPerToken::get(),
+			price: 100_u32.into(),
+			royalty: Vec::new(),
+		});
+		for order_id in 0 .. n {
+			Orders::<T>::insert(token, order_id as OrderId, Order {
+				owner: seller.clone(),
+				side: OrderSide::Ask,
+				shares: 1_u32.into(),
+				price: 100_u32.into(),
+				royalty: Vec::new(),
+			});
+		}
+		OrderCount::<T>::insert(token, n);
+	}: {
+		<Pallet<T> as OnTokenChange<_, _, _, _>>::on_transfer(&seller, &buyer, token, 1_u32.into());
+	}
+	verify {
+		assert_eq!(OrderCount::<T>::get(token), 0);
+	}
+
+	// `l` listings, `o` offers and `n` bids are cancelled
+	on_burn {
+		let l in 0 .. T::MaxOwnersPerToken::get();
+		let o in 0 .. T::MaxOffersPerToken::get();
+		let n in 0 .. T::MaxOrdersPerToken::get();
+
+		let owner = funded_account::<T>("owner", 0);
+		let token = mint_token::<T>(&owner);
+		for i in 0 .. l {
+			Listings::<T>::insert(token, funded_account::<T>("seller", i), Listing {
+				shares: 1_u32.into(),
+				price: 100_u32.into(),
+				royalty: Vec::new(),
+			});
+		}
+		for i in 0 .. o {
+			Pallet::<T>::make_offer(
+				RawOrigin::Signed(funded_account::<T>("offerer", i)).into(),
+				token,
+				T::SharesPerToken::get(),
+				100_u32.into(),
+				100_u32.into(),
+				false,
+			)?;
+		}
+		for i in 0 .. n {
+			Pallet::<T>::place_order(
+				RawOrigin::Signed(funded_account::<T>("bidder", i)).into(),
+				token,
+				OrderSide::Bid,
+				1_u32.into(),
+				100_u32.into(),
+			)?;
+		}
+	}: {
+		<Pallet<T> as OnTokenChange<_, _, _, _>>::on_burn(token, &[]);
+	}
+	verify {
+		assert_eq!(OfferCount::<T>::get(token), 0);
+		assert_eq!(OrderCount::<T>::get(token), 0);
+	}
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Runtime);
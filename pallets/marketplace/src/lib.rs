@@ -0,0 +1,1075 @@
+//! # Marketplace
+//! Fixed-price sales of `base_nft` tokens.
+//!
+//! An owner lists some or all of their shares of a token for a total price in the currency of
+//! `base_nft::Config::Currency`. A buyer pays the price and receives the shares through
+//! `base_nft::Pallet::transfer` in the same transaction. Royalties of the token when it was
+//! listed are paid out of the price, the seller receives the rest.
+//!
+//! Buyers also reserve offers on any token, listed or not, or floor offers on any token of a
+//! class. A holder fills an offer with all the offered shares. When the buyer opts into partial
+//! fills, holders fill it with some of their shares for the pro-rata part of its price, so the
+//! co-owners of a token accept it together. Holders also approve an offer for the whole token,
+//! once the approving holders own more than half of the shares every holder sells to the buyer
+//! for the pro-rata part of the price. A floor offer buys a whole token. Offers expire at a
+//! block, after which anyone can cancel them to refund the buyer. Royalties of the token when
+//! an offer was made are paid out of its price.
+//!
+//! Shares of a token also trade on a per-token order book of limit bids and asks priced per
+//! share. A new order fills against the best crossing orders of other accounts at their price,
+//! oldest first, and rests on the book for the shares left. Bids reserve their value, asks are
+//! backed by the shares of their owner. Fills settle through `base_nft::Pallet::transfer` and
+//! pay the royalties of the token when the resting order was placed. `OrderPlaced`,
+//! `OrderFilled` and `OrderCancelled` events are enough to rebuild the book off chain.
+//!
+//! `Config::TradeFilter` keeps tokens out of listings, offers and orders, e.g. tokens of classes
+//! requiring strict metadata until their metadata is verified. Trades already open on such a
+//! token can only be cancelled.
+//!
+//! The pallet implements `base_nft::OnTokenChange`, a listing is cancelled once its seller
+//! transfers away the shares backing it or the token is burned. Offers and orders on a burned
+//! token are cancelled, so are the asks of an owner left without the shares to back them.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use base_nft::{BalanceOf, Module as BaseNft, OnTokenChange, RoyaltyOf};
+use codec::{Decode, Encode};
+use frame_support::{
+	dispatch::DispatchResultWithPostInfo,
+	pallet_prelude::*,
+	traits::{BalanceStatus, Currency, ExistenceRequirement, Filter, ReservableCurrency},
+	transactional,
+};
+use frame_system::pallet_prelude::*;
+use sp_runtime::{
+	helpers_128bit::multiply_by_rational,
+	traits::{SaturatedConversion, Saturating, Zero},
+	DispatchError, DispatchResult, Perbill, RuntimeDebug,
+};
+use sp_std::vec::Vec;
+
+pub use pallet::*;
+
+mod mock;
+mod tests;
+
+mod benchmarking;
+pub mod weights;
+pub use weights::WeightInfo;
+
+/// Shares of a token offered by their owner
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
+pub struct Listing<AccountId, Shares, Balance> {
+	/// Shares sold together
+	pub shares: Shares,
+	/// Price of all the listed shares
+	pub price: Balance,
+	/// Royalty of the token when it was listed, paid on the sale
+	pub royalty: Vec<(AccountId, Perbill)>,
+}
+
+pub type ListingOf<T> =
+	Listing<<T as frame_system::Config>::AccountId, <T as base_nft::Config>::Shares, BalanceOf<T>>;
+
+/// Price reserved by a buyer for shares of a token
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
+pub struct Offer<AccountId, Shares, Balance, BlockNumber> {
+	/// Shares still wanted
+	pub shares: Shares,
+	/// Price of the shares still wanted
+	pub price: Balance,
+	/// Block the offer can no longer be accepted at
+	pub expiry: BlockNumber,
+	/// Whether holders may fill part of the shares, otherwise a single holder fills them all
+	pub partial: bool,
+	/// Royalty of the token when the offer was made, paid on every fill
+	pub royalty: Vec<(AccountId, Perbill)>,
+	/// Sorted holders who approved to sell the whole token to the offer
+	pub approvals: Vec<AccountId>,
+}
+
+pub type OfferOf<T> = Offer<
+	<T as frame_system::Config>::AccountId,
+	<T as base_nft::Config>::Shares,
+	BalanceOf<T>,
+	<T as frame_system::Config>::BlockNumber,
+>;
+
+/// Price reserved by a buyer for any whole token of a class
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
+pub struct FloorOffer<Balance, BlockNumber> {
+	/// Price of a token
+	pub price: Balance,
+	/// Block the offer can no longer be accepted at
+	pub expiry: BlockNumber,
+}
+
+pub type FloorOfferOf<T> = FloorOffer<BalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
+
+/// Identifier of an order, unique across tokens and increasing with time
+pub type OrderId = u64;
+
+/// Side of the order book an order rests on
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug)]
+pub enum OrderSide {
+	/// Buy shares, the order value is reserved
+	Bid,
+	/// Sell shares held by the order owner
+	Ask,
+}
+
+/// Limit order for shares of a token
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
+pub struct Order<AccountId, Shares, Balance> {
+	pub owner: AccountId,
+	pub side: OrderSide,
+	/// Shares left to fill
+	pub shares: Shares,
+	/// Price of a single share
+	pub price: Balance,
+	/// Royalty of the token when the order was placed, paid on the fills against it
+	pub royalty: Vec<(AccountId, Perbill)>,
+}
+
+pub type OrderOf<T> =
+	Order<<T as frame_system::Config>::AccountId, <T as base_nft::Config>::Shares, BalanceOf<T>>;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + base_nft::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		type WeightInfo: WeightInfo;
+		/// Maximum number of offers on a token
+		#[pallet::constant]
+		type MaxOffersPerToken: Get<u32>;
+		/// Maximum number of orders on the book of a token
+		#[pallet::constant]
+		type MaxOrdersPerToken: Get<u32>;
+		/// Tokens that may be listed, offered on and ordered
+		type TradeFilter: Filter<(Self::ClassId, Self::TokenId)>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	/// Listings of a token by seller.
+	#[pallet::storage]
+	#[pallet::getter(fn listings)]
+	pub type Listings<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		(T::ClassId, T::TokenId),
+		Twox64Concat,
+		T::AccountId,
+		ListingOf<T>,
+	>;
+
+	/// Offers on a token by buyer.
+	#[pallet::storage]
+	#[pallet::getter(fn offers)]
+	pub type Offers<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		(T::ClassId, T::TokenId),
+		Twox64Concat,
+		T::AccountId,
+		OfferOf<T>,
+	>;
+
+	/// Number of offers on a token.
+	#[pallet::storage]
+	#[pallet::getter(fn offer_count)]
+	pub type OfferCount<T: Config> =
+		StorageMap<_, Twox64Concat, (T::ClassId, T::TokenId), u32, ValueQuery>;
+
+	/// Floor offers on a class by buyer.
+	#[pallet::storage]
+	#[pallet::getter(fn floor_offers)]
+	pub type FloorOffers<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::ClassId, Twox64Concat, T::AccountId, FloorOfferOf<T>>;
+
+	/// Identifier of the next order.
+	#[pallet::storage]
+	#[pallet::getter(fn next_order_id)]
+	pub type NextOrderId<T: Config> = StorageValue<_, OrderId, ValueQuery>;
+
+	/// Order book of a token by order id.
+	#[pallet::storage]
+	#[pallet::getter(fn orders)]
+	pub type Orders<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		(T::ClassId, T::TokenId),
+		Twox64Concat,
+		OrderId,
+		OrderOf<T>,
+	>;
+
+	/// Number of orders on the book of a token.
+	#[pallet::storage]
+	#[pallet::getter(fn order_count)]
+	pub type OrderCount<T: Config> =
+		StorageMap<_, Twox64Concat, (T::ClassId, T::TokenId), u32, ValueQuery>;
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Listing must offer at least one share
+		ZeroShares,
+		/// Seller does not hold the listed shares
+		InsufficientShares,
+		/// Seller has no listing for the token
+		ListingNotFound,
+		/// Listing price is above the price the buyer accepts
+		PriceTooHigh,
+		/// Sellers cannot buy their own listing
+		BuyerIsSeller,
+		/// Offer is for more shares than a token has
+		TooManyShares,
+		/// Expiry block has already passed
+		InvalidExpiry,
+		/// Token already has `MaxOffersPerToken` offers
+		TooManyOffers,
+		/// Buyer has no offer for the token or class
+		OfferNotFound,
+		/// Offer expired
+		OfferExpired,
+		/// Only the buyer cancels an offer before it expires
+		OfferNotExpired,
+		/// Offer is for fewer shares than accepted
+		OfferTooSmall,
+		/// Floor offers are accepted by the holder of every share of a token
+		NotWholeTokenOwner,
+		/// Orders must have a non-zero price
+		ZeroPrice,
+		/// Order book of the token already has `MaxOrdersPerToken` orders
+		TooManyOrders,
+		/// Order not found on the book of the token
+		OrderNotFound,
+		/// Only the owner cancels an order
+		NotOrderOwner,
+		/// No more order ids available
+		NoAvailableOrderId,
+		/// Offer is filled by a single holder with all its shares
+		PartialFillNotAllowed,
+		/// Token is not allowed to trade by `TradeFilter`
+		TokenInactive,
+		/// Only offers for every share of a token are approved by the holders
+		NotWholeTokenOffer,
+		/// Holder already approved the offer
+		OfferAlreadyApproved,
+	}
+
+	#[pallet::event]
+	#[pallet::metadata(
+		T::AccountId = "AccountId",
+		BalanceOf<T> = "Balance",
+		T::BlockNumber = "BlockNumber"
+	)]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Shares of a token were listed. \[seller, token, shares, price\]
+		Listed(
+			T::AccountId,
+			(T::ClassId, T::TokenId),
+			T::Shares,
+			BalanceOf<T>,
+		),
+		/// Listing was cancelled by its seller or by an ownership change. \[seller, token\]
+		ListingCancelled(T::AccountId, (T::ClassId, T::TokenId)),
+		/// Listed shares were bought. \[seller, buyer, token, shares, price\]
+		Sold(
+			T::AccountId,
+			T::AccountId,
+			(T::ClassId, T::TokenId),
+			T::Shares,
+			BalanceOf<T>,
+		),
+		/// Buyer made an offer. \[buyer, token, shares, price, expiry, partial\]
+		OfferMade(
+			T::AccountId,
+			(T::ClassId, T::TokenId),
+			T::Shares,
+			BalanceOf<T>,
+			T::BlockNumber,
+			bool,
+		),
+		/// Offer was cancelled and its price refunded. \[buyer, token\]
+		OfferCancelled(T::AccountId, (T::ClassId, T::TokenId)),
+		/// Holder sold shares to an offer. \[seller, buyer, token, shares, price\]
+		OfferAccepted(
+			T::AccountId,
+			T::AccountId,
+			(T::ClassId, T::TokenId),
+			T::Shares,
+			BalanceOf<T>,
+		),
+		/// Holder approved to sell the whole token to an offer. \[holder, buyer, token\]
+		OfferApproved(T::AccountId, T::AccountId, (T::ClassId, T::TokenId)),
+		/// Buyer made a floor offer. \[buyer, class_id, price, expiry\]
+		FloorOfferMade(T::AccountId, T::ClassId, BalanceOf<T>, T::BlockNumber),
+		/// Floor offer was cancelled and its price refunded. \[buyer, class_id\]
+		FloorOfferCancelled(T::AccountId, T::ClassId),
+		/// Holder sold a token to a floor offer. \[seller, buyer, token, price\]
+		FloorOfferAccepted(
+			T::AccountId,
+			T::AccountId,
+			(T::ClassId, T::TokenId),
+			BalanceOf<T>,
+		),
+		/// Order was placed, fills follow before it rests on the book.
+		/// \[order_id, owner, token, side, shares, price\]
+		OrderPlaced(
+			OrderId,
+			T::AccountId,
+			(T::ClassId, T::TokenId),
+			OrderSide,
+			T::Shares,
+			BalanceOf<T>,
+		),
+		/// Resting order was filled by a new order at the resting price.
+		/// \[token, maker_order_id, taker_order_id, buyer, seller, shares, price\]
+		OrderFilled(
+			(T::ClassId, T::TokenId),
+			OrderId,
+			OrderId,
+			T::AccountId,
+			T::AccountId,
+			T::Shares,
+			BalanceOf<T>,
+		),
+		/// Order was removed from the book with shares left. \[token, order_id, owner\]
+		OrderCancelled((T::ClassId, T::TokenId), OrderId, T::AccountId),
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// List `shares` of `token` held by the caller for `price`, replacing a previous listing
+		#[pallet::weight(T::WeightInfo::list())]
+		pub fn list(
+			origin: OriginFor<T>,
+			token: (T::ClassId, T::TokenId),
+			shares: T::Shares,
+			price: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let seller = ensure_signed(origin)?;
+
+			ensure!(!shares.is_zero(), Error::<T>::ZeroShares);
+			ensure!(
+				BaseNft::<T>::shares_of(&seller, token) >= shares,
+				Error::<T>::InsufficientShares
+			);
+			ensure!(T::TradeFilter::filter(&token), Error::<T>::TokenInactive);
+
+			Listings::<T>::insert(
+				token,
+				&seller,
+				Listing {
+					shares,
+					price,
+					royalty: BaseNft::<T>::royalty_of(token),
+				},
+			);
+
+			Self::deposit_event(Event::Listed(seller, token, shares, price));
+			Ok(().into())
+		}
+
+		#[pallet::weight(T::WeightInfo::cancel_listing())]
+		pub fn cancel_listing(
+			origin: OriginFor<T>,
+			token: (T::ClassId, T::TokenId),
+		) -> DispatchResultWithPostInfo {
+			let seller = ensure_signed(origin)?;
+
+			ensure!(
+				Listings::<T>::contains_key(token, &seller),
+				Error::<T>::ListingNotFound
+			);
+			Listings::<T>::remove(token, &seller);
+
+			Self::deposit_event(Event::ListingCancelled(seller, token));
+			Ok(().into())
+		}
+
+		/// Buy the shares of `token` listed by `seller`, paying at most `max_price`
+		#[pallet::weight(
+			T::WeightInfo::buy(T::MaxRoyaltyBeneficiaries::get())
+				.saturating_add(T::OnTokenChange::on_transfer_weight())
+		)]
+		#[transactional]
+		pub fn buy(
+			origin: OriginFor<T>,
+			token: (T::ClassId, T::TokenId),
+			seller: T::AccountId,
+			max_price: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let buyer = ensure_signed(origin)?;
+
+			ensure!(buyer != seller, Error::<T>::BuyerIsSeller);
+			ensure!(T::TradeFilter::filter(&token), Error::<T>::TokenInactive);
+			let listing = Listings::<T>::take(token, &seller).ok_or(Error::<T>::ListingNotFound)?;
+			ensure!(listing.price <= max_price, Error::<T>::PriceTooHigh);
+
+			let royalties =
+				BaseNft::<T>::pay_royalties(&buyer, token, &listing.royalty, listing.price)?;
+			<T as base_nft::Config>::Currency::transfer(
+				&buyer,
+				&seller,
+				listing.price.saturating_sub(royalties),
+				ExistenceRequirement::KeepAlive,
+			)?;
+			BaseNft::<T>::transfer(&seller, &buyer, token, listing.shares)?;
+
+			Self::deposit_event(Event::Sold(
+				seller,
+				buyer,
+				token,
+				listing.shares,
+				listing.price,
+			));
+			Ok(().into())
+		}
+
+		/// Offer `price` for `shares` of `token` until `expiry`, replacing a previous offer
+		///
+		/// The price is reserved until the offer is filled or cancelled. A `partial` offer is
+		/// filled by any number of holders, which may leave the buyer with part of the shares.
+		#[pallet::weight(T::WeightInfo::make_offer())]
+		#[transactional]
+		pub fn make_offer(
+			origin: OriginFor<T>,
+			token: (T::ClassId, T::TokenId),
+			shares: T::Shares,
+			price: BalanceOf<T>,
+			expiry: T::BlockNumber,
+			partial: bool,
+		) -> DispatchResultWithPostInfo {
+			let buyer = ensure_signed(origin)?;
+
+			ensure!(!shares.is_zero(), Error::<T>::ZeroShares);
+			ensure!(
+				shares <= T::SharesPerToken::get(),
+				Error::<T>::TooManyShares
+			);
+			ensure!(
+				base_nft::Tokens::<T>::contains_key(token.0, token.1),
+				base_nft::Error::<T>::TokenNotFound
+			);
+			ensure!(T::TradeFilter::filter(&token), Error::<T>::TokenInactive);
+			ensure!(
+				expiry > frame_system::Pallet::<T>::block_number(),
+				Error::<T>::InvalidExpiry
+			);
+
+			match Offers::<T>::get(token, &buyer) {
+				Some(previous) => {
+					<T as base_nft::Config>::Currency::unreserve(&buyer, previous.price);
+				}
+				None => {
+					let count = OfferCount::<T>::get(token);
+					ensure!(
+						count < T::MaxOffersPerToken::get(),
+						Error::<T>::TooManyOffers
+					);
+					OfferCount::<T>::insert(token, count + 1);
+				}
+			}
+			<T as base_nft::Config>::Currency::reserve(&buyer, price)?;
+			Offers::<T>::insert(
+				token,
+				&buyer,
+				Offer {
+					shares,
+					price,
+					expiry,
+					partial,
+					royalty: BaseNft::<T>::royalty_of(token),
+					approvals: Vec::new(),
+				},
+			);
+
+			Self::deposit_event(Event::OfferMade(
+				buyer, token, shares, price, expiry, partial,
+			));
+			Ok(().into())
+		}
+
+		/// Cancel the offer of `buyer` on `token`, anyone may cancel an expired offer
+		#[pallet::weight(T::WeightInfo::cancel_offer())]
+		pub fn cancel_offer(
+			origin: OriginFor<T>,
+			token: (T::ClassId, T::TokenId),
+			buyer: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let offer = Offers::<T>::get(token, &buyer).ok_or(Error::<T>::OfferNotFound)?;
+			ensure!(
+				who == buyer || Self::is_expired(offer.expiry),
+				Error::<T>::OfferNotExpired
+			);
+
+			Self::remove_offer(token, &buyer, &offer);
+			Ok(().into())
+		}
+
+		/// Sell `shares` of `token` held by the caller to the offer of `buyer`
+		///
+		/// The caller is paid the pro-rata part of the offered price. A partial offer stays open
+		/// for the remaining shares, any other offer is filled by a single holder at once.
+		#[pallet::weight(
+			T::WeightInfo::accept_offer(T::MaxRoyaltyBeneficiaries::get())
+				.saturating_add(T::OnTokenChange::on_transfer_weight())
+		)]
+		#[transactional]
+		pub fn accept_offer(
+			origin: OriginFor<T>,
+			token: (T::ClassId, T::TokenId),
+			buyer: T::AccountId,
+			shares: T::Shares,
+		) -> DispatchResultWithPostInfo {
+			let seller = ensure_signed(origin)?;
+
+			ensure!(seller != buyer, Error::<T>::BuyerIsSeller);
+			ensure!(!shares.is_zero(), Error::<T>::ZeroShares);
+			ensure!(
+				BaseNft::<T>::shares_of(&seller, token) >= shares,
+				Error::<T>::InsufficientShares
+			);
+			ensure!(T::TradeFilter::filter(&token), Error::<T>::TokenInactive);
+
+			let (price, royalty) = Offers::<T>::try_mutate_exists(
+				token,
+				&buyer,
+				|maybe_offer| -> Result<(BalanceOf<T>, RoyaltyOf<T>), DispatchError> {
+					let offer = maybe_offer.as_mut().ok_or(Error::<T>::OfferNotFound)?;
+					ensure!(!Self::is_expired(offer.expiry), Error::<T>::OfferExpired);
+					ensure!(shares <= offer.shares, Error::<T>::OfferTooSmall);
+					ensure!(
+						offer.partial || shares == offer.shares,
+						Error::<T>::PartialFillNotAllowed
+					);
+
+					let price = Self::pro_rata(offer.price, shares, offer.shares);
+					offer.shares -= shares;
+					offer.price = offer.price.saturating_sub(price);
+					let royalty = offer.royalty.clone();
+					if offer.shares.is_zero() {
+						*maybe_offer = None;
+						OfferCount::<T>::mutate(token, |count| *count = count.saturating_sub(1));
+					}
+					Ok((price, royalty))
+				},
+			)?;
+			Self::settle_offer(&buyer, &seller, token, shares, price, &royalty)?;
+
+			Self::deposit_event(Event::OfferAccepted(seller, buyer, token, shares, price));
+			Ok(().into())
+		}
+
+		/// Approve to sell the whole `token` to the offer of `buyer` for every share of it
+		///
+		/// Once the holders who approved the offer own more than half of the shares, every
+		/// holder sells their shares to the buyer for the pro-rata part of the offered price.
+		#[pallet::weight(
+			T::WeightInfo::approve_offer(
+				T::MaxOwnersPerToken::get(),
+				T::MaxRoyaltyBeneficiaries::get(),
+			)
+			.saturating_add(
+				T::OnTokenChange::on_transfer_weight()
+					.saturating_mul(T::MaxOwnersPerToken::get() as Weight)
+			)
+		)]
+		#[transactional]
+		pub fn approve_offer(
+			origin: OriginFor<T>,
+			token: (T::ClassId, T::TokenId),
+			buyer: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			let holder = ensure_signed(origin)?;
+
+			ensure!(holder != buyer, Error::<T>::BuyerIsSeller);
+			ensure!(
+				!BaseNft::<T>::shares_of(&holder, token).is_zero(),
+				Error::<T>::InsufficientShares
+			);
+			ensure!(T::TradeFilter::filter(&token), Error::<T>::TokenInactive);
+
+			let mut offer = Offers::<T>::get(token, &buyer).ok_or(Error::<T>::OfferNotFound)?;
+			ensure!(!Self::is_expired(offer.expiry), Error::<T>::OfferExpired);
+			ensure!(
+				offer.shares == T::SharesPerToken::get(),
+				Error::<T>::NotWholeTokenOffer
+			);
+			match offer.approvals.binary_search(&holder) {
+				Ok(_) => return Err(Error::<T>::OfferAlreadyApproved.into()),
+				Err(pos) => offer.approvals.insert(pos, holder.clone()),
+			}
+
+			Self::deposit_event(Event::OfferApproved(holder, buyer.clone(), token));
+
+			let approved = offer
+				.approvals
+				.iter()
+				.fold(Zero::zero(), |total: T::Shares, who| {
+					total.saturating_add(BaseNft::<T>::shares_of(who, token))
+				});
+			if approved.saturating_add(approved) > T::SharesPerToken::get() {
+				Self::sell_whole_token(token, &buyer, offer)?;
+			} else {
+				Offers::<T>::insert(token, &buyer, offer);
+			}
+			Ok(().into())
+		}
+
+		/// Offer `price` for any whole token of `class_id` until `expiry`, replacing a previous
+		/// floor offer
+		#[pallet::weight(T::WeightInfo::make_floor_offer())]
+		#[transactional]
+		pub fn make_floor_offer(
+			origin: OriginFor<T>,
+			class_id: T::ClassId,
+			price: BalanceOf<T>,
+			expiry: T::BlockNumber,
+		) -> DispatchResultWithPostInfo {
+			let buyer = ensure_signed(origin)?;
+
+			ensure!(
+				base_nft::Classes::<T>::contains_key(class_id),
+				base_nft::Error::<T>::ClassNotFound
+			);
+			ensure!(
+				expiry > frame_system::Pallet::<T>::block_number(),
+				Error::<T>::InvalidExpiry
+			);
+
+			if let Some(previous) = FloorOffers::<T>::get(class_id, &buyer) {
+				<T as base_nft::Config>::Currency::unreserve(&buyer, previous.price);
+			}
+			<T as base_nft::Config>::Currency::reserve(&buyer, price)?;
+			FloorOffers::<T>::insert(class_id, &buyer, FloorOffer { price, expiry });
+
+			Self::deposit_event(Event::FloorOfferMade(buyer, class_id, price, expiry));
+			Ok(().into())
+		}
+
+		/// Cancel the floor offer of `buyer` on `class_id`, anyone may cancel an expired offer
+		#[pallet::weight(T::WeightInfo::cancel_floor_offer())]
+		pub fn cancel_floor_offer(
+			origin: OriginFor<T>,
+			class_id: T::ClassId,
+			buyer: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let offer = FloorOffers::<T>::get(class_id, &buyer).ok_or(Error::<T>::OfferNotFound)?;
+			ensure!(
+				who == buyer || Self::is_expired(offer.expiry),
+				Error::<T>::OfferNotExpired
+			);
+
+			FloorOffers::<T>::remove(class_id, &buyer);
+			<T as base_nft::Config>::Currency::unreserve(&buyer, offer.price);
+
+			Self::deposit_event(Event::FloorOfferCancelled(buyer, class_id));
+			Ok(().into())
+		}
+
+		/// Sell `token`, held entirely by the caller, to the floor offer of `buyer` on its class
+		#[pallet::weight(
+			T::WeightInfo::accept_floor_offer(T::MaxRoyaltyBeneficiaries::get())
+				.saturating_add(T::OnTokenChange::on_transfer_weight())
+		)]
+		#[transactional]
+		pub fn accept_floor_offer(
+			origin: OriginFor<T>,
+			token: (T::ClassId, T::TokenId),
+			buyer: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			let seller = ensure_signed(origin)?;
+
+			ensure!(seller != buyer, Error::<T>::BuyerIsSeller);
+			let shares = T::SharesPerToken::get();
+			ensure!(
+				BaseNft::<T>::shares_of(&seller, token) == shares,
+				Error::<T>::NotWholeTokenOwner
+			);
+			ensure!(T::TradeFilter::filter(&token), Error::<T>::TokenInactive);
+			let offer = FloorOffers::<T>::take(token.0, &buyer).ok_or(Error::<T>::OfferNotFound)?;
+			ensure!(!Self::is_expired(offer.expiry), Error::<T>::OfferExpired);
+
+			// the token is only known once a floor offer is accepted
+			let royalty = BaseNft::<T>::royalty_of(token);
+			Self::settle_offer(&buyer, &seller, token, shares, offer.price, &royalty)?;
+
+			Self::deposit_event(Event::FloorOfferAccepted(seller, buyer, token, offer.price));
+			Ok(().into())
+		}
+
+		/// Place a limit order for `shares` of `token` at `price` per share
+		///
+		/// The order fills against crossing orders of other accounts, best price first, and the
+		/// shares left rest on the book. Asks are limited to the shares of the caller not already
+		/// asked for.
+		#[pallet::weight(
+			T::WeightInfo::place_order(
+				T::MaxOrdersPerToken::get(),
+				T::MaxRoyaltyBeneficiaries::get(),
+			)
+			.saturating_add(
+				T::OnTokenChange::on_transfer_weight()
+					.saturating_mul(T::MaxOrdersPerToken::get() as Weight)
+			)
+		)]
+		#[transactional]
+		pub fn place_order(
+			origin: OriginFor<T>,
+			token: (T::ClassId, T::TokenId),
+			side: OrderSide,
+			shares: T::Shares,
+			price: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			ensure!(!shares.is_zero(), Error::<T>::ZeroShares);
+			ensure!(
+				shares <= T::SharesPerToken::get(),
+				Error::<T>::TooManyShares
+			);
+			ensure!(!price.is_zero(), Error::<T>::ZeroPrice);
+			ensure!(
+				base_nft::Tokens::<T>::contains_key(token.0, token.1),
+				base_nft::Error::<T>::TokenNotFound
+			);
+			ensure!(T::TradeFilter::filter(&token), Error::<T>::TokenInactive);
+			if side == OrderSide::Ask {
+				ensure!(
+					BaseNft::<T>::shares_of(&who, token)
+						>= Self::asked_shares(&who, token).saturating_add(shares),
+					Error::<T>::InsufficientShares
+				);
+			}
+
+			let order_id = NextOrderId::<T>::try_mutate(|id| -> Result<OrderId, DispatchError> {
+				let current = *id;
+				*id = id.checked_add(1).ok_or(Error::<T>::NoAvailableOrderId)?;
+				Ok(current)
+			})?;
+			Self::deposit_event(Event::OrderPlaced(
+				order_id,
+				who.clone(),
+				token,
+				side,
+				shares,
+				price,
+			));
+
+			let (remaining, fills) = Self::match_order(order_id, &who, token, side, shares, price)?;
+
+			if !remaining.is_zero() {
+				let count = OrderCount::<T>::get(token);
+				ensure!(
+					count < T::MaxOrdersPerToken::get(),
+					Error::<T>::TooManyOrders
+				);
+				if side == OrderSide::Bid {
+					<T as base_nft::Config>::Currency::reserve(
+						&who,
+						Self::order_value(price, remaining),
+					)?;
+				}
+				OrderCount::<T>::insert(token, count + 1);
+				Orders::<T>::insert(
+					token,
+					order_id,
+					Order {
+						owner: who,
+						side,
+						shares: remaining,
+						price,
+						royalty: BaseNft::<T>::royalty_of(token),
+					},
+				);
+			}
+
+			Ok(Some(T::WeightInfo::place_order(
+				fills,
+				T::MaxRoyaltyBeneficiaries::get(),
+			))
+			.into())
+		}
+
+		/// Remove order `order_id` of the caller from the book of `token`
+		#[pallet::weight(T::WeightInfo::cancel_order())]
+		pub fn cancel_order(
+			origin: OriginFor<T>,
+			token: (T::ClassId, T::TokenId),
+			order_id: OrderId,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let order = Orders::<T>::get(token, order_id).ok_or(Error::<T>::OrderNotFound)?;
+			ensure!(order.owner == who, Error::<T>::NotOrderOwner);
+
+			Self::remove_order(token, order_id, &order);
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Whether an offer expiring at `expiry` can no longer be accepted
+	fn is_expired(expiry: T::BlockNumber) -> bool {
+		frame_system::Pallet::<T>::block_number() >= expiry
+	}
+
+	/// Part of `price` paid for `shares` out of `total` shares
+	///
+	/// Filling the remaining shares pays the remaining price, rounding never leaves dust reserved.
+	fn pro_rata(price: BalanceOf<T>, shares: T::Shares, total: T::Shares) -> BalanceOf<T> {
+		if shares >= total {
+			return price;
+		}
+		multiply_by_rational(
+			price.saturated_into(),
+			shares.saturated_into(),
+			total.saturated_into(),
+		)
+		.map(SaturatedConversion::saturated_into)
+		.unwrap_or_else(|_| Zero::zero())
+	}
+
+	/// Pay `price` reserved by `buyer` to `seller` and the `royalty` beneficiaries, and transfer
+	/// the shares
+	///
+	/// What cannot be paid to `seller` is left reserved by `buyer` for them to claim.
+	fn settle_offer(
+		buyer: &T::AccountId,
+		seller: &T::AccountId,
+		token: (T::ClassId, T::TokenId),
+		shares: T::Shares,
+		price: BalanceOf<T>,
+		royalty: &[(T::AccountId, Perbill)],
+	) -> DispatchResult {
+		let royalties = BaseNft::<T>::repatriate_royalties(buyer, token, royalty, price);
+		BaseNft::<T>::repatriate_proceeds(buyer, seller, token, price.saturating_sub(royalties));
+		BaseNft::<T>::transfer(seller, buyer, token, shares)
+	}
+
+	/// Sell the shares of every holder of `token` to the approved whole token `offer` of
+	/// `buyer`, the part of the price for shares the buyer already holds is refunded
+	fn sell_whole_token(
+		token: (T::ClassId, T::TokenId),
+		buyer: &T::AccountId,
+		mut offer: OfferOf<T>,
+	) -> DispatchResult {
+		Offers::<T>::remove(token, buyer);
+		OfferCount::<T>::mutate(token, |count| *count = count.saturating_sub(1));
+
+		for (seller, shares) in BaseNft::<T>::owners_of_token(token, 0, T::MaxOwnersPerToken::get())
+		{
+			if &seller == buyer {
+				continue;
+			}
+			let price = Self::pro_rata(offer.price, shares, offer.shares);
+			offer.shares = offer.shares.saturating_sub(shares);
+			offer.price = offer.price.saturating_sub(price);
+			Self::settle_offer(buyer, &seller, token, shares, price, &offer.royalty)?;
+
+			Self::deposit_event(Event::OfferAccepted(
+				seller,
+				buyer.clone(),
+				token,
+				shares,
+				price,
+			));
+		}
+		<T as base_nft::Config>::Currency::unreserve(buyer, offer.price);
+		Ok(())
+	}
+
+	/// Value of `shares` at `price` per share
+	fn order_value(price: BalanceOf<T>, shares: T::Shares) -> BalanceOf<T> {
+		price.saturating_mul(shares.saturated_into::<u128>().saturated_into())
+	}
+
+	/// Shares of `token` asked for by `owner` on the book
+	fn asked_shares(owner: &T::AccountId, token: (T::ClassId, T::TokenId)) -> T::Shares {
+		Orders::<T>::iter_prefix_values(token)
+			.filter(|order| order.side == OrderSide::Ask && &order.owner == owner)
+			.fold(Zero::zero(), |total: T::Shares, order| {
+				total.saturating_add(order.shares)
+			})
+	}
+
+	/// Fill order `order_id` of `taker` against the crossing orders of other accounts on the
+	/// book of `token`, returns the shares left and the number of fills
+	///
+	/// Each fill pays the royalty of the resting order it fills.
+	fn match_order(
+		order_id: OrderId,
+		taker: &T::AccountId,
+		token: (T::ClassId, T::TokenId),
+		side: OrderSide,
+		shares: T::Shares,
+		price: BalanceOf<T>,
+	) -> Result<(T::Shares, u32), DispatchError> {
+		let mut makers: Vec<(OrderId, OrderOf<T>)> = Orders::<T>::iter_prefix(token)
+			.filter(|(_, order)| {
+				order.side != side
+					&& &order.owner != taker
+					&& match side {
+						OrderSide::Bid => order.price <= price,
+						OrderSide::Ask => order.price >= price,
+					}
+			})
+			.collect();
+		makers.sort_by(|(a_id, a), (b_id, b)| match side {
+			OrderSide::Bid => a.price.cmp(&b.price).then(a_id.cmp(b_id)),
+			OrderSide::Ask => b.price.cmp(&a.price).then(a_id.cmp(b_id)),
+		});
+
+		let mut remaining = shares;
+		let mut fills = 0;
+		for (maker_id, mut maker) in makers {
+			if remaining.is_zero() {
+				break;
+			}
+			let filled = remaining.min(maker.shares);
+			remaining -= filled;
+			maker.shares -= filled;
+			if maker.shares.is_zero() {
+				Orders::<T>::remove(token, maker_id);
+				OrderCount::<T>::mutate(token, |count| *count = count.saturating_sub(1));
+			} else {
+				Orders::<T>::insert(token, maker_id, &maker);
+			}
+
+			let value = Self::order_value(maker.price, filled);
+			let (buyer, seller) = match side {
+				OrderSide::Bid => {
+					let royalties =
+						BaseNft::<T>::pay_royalties(taker, token, &maker.royalty, value)?;
+					<T as base_nft::Config>::Currency::transfer(
+						taker,
+						&maker.owner,
+						value.saturating_sub(royalties),
+						ExistenceRequirement::KeepAlive,
+					)?;
+					(taker, &maker.owner)
+				}
+				OrderSide::Ask => {
+					let royalties = BaseNft::<T>::repatriate_royalties(
+						&maker.owner,
+						token,
+						&maker.royalty,
+						value,
+					);
+					<T as base_nft::Config>::Currency::repatriate_reserved(
+						&maker.owner,
+						taker,
+						value.saturating_sub(royalties),
+						BalanceStatus::Free,
+					)?;
+					(&maker.owner, taker)
+				}
+			};
+			BaseNft::<T>::transfer(seller, buyer, token, filled)?;
+			fills += 1;
+
+			Self::deposit_event(Event::OrderFilled(
+				token,
+				maker_id,
+				order_id,
+				buyer.clone(),
+				seller.clone(),
+				filled,
+				maker.price,
+			));
+		}
+		Ok((remaining, fills))
+	}
+
+	/// Remove order `order_id` from the book of `token` and refund a bid
+	fn remove_order(token: (T::ClassId, T::TokenId), order_id: OrderId, order: &OrderOf<T>) {
+		Orders::<T>::remove(token, order_id);
+		OrderCount::<T>::mutate(token, |count| *count = count.saturating_sub(1));
+		if order.side == OrderSide::Bid {
+			<T as base_nft::Config>::Currency::unreserve(
+				&order.owner,
+				Self::order_value(order.price, order.shares),
+			);
+		}
+		Self::deposit_event(Event::OrderCancelled(token, order_id, order.owner.clone()));
+	}
+
+	/// Remove the offer of `buyer` on `token` and refund its price
+	fn remove_offer(token: (T::ClassId, T::TokenId), buyer: &T::AccountId, offer: &OfferOf<T>) {
+		Offers::<T>::remove(token, buyer);
+		OfferCount::<T>::mutate(token, |count| *count = count.saturating_sub(1));
+		<T as base_nft::Config>::Currency::unreserve(buyer, offer.price);
+		Self::deposit_event(Event::OfferCancelled(buyer.clone(), token));
+	}
+}
+
+impl<T: Config> OnTokenChange<T::AccountId, T::ClassId, T::TokenId, T::Shares> for Pallet<T> {
+	/// The listing and the asks of the sender are cancelled once the shares left no longer back
+	/// them.
+	fn on_transfer(
+		from: &T::AccountId,
+		_to: &T::AccountId,
+		token: (T::ClassId, T::TokenId),
+		_shares: T::Shares,
+	) {
+		let remaining = BaseNft::<T>::shares_of(from, token);
+		let listed = Listings::<T>::get(token, from).map(|listing| listing.shares);
+		if matches!(listed, Some(listed) if listed > remaining) {
+			Listings::<T>::remove(token, from);
+			Self::deposit_event(Event::ListingCancelled(from.clone(), token));
+		}
+		if Self::asked_shares(from, token) > remaining {
+			let asks: Vec<(OrderId, OrderOf<T>)> = Orders::<T>::iter_prefix(token)
+				.filter(|(_, order)| order.side == OrderSide::Ask && &order.owner == from)
+				.collect();
+			for (order_id, order) in asks {
+				Self::remove_order(token, order_id, &order);
+			}
+		}
+	}
+
+	fn on_burn(token: (T::ClassId, T::TokenId), _owners: &[(T::AccountId, T::Shares)]) {
+		for (seller, _) in Listings::<T>::drain_prefix(token) {
+			Self::deposit_event(Event::ListingCancelled(seller, token));
+		}
+		OfferCount::<T>::remove(token);
+		for (buyer, offer) in Offers::<T>::drain_prefix(token) {
+			<T as base_nft::Config>::Currency::unreserve(&buyer, offer.price);
+			Self::deposit_event(Event::OfferCancelled(buyer, token));
+		}
+		let orders: Vec<(OrderId, OrderOf<T>)> = Orders::<T>::iter_prefix(token).collect();
+		for (order_id, order) in orders {
+			Self::remove_order(token, order_id, &order);
+		}
+	}
+
+	fn on_transfer_weight() -> Weight {
+		T::WeightInfo::on_transfer(T::MaxOrdersPerToken::get())
+	}
+
+	/// A token has at most one listing per owner
+	fn on_burn_weight(owners: u32) -> Weight {
+		T::WeightInfo::on_burn(
+			owners,
+			T::MaxOffersPerToken::get(),
+			T::MaxOrdersPerToken::get(),
+		)
+	}
+}
@@ -0,0 +1,1204 @@
+#![cfg(test)]
+
+use crate::mock::{Event, *};
+use crate::{Error, FloorOffer, Listing, Offer, Order, OrderSide};
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::Perbill;
+
+/// Token minted to `owner` in a new class
+fn mint_token(owner: &AccountId) -> (u32, u32) {
+	let class_id = BaseNft::create_class(owner, vec![1], ()).unwrap();
+	let token_id = BaseNft::mint(owner, class_id, vec![1], ()).unwrap();
+	(class_id, token_id)
+}
+
+#[test]
+fn list_should_work() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+
+		assert_ok!(Marketplace::list(Origin::signed(ALICE), token, 40, 200));
+		assert_eq!(
+			Marketplace::listings(token, ALICE),
+			Some(Listing {
+				shares: 40,
+				price: 200,
+				royalty: vec![]
+			})
+		);
+		assert_eq!(
+			last_event(),
+			Event::pallet_marketplace(crate::Event::Listed(ALICE, token, 40, 200))
+		);
+
+		// listing again replaces the offer
+		assert_ok!(Marketplace::list(Origin::signed(ALICE), token, 100, 500));
+		assert_eq!(
+			Marketplace::listings(token, ALICE),
+			Some(Listing {
+				shares: 100,
+				price: 500,
+				royalty: vec![]
+			})
+		);
+	});
+}
+
+#[test]
+fn list_should_fail() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+
+		assert_noop!(
+			Marketplace::list(Origin::signed(ALICE), token, 0, 200),
+			Error::<Runtime>::ZeroShares
+		);
+		assert_noop!(
+			Marketplace::list(Origin::signed(ALICE), token, 101, 200),
+			Error::<Runtime>::InsufficientShares
+		);
+		assert_noop!(
+			Marketplace::list(Origin::signed(BOB), token, 1, 200),
+			Error::<Runtime>::InsufficientShares
+		);
+	});
+}
+
+#[test]
+fn cancel_listing_should_work() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+
+		assert_noop!(
+			Marketplace::cancel_listing(Origin::signed(ALICE), token),
+			Error::<Runtime>::ListingNotFound
+		);
+
+		assert_ok!(Marketplace::list(Origin::signed(ALICE), token, 100, 200));
+		assert_ok!(Marketplace::cancel_listing(Origin::signed(ALICE), token));
+		assert_eq!(Marketplace::listings(token, ALICE), None);
+		assert_eq!(
+			last_event(),
+			Event::pallet_marketplace(crate::Event::ListingCancelled(ALICE, token))
+		);
+	});
+}
+
+#[test]
+fn buy_whole_token_should_work() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+		assert_ok!(Marketplace::list(Origin::signed(ALICE), token, 100, 200));
+
+		assert_ok!(Marketplace::buy(Origin::signed(BOB), token, ALICE, 200));
+
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 200);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE - 200);
+		assert_eq!(BaseNft::shares_of(&BOB, token), 100);
+		assert!(!BaseNft::is_owner(&ALICE, token));
+		assert_eq!(Marketplace::listings(token, ALICE), None);
+		assert_eq!(
+			last_event(),
+			Event::pallet_marketplace(crate::Event::Sold(ALICE, BOB, token, 100, 200))
+		);
+	});
+}
+
+#[test]
+fn buy_pays_royalties() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+		assert_ok!(BaseNft::set_class_royalty(
+			&ALICE,
+			token.0,
+			vec![(CHARLIE, Perbill::from_percent(10))]
+		));
+		assert_ok!(Marketplace::list(Origin::signed(ALICE), token, 100, 200));
+		assert_ok!(Marketplace::buy(Origin::signed(BOB), token, ALICE, 200));
+
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE - 200);
+		assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE + 20);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 180);
+
+		// resales pay the token override
+		assert_ok!(BaseNft::set_token_royalty(
+			&ALICE,
+			token,
+			Some(vec![(ALICE, Perbill::from_percent(5))])
+		));
+		assert_ok!(Marketplace::list(Origin::signed(BOB), token, 100, 400));
+		assert_ok!(Marketplace::buy(Origin::signed(CHARLIE), token, BOB, 400));
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 200);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE + 180);
+		assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE - 380);
+	});
+}
+
+#[test]
+fn buy_pays_royalty_of_listing() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+		assert_ok!(BaseNft::set_class_royalty(
+			&ALICE,
+			token.0,
+			vec![(ALICE, Perbill::from_percent(10))]
+		));
+		assert_ok!(BaseNft::transfer(&ALICE, &BOB, token, 100));
+		assert_ok!(Marketplace::list(Origin::signed(BOB), token, 100, 200));
+
+		// the class owner raises the royalty of a listed token
+		assert_ok!(BaseNft::set_class_royalty(
+			&ALICE,
+			token.0,
+			vec![(ALICE, Perbill::from_percent(50))]
+		));
+		assert_ok!(Marketplace::buy(Origin::signed(CHARLIE), token, BOB, 200));
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE + 180);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 20);
+
+		// resting orders keep the royalty they were placed with
+		assert_ok!(Marketplace::place_order(
+			Origin::signed(CHARLIE),
+			token,
+			OrderSide::Ask,
+			100,
+			2
+		));
+		assert_ok!(BaseNft::set_class_royalty(&ALICE, token.0, vec![]));
+		assert_ok!(Marketplace::place_order(
+			Origin::signed(BOB),
+			token,
+			OrderSide::Bid,
+			100,
+			2
+		));
+		assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE - 200 + 100);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 120);
+
+		// so do offers
+		assert_ok!(BaseNft::set_class_royalty(
+			&ALICE,
+			token.0,
+			vec![(ALICE, Perbill::from_percent(10))]
+		));
+		assert_ok!(Marketplace::make_offer(
+			Origin::signed(CHARLIE),
+			token,
+			100,
+			100,
+			10,
+			false
+		));
+		assert_ok!(BaseNft::set_class_royalty(
+			&ALICE,
+			token.0,
+			vec![(ALICE, Perbill::from_percent(50))]
+		));
+		assert_ok!(Marketplace::accept_offer(
+			Origin::signed(BOB),
+			token,
+			CHARLIE,
+			100
+		));
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE + 70);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 130);
+	});
+}
+
+#[test]
+fn buy_fractional_share_should_work() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+		assert_ok!(Marketplace::list(Origin::signed(ALICE), token, 25, 50));
+
+		assert_ok!(Marketplace::buy(Origin::signed(BOB), token, ALICE, 60));
+
+		assert_eq!(BaseNft::shares_of(&ALICE, token), 75);
+		assert_eq!(BaseNft::shares_of(&BOB, token), 25);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 50);
+
+		// co-owners list independently
+		assert_ok!(Marketplace::list(Origin::signed(ALICE), token, 75, 150));
+		assert_ok!(Marketplace::list(Origin::signed(BOB), token, 25, 80));
+		assert_ok!(Marketplace::buy(Origin::signed(CHARLIE), token, BOB, 80));
+		assert_eq!(BaseNft::shares_of(&CHARLIE, token), 25);
+		assert!(Marketplace::listings(token, ALICE).is_some());
+	});
+}
+
+#[test]
+fn buy_should_fail() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+
+		assert_noop!(
+			Marketplace::buy(Origin::signed(BOB), token, ALICE, 200),
+			Error::<Runtime>::ListingNotFound
+		);
+
+		assert_ok!(Marketplace::list(Origin::signed(ALICE), token, 100, 200));
+		assert_noop!(
+			Marketplace::buy(Origin::signed(ALICE), token, ALICE, 200),
+			Error::<Runtime>::BuyerIsSeller
+		);
+		assert_noop!(
+			Marketplace::buy(Origin::signed(BOB), token, ALICE, 199),
+			Error::<Runtime>::PriceTooHigh
+		);
+
+		assert_ok!(Marketplace::list(
+			Origin::signed(ALICE),
+			token,
+			100,
+			INITIAL_BALANCE * 2
+		));
+		assert_noop!(
+			Marketplace::buy(Origin::signed(BOB), token, ALICE, INITIAL_BALANCE * 2),
+			pallet_balances::Error::<Runtime>::InsufficientBalance
+		);
+		assert!(Marketplace::listings(token, ALICE).is_some());
+	});
+}
+
+#[test]
+fn transfer_below_listed_shares_cancels_listing() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+		assert_ok!(Marketplace::list(Origin::signed(ALICE), token, 50, 200));
+
+		// the shares left still cover the listing
+		assert_ok!(BaseNft::transfer(&ALICE, &BOB, token, 50));
+		assert!(Marketplace::listings(token, ALICE).is_some());
+
+		assert_ok!(BaseNft::transfer(&ALICE, &BOB, token, 10));
+
+		assert_eq!(Marketplace::listings(token, ALICE), None);
+		assert_eq!(
+			last_event(),
+			Event::pallet_marketplace(crate::Event::ListingCancelled(ALICE, token))
+		);
+		assert_noop!(
+			Marketplace::buy(Origin::signed(CHARLIE), token, ALICE, 200),
+			Error::<Runtime>::ListingNotFound
+		);
+
+		// the recipient's listing is untouched
+		assert_ok!(Marketplace::list(Origin::signed(BOB), token, 60, 20));
+		assert_ok!(BaseNft::transfer(&ALICE, &BOB, token, 10));
+		assert!(Marketplace::listings(token, BOB).is_some());
+	});
+}
+
+#[test]
+fn burn_cancels_listings() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+		assert_ok!(BaseNft::transfer(&ALICE, &BOB, token, 10));
+		assert_ok!(Marketplace::list(Origin::signed(ALICE), token, 90, 200));
+		assert_ok!(Marketplace::list(Origin::signed(BOB), token, 10, 20));
+
+		assert_ok!(BaseNft::burn_with_approvals(&[ALICE], token));
+
+		assert_eq!(Marketplace::listings(token, ALICE), None);
+		assert_eq!(Marketplace::listings(token, BOB), None);
+	});
+}
+
+#[test]
+fn accept_offer_should_work() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+		assert_ok!(BaseNft::transfer(&ALICE, &CHARLIE, token, 30));
+
+		assert_ok!(Marketplace::make_offer(
+			Origin::signed(BOB),
+			token,
+			100,
+			200,
+			10,
+			true
+		));
+		assert_eq!(Balances::reserved_balance(BOB), 200);
+		assert_eq!(Marketplace::offer_count(token), 1);
+		assert_eq!(
+			last_event(),
+			Event::pallet_marketplace(crate::Event::OfferMade(BOB, token, 100, 200, 10, true))
+		);
+
+		// co-owners fill the offer with the pro-rata price
+		assert_ok!(Marketplace::accept_offer(
+			Origin::signed(ALICE),
+			token,
+			BOB,
+			40
+		));
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 80);
+		assert_eq!(
+			Marketplace::offers(token, BOB),
+			Some(Offer {
+				shares: 60,
+				price: 120,
+				expiry: 10,
+				partial: true,
+				royalty: vec![],
+				approvals: vec![],
+			})
+		);
+		assert_eq!(
+			last_event(),
+			Event::pallet_marketplace(crate::Event::OfferAccepted(ALICE, BOB, token, 40, 80))
+		);
+
+		assert_ok!(Marketplace::accept_offer(
+			Origin::signed(CHARLIE),
+			token,
+			BOB,
+			30
+		));
+		assert_ok!(Marketplace::accept_offer(
+			Origin::signed(ALICE),
+			token,
+			BOB,
+			30
+		));
+
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 140);
+		assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE + 60);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE - 200);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(BaseNft::shares_of(&BOB, token), 100);
+		assert_eq!(Marketplace::offers(token, BOB), None);
+		assert_eq!(Marketplace::offer_count(token), 0);
+	});
+}
+
+#[test]
+fn make_offer_should_fail() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+
+		assert_noop!(
+			Marketplace::make_offer(Origin::signed(BOB), token, 0, 200, 10, false),
+			Error::<Runtime>::ZeroShares
+		);
+		assert_noop!(
+			Marketplace::make_offer(Origin::signed(BOB), token, 101, 200, 10, false),
+			Error::<Runtime>::TooManyShares
+		);
+		assert_noop!(
+			Marketplace::make_offer(Origin::signed(BOB), (token.0, 1), 100, 200, 10, false),
+			base_nft::Error::<Runtime>::TokenNotFound
+		);
+		assert_noop!(
+			Marketplace::make_offer(Origin::signed(BOB), token, 100, 200, 1, false),
+			Error::<Runtime>::InvalidExpiry
+		);
+		assert_noop!(
+			Marketplace::make_offer(
+				Origin::signed(BOB),
+				token,
+				100,
+				INITIAL_BALANCE * 2,
+				10,
+				false
+			),
+			pallet_balances::Error::<Runtime>::InsufficientBalance
+		);
+
+		// a new offer replaces the previous one of the buyer
+		assert_ok!(Marketplace::make_offer(
+			Origin::signed(BOB),
+			token,
+			100,
+			200,
+			10,
+			false
+		));
+		assert_ok!(Marketplace::make_offer(
+			Origin::signed(BOB),
+			token,
+			50,
+			300,
+			10,
+			false
+		));
+		assert_eq!(Balances::reserved_balance(BOB), 300);
+		assert_eq!(Marketplace::offer_count(token), 1);
+
+		assert_ok!(Marketplace::make_offer(
+			Origin::signed(ALICE),
+			token,
+			10,
+			10,
+			10,
+			false
+		));
+		assert_ok!(Marketplace::make_offer(
+			Origin::signed(CHARLIE),
+			token,
+			10,
+			10,
+			10,
+			false
+		));
+		assert_noop!(
+			Marketplace::make_offer(Origin::signed(4), token, 10, 10, 10, false),
+			Error::<Runtime>::TooManyOffers
+		);
+	});
+}
+
+#[test]
+fn accept_offer_should_fail() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+
+		assert_noop!(
+			Marketplace::accept_offer(Origin::signed(ALICE), token, BOB, 50),
+			Error::<Runtime>::OfferNotFound
+		);
+
+		assert_ok!(Marketplace::make_offer(
+			Origin::signed(BOB),
+			token,
+			50,
+			200,
+			10,
+			false
+		));
+		assert_noop!(
+			Marketplace::accept_offer(Origin::signed(BOB), token, BOB, 50),
+			Error::<Runtime>::BuyerIsSeller
+		);
+		assert_noop!(
+			Marketplace::accept_offer(Origin::signed(CHARLIE), token, BOB, 50),
+			Error::<Runtime>::InsufficientShares
+		);
+		assert_noop!(
+			Marketplace::accept_offer(Origin::signed(ALICE), token, BOB, 60),
+			Error::<Runtime>::OfferTooSmall
+		);
+		assert_noop!(
+			Marketplace::accept_offer(Origin::signed(ALICE), token, BOB, 40),
+			Error::<Runtime>::PartialFillNotAllowed
+		);
+
+		System::set_block_number(10);
+		assert_noop!(
+			Marketplace::accept_offer(Origin::signed(ALICE), token, BOB, 50),
+			Error::<Runtime>::OfferExpired
+		);
+	});
+}
+
+#[test]
+fn approve_offer_should_work() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+		assert_ok!(BaseNft::transfer(&ALICE, &CHARLIE, token, 35));
+		assert_ok!(BaseNft::transfer(&ALICE, &4, token, 25));
+		assert_ok!(Marketplace::make_offer(
+			Origin::signed(BOB),
+			token,
+			100,
+			200,
+			10,
+			false
+		));
+
+		assert_ok!(Marketplace::approve_offer(
+			Origin::signed(CHARLIE),
+			token,
+			BOB
+		));
+		assert_eq!(
+			Marketplace::offers(token, BOB).map(|offer| offer.approvals),
+			Some(vec![CHARLIE])
+		);
+		assert_eq!(
+			last_event(),
+			Event::pallet_marketplace(crate::Event::OfferApproved(CHARLIE, BOB, token))
+		);
+
+		// holders of more than half of the shares sell the whole token
+		assert_ok!(Marketplace::approve_offer(
+			Origin::signed(ALICE),
+			token,
+			BOB
+		));
+		assert_eq!(BaseNft::shares_of(&BOB, token), 100);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 80);
+		assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE + 70);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE - 200);
+		// 4 has no account to be paid into, its part stays reserved for it to claim
+		assert_eq!(Balances::free_balance(4), 0);
+		assert_eq!(BaseNft::unclaimed_proceeds(4, BOB), 50);
+		assert_eq!(Balances::reserved_balance(BOB), 50);
+		assert_eq!(Marketplace::offers(token, BOB), None);
+		assert_eq!(Marketplace::offer_count(token), 0);
+		assert_eq!(
+			last_event(),
+			Event::pallet_marketplace(crate::Event::OfferAccepted(4, BOB, token, 25, 50))
+		);
+	});
+}
+
+#[test]
+fn approve_offer_refunds_shares_of_buyer() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+		assert_ok!(BaseNft::transfer(&ALICE, &BOB, token, 20));
+		assert_ok!(Marketplace::make_offer(
+			Origin::signed(BOB),
+			token,
+			100,
+			200,
+			10,
+			false
+		));
+
+		assert_ok!(Marketplace::approve_offer(
+			Origin::signed(ALICE),
+			token,
+			BOB
+		));
+		assert_eq!(BaseNft::shares_of(&BOB, token), 100);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 160);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE - 160);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+	});
+}
+
+#[test]
+fn approve_offer_should_fail() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+		assert_ok!(BaseNft::transfer(&ALICE, &CHARLIE, token, 50));
+
+		assert_noop!(
+			Marketplace::approve_offer(Origin::signed(ALICE), token, BOB),
+			Error::<Runtime>::OfferNotFound
+		);
+
+		assert_ok!(Marketplace::make_offer(
+			Origin::signed(BOB),
+			token,
+			50,
+			200,
+			10,
+			true
+		));
+		assert_noop!(
+			Marketplace::approve_offer(Origin::signed(ALICE), token, BOB),
+			Error::<Runtime>::NotWholeTokenOffer
+		);
+
+		assert_ok!(Marketplace::make_offer(
+			Origin::signed(BOB),
+			token,
+			100,
+			200,
+			10,
+			true
+		));
+		assert_noop!(
+			Marketplace::approve_offer(Origin::signed(BOB), token, BOB),
+			Error::<Runtime>::BuyerIsSeller
+		);
+		assert_noop!(
+			Marketplace::approve_offer(Origin::signed(4), token, BOB),
+			Error::<Runtime>::InsufficientShares
+		);
+
+		// half of the shares is not a majority
+		assert_ok!(Marketplace::approve_offer(
+			Origin::signed(ALICE),
+			token,
+			BOB
+		));
+		assert_eq!(BaseNft::shares_of(&ALICE, token), 50);
+		assert_noop!(
+			Marketplace::approve_offer(Origin::signed(ALICE), token, BOB),
+			Error::<Runtime>::OfferAlreadyApproved
+		);
+
+		// a partial fill leaves an offer for part of the token
+		assert_ok!(Marketplace::accept_offer(
+			Origin::signed(CHARLIE),
+			token,
+			BOB,
+			10
+		));
+		assert_noop!(
+			Marketplace::approve_offer(Origin::signed(CHARLIE), token, BOB),
+			Error::<Runtime>::NotWholeTokenOffer
+		);
+
+		System::set_block_number(10);
+		assert_noop!(
+			Marketplace::approve_offer(Origin::signed(CHARLIE), token, BOB),
+			Error::<Runtime>::OfferExpired
+		);
+	});
+}
+
+#[test]
+fn cancel_offer_should_work() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+		assert_ok!(Marketplace::make_offer(
+			Origin::signed(BOB),
+			token,
+			100,
+			200,
+			10,
+			false
+		));
+
+		assert_noop!(
+			Marketplace::cancel_offer(Origin::signed(CHARLIE), token, BOB),
+			Error::<Runtime>::OfferNotExpired
+		);
+		assert_ok!(Marketplace::cancel_offer(Origin::signed(BOB), token, BOB));
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Marketplace::offer_count(token), 0);
+		assert_eq!(
+			last_event(),
+			Event::pallet_marketplace(crate::Event::OfferCancelled(BOB, token))
+		);
+		assert_noop!(
+			Marketplace::cancel_offer(Origin::signed(BOB), token, BOB),
+			Error::<Runtime>::OfferNotFound
+		);
+
+		// anyone cancels an expired offer
+		assert_ok!(Marketplace::make_offer(
+			Origin::signed(BOB),
+			token,
+			100,
+			200,
+			10,
+			false
+		));
+		System::set_block_number(10);
+		assert_ok!(Marketplace::cancel_offer(
+			Origin::signed(CHARLIE),
+			token,
+			BOB
+		));
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE);
+	});
+}
+
+#[test]
+fn burn_cancels_offers() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+		assert_ok!(Marketplace::make_offer(
+			Origin::signed(BOB),
+			token,
+			100,
+			200,
+			10,
+			false
+		));
+		assert_ok!(Marketplace::make_offer(
+			Origin::signed(CHARLIE),
+			token,
+			10,
+			30,
+			10,
+			false
+		));
+
+		assert_ok!(BaseNft::burn_with_approvals(&[ALICE], token));
+
+		assert_eq!(Marketplace::offers(token, BOB), None);
+		assert_eq!(Marketplace::offers(token, CHARLIE), None);
+		assert_eq!(Marketplace::offer_count(token), 0);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Balances::reserved_balance(CHARLIE), 0);
+	});
+}
+
+#[test]
+fn floor_offer_should_work() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+		assert_ok!(BaseNft::set_class_royalty(
+			&ALICE,
+			token.0,
+			vec![(CHARLIE, Perbill::from_percent(10))]
+		));
+
+		assert_noop!(
+			Marketplace::make_floor_offer(Origin::signed(BOB), token.0 + 1, 300, 10),
+			base_nft::Error::<Runtime>::ClassNotFound
+		);
+		assert_ok!(Marketplace::make_floor_offer(
+			Origin::signed(BOB),
+			token.0,
+			300,
+			10
+		));
+		assert_eq!(
+			Marketplace::floor_offers(token.0, BOB),
+			Some(FloorOffer {
+				price: 300,
+				expiry: 10
+			})
+		);
+		assert_eq!(Balances::reserved_balance(BOB), 300);
+
+		// only the holder of every share accepts
+		assert_ok!(BaseNft::transfer(&ALICE, &CHARLIE, token, 10));
+		assert_noop!(
+			Marketplace::accept_floor_offer(Origin::signed(ALICE), token, BOB),
+			Error::<Runtime>::NotWholeTokenOwner
+		);
+		assert_ok!(BaseNft::transfer(&CHARLIE, &ALICE, token, 10));
+
+		assert_ok!(Marketplace::accept_floor_offer(
+			Origin::signed(ALICE),
+			token,
+			BOB
+		));
+		assert_eq!(BaseNft::shares_of(&BOB, token), 100);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE - 300);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE + 30);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 270);
+		assert_eq!(
+			last_event(),
+			Event::pallet_marketplace(crate::Event::FloorOfferAccepted(ALICE, BOB, token, 300))
+		);
+		assert_noop!(
+			Marketplace::accept_floor_offer(Origin::signed(BOB), token, BOB),
+			Error::<Runtime>::BuyerIsSeller
+		);
+		assert_noop!(
+			Marketplace::accept_floor_offer(Origin::signed(BOB), token, CHARLIE),
+			Error::<Runtime>::OfferNotFound
+		);
+	});
+}
+
+#[test]
+fn cancel_floor_offer_should_work() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+		assert_ok!(Marketplace::make_floor_offer(
+			Origin::signed(BOB),
+			token.0,
+			300,
+			10
+		));
+
+		assert_noop!(
+			Marketplace::cancel_floor_offer(Origin::signed(CHARLIE), token.0, BOB),
+			Error::<Runtime>::OfferNotExpired
+		);
+		System::set_block_number(10);
+		assert_noop!(
+			Marketplace::accept_floor_offer(Origin::signed(ALICE), token, BOB),
+			Error::<Runtime>::OfferExpired
+		);
+		assert_ok!(Marketplace::cancel_floor_offer(
+			Origin::signed(CHARLIE),
+			token.0,
+			BOB
+		));
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Marketplace::floor_offers(token.0, BOB), None);
+		assert_eq!(
+			last_event(),
+			Event::pallet_marketplace(crate::Event::FloorOfferCancelled(BOB, token.0))
+		);
+	});
+}
+
+#[test]
+fn place_order_should_match() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+		assert_ok!(Marketplace::place_order(
+			Origin::signed(ALICE),
+			token,
+			OrderSide::Ask,
+			30,
+			5
+		));
+		assert_ok!(Marketplace::place_order(
+			Origin::signed(ALICE),
+			token,
+			OrderSide::Ask,
+			20,
+			4
+		));
+		assert_eq!(
+			last_event(),
+			Event::pallet_marketplace(crate::Event::OrderPlaced(
+				1,
+				ALICE,
+				token,
+				OrderSide::Ask,
+				20,
+				4
+			))
+		);
+
+		// a bid fills the lowest asks first at their price
+		assert_ok!(Marketplace::place_order(
+			Origin::signed(BOB),
+			token,
+			OrderSide::Bid,
+			40,
+			5
+		));
+		assert_eq!(
+			last_event(),
+			Event::pallet_marketplace(crate::Event::OrderFilled(token, 0, 2, BOB, ALICE, 20, 5))
+		);
+		assert_eq!(BaseNft::shares_of(&BOB, token), 40);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE - 180);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 180);
+		assert_eq!(Marketplace::orders(token, 1), None);
+		assert_eq!(Marketplace::orders(token, 2), None);
+		assert_eq!(
+			Marketplace::orders(token, 0),
+			Some(Order {
+				owner: ALICE,
+				side: OrderSide::Ask,
+				shares: 10,
+				price: 5,
+				royalty: vec![]
+			})
+		);
+
+		// bids that do not cross rest on the book with their value reserved
+		assert_ok!(Marketplace::place_order(
+			Origin::signed(CHARLIE),
+			token,
+			OrderSide::Bid,
+			20,
+			3
+		));
+		assert_eq!(Balances::reserved_balance(CHARLIE), 60);
+		assert_eq!(Marketplace::order_count(token), 2);
+
+		// an ask fills the bid at the bid price and rests for the shares left
+		assert_ok!(Marketplace::place_order(
+			Origin::signed(BOB),
+			token,
+			OrderSide::Ask,
+			25,
+			2
+		));
+		assert_eq!(BaseNft::shares_of(&CHARLIE, token), 20);
+		assert_eq!(Balances::reserved_balance(CHARLIE), 0);
+		assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE - 60);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE - 120);
+		assert_eq!(Marketplace::orders(token, 3), None);
+		assert_eq!(
+			Marketplace::orders(token, 4),
+			Some(Order {
+				owner: BOB,
+				side: OrderSide::Ask,
+				shares: 5,
+				price: 2,
+				royalty: vec![]
+			})
+		);
+		assert_eq!(Marketplace::order_count(token), 2);
+	});
+}
+
+#[test]
+fn place_order_skips_own_orders() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+		assert_ok!(Marketplace::place_order(
+			Origin::signed(ALICE),
+			token,
+			OrderSide::Ask,
+			10,
+			5
+		));
+		assert_ok!(Marketplace::place_order(
+			Origin::signed(ALICE),
+			token,
+			OrderSide::Bid,
+			10,
+			6
+		));
+
+		assert_eq!(BaseNft::shares_of(&ALICE, token), 100);
+		assert_eq!(Balances::reserved_balance(ALICE), 60);
+		assert_eq!(Marketplace::order_count(token), 2);
+	});
+}
+
+#[test]
+fn place_order_pays_royalties() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+		assert_ok!(BaseNft::set_class_royalty(
+			&ALICE,
+			token.0,
+			vec![(CHARLIE, Perbill::from_percent(10))]
+		));
+
+		assert_ok!(Marketplace::place_order(
+			Origin::signed(ALICE),
+			token,
+			OrderSide::Ask,
+			50,
+			4
+		));
+		assert_ok!(Marketplace::place_order(
+			Origin::signed(BOB),
+			token,
+			OrderSide::Bid,
+			50,
+			4
+		));
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE - 200);
+		assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE + 20);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 180);
+
+		// royalties of a resting bid are paid from its reserve
+		assert_ok!(Marketplace::place_order(
+			Origin::signed(BOB),
+			token,
+			OrderSide::Bid,
+			50,
+			2
+		));
+		assert_ok!(Marketplace::place_order(
+			Origin::signed(ALICE),
+			token,
+			OrderSide::Ask,
+			50,
+			2
+		));
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE - 300);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE + 30);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 270);
+		assert_eq!(BaseNft::shares_of(&BOB, token), 100);
+	});
+}
+
+#[test]
+fn place_order_should_fail() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+
+		assert_noop!(
+			Marketplace::place_order(Origin::signed(ALICE), token, OrderSide::Ask, 0, 5),
+			Error::<Runtime>::ZeroShares
+		);
+		assert_noop!(
+			Marketplace::place_order(Origin::signed(ALICE), token, OrderSide::Ask, 101, 5),
+			Error::<Runtime>::TooManyShares
+		);
+		assert_noop!(
+			Marketplace::place_order(Origin::signed(ALICE), token, OrderSide::Ask, 10, 0),
+			Error::<Runtime>::ZeroPrice
+		);
+		assert_noop!(
+			Marketplace::place_order(Origin::signed(BOB), (token.0, 1), OrderSide::Bid, 10, 5),
+			base_nft::Error::<Runtime>::TokenNotFound
+		);
+		assert_noop!(
+			Marketplace::place_order(Origin::signed(BOB), token, OrderSide::Ask, 10, 5),
+			Error::<Runtime>::InsufficientShares
+		);
+		assert_noop!(
+			Marketplace::place_order(Origin::signed(BOB), token, OrderSide::Bid, 100, 20),
+			pallet_balances::Error::<Runtime>::InsufficientBalance
+		);
+
+		// asks cannot commit the same shares twice
+		assert_ok!(Marketplace::place_order(
+			Origin::signed(ALICE),
+			token,
+			OrderSide::Ask,
+			60,
+			5
+		));
+		assert_noop!(
+			Marketplace::place_order(Origin::signed(ALICE), token, OrderSide::Ask, 50, 5),
+			Error::<Runtime>::InsufficientShares
+		);
+
+		assert_ok!(Marketplace::place_order(
+			Origin::signed(ALICE),
+			token,
+			OrderSide::Ask,
+			40,
+			6
+		));
+		assert_ok!(Marketplace::place_order(
+			Origin::signed(BOB),
+			token,
+			OrderSide::Bid,
+			10,
+			1
+		));
+		assert_ok!(Marketplace::place_order(
+			Origin::signed(CHARLIE),
+			token,
+			OrderSide::Bid,
+			10,
+			1
+		));
+		assert_noop!(
+			Marketplace::place_order(Origin::signed(BOB), token, OrderSide::Bid, 10, 2),
+			Error::<Runtime>::TooManyOrders
+		);
+		// orders that fill completely never rest on the book
+		assert_ok!(Marketplace::place_order(
+			Origin::signed(BOB),
+			token,
+			OrderSide::Bid,
+			10,
+			5
+		));
+	});
+}
+
+#[test]
+fn cancel_order_should_work() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+		assert_ok!(Marketplace::place_order(
+			Origin::signed(BOB),
+			token,
+			OrderSide::Bid,
+			10,
+			5
+		));
+
+		assert_noop!(
+			Marketplace::cancel_order(Origin::signed(ALICE), token, 0),
+			Error::<Runtime>::NotOrderOwner
+		);
+		assert_ok!(Marketplace::cancel_order(Origin::signed(BOB), token, 0));
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Marketplace::order_count(token), 0);
+		assert_eq!(
+			last_event(),
+			Event::pallet_marketplace(crate::Event::OrderCancelled(token, 0, BOB))
+		);
+		assert_noop!(
+			Marketplace::cancel_order(Origin::signed(BOB), token, 0),
+			Error::<Runtime>::OrderNotFound
+		);
+	});
+}
+
+#[test]
+fn transfer_cancels_uncovered_asks() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+		assert_ok!(Marketplace::place_order(
+			Origin::signed(ALICE),
+			token,
+			OrderSide::Ask,
+			60,
+			5
+		));
+
+		assert_ok!(BaseNft::transfer(&ALICE, &BOB, token, 30));
+		assert!(Marketplace::orders(token, 0).is_some());
+
+		assert_ok!(BaseNft::transfer(&ALICE, &BOB, token, 20));
+		assert_eq!(Marketplace::orders(token, 0), None);
+		assert_eq!(Marketplace::order_count(token), 0);
+		assert_eq!(
+			last_event(),
+			Event::pallet_marketplace(crate::Event::OrderCancelled(token, 0, ALICE))
+		);
+	});
+}
+
+#[test]
+fn burn_cancels_orders() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+		assert_ok!(Marketplace::place_order(
+			Origin::signed(ALICE),
+			token,
+			OrderSide::Ask,
+			60,
+			5
+		));
+		assert_ok!(Marketplace::place_order(
+			Origin::signed(BOB),
+			token,
+			OrderSide::Bid,
+			10,
+			4
+		));
+
+		assert_ok!(BaseNft::burn_with_approvals(&[ALICE], token));
+
+		assert_eq!(Marketplace::orders(token, 0), None);
+		assert_eq!(Marketplace::orders(token, 1), None);
+		assert_eq!(Marketplace::order_count(token), 0);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+	});
+}
+
+#[test]
+fn inactive_tokens_do_not_trade() {
+	new_test_ext().execute_with(|| {
+		let token = mint_token(&ALICE);
+		assert_ok!(Marketplace::list(Origin::signed(ALICE), token, 40, 200));
+		assert_ok!(Marketplace::make_offer(
+			Origin::signed(BOB),
+			token,
+			40,
+			200,
+			10,
+			false
+		));
+		assert_ok!(Marketplace::make_floor_offer(
+			Origin::signed(BOB),
+			token.0,
+			300,
+			10
+		));
+		assert_ok!(BaseNft::set_token_metadata(&ALICE, token, vec![]));
+
+		assert_noop!(
+			Marketplace::list(Origin::signed(ALICE), token, 40, 200),
+			Error::<Runtime>::TokenInactive
+		);
+		assert_noop!(
+			Marketplace::buy(Origin::signed(BOB), token, ALICE, 200),
+			Error::<Runtime>::TokenInactive
+		);
+		assert_noop!(
+			Marketplace::make_offer(Origin::signed(CHARLIE), token, 40, 200, 10, false),
+			Error::<Runtime>::TokenInactive
+		);
+		assert_noop!(
+			Marketplace::accept_offer(Origin::signed(ALICE), token, BOB, 40),
+			Error::<Runtime>::TokenInactive
+		);
+		assert_noop!(
+			Marketplace::accept_floor_offer(Origin::signed(ALICE), token, BOB),
+			Error::<Runtime>::TokenInactive
+		);
+		assert_noop!(
+			Marketplace::place_order(Origin::signed(BOB), token, OrderSide::Bid, 10, 5),
+			Error::<Runtime>::TokenInactive
+		);
+
+		// open trades are still cancelled
+		assert_ok!(Marketplace::cancel_listing(Origin::signed(ALICE), token));
+		assert_ok!(Marketplace::cancel_offer(Origin::signed(BOB), token, BOB));
+	});
+}
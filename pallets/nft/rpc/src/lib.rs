@@ -0,0 +1,267 @@
+//! RPC interface for querying NFT classes and tokens.
+
+use std::{
+	convert::{TryFrom, TryInto},
+	sync::Arc,
+};
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+pub use nft_primitives::NftApi as NftRuntimeApi;
+use nft_primitives::{ClassDetails, TokenDetails, MAX_PAGE_SIZE};
+use sp_api::{ApiErrorExt, ApiExt, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+/// Page size used when the client doesn't ask for one.
+const DEFAULT_PAGE_SIZE: u32 = 100;
+
+/// Error code returned when a runtime API call fails.
+const RUNTIME_ERROR: i64 = 1;
+
+#[rpc]
+pub trait NftApi<BlockHash, AccountId, ClassId, TokenId, Shares, Balance> {
+	#[rpc(name = "nft_tokensOfOwner")]
+	fn tokens_of_owner(
+		&self,
+		owner: AccountId,
+		offset: Option<u32>,
+		limit: Option<u32>,
+		at: Option<BlockHash>,
+	) -> Result<Vec<((ClassId, TokenId), Shares)>>;
+
+	#[rpc(name = "nft_ownersOfToken")]
+	fn owners_of_token(
+		&self,
+		class_id: ClassId,
+		token_id: TokenId,
+		offset: Option<u32>,
+		limit: Option<u32>,
+		at: Option<BlockHash>,
+	) -> Result<Vec<(AccountId, Shares)>>;
+
+	#[rpc(name = "nft_classInfo")]
+	fn class_info(
+		&self,
+		class_id: ClassId,
+		at: Option<BlockHash>,
+	) -> Result<Option<ClassDetails<AccountId, TokenId>>>;
+
+	#[rpc(name = "nft_tokenInfo")]
+	fn token_info(
+		&self,
+		class_id: ClassId,
+		token_id: TokenId,
+		at: Option<BlockHash>,
+	) -> Result<Option<TokenDetails>>;
+
+	#[rpc(name = "nft_classTotalIssuance")]
+	fn class_total_issuance(
+		&self,
+		class_id: ClassId,
+		at: Option<BlockHash>,
+	) -> Result<Option<TokenId>>;
+
+	#[rpc(name = "nft_royaltiesFor")]
+	fn royalties_for(
+		&self,
+		class_id: ClassId,
+		token_id: TokenId,
+		price: NumberOrHex,
+		at: Option<BlockHash>,
+	) -> Result<Vec<(AccountId, NumberOrHex)>>;
+
+	#[rpc(name = "nft_tokensByContent")]
+	fn tokens_by_content(
+		&self,
+		cid: String,
+		at: Option<BlockHash>,
+	) -> Result<Vec<(ClassId, TokenId)>>;
+}
+
+/// NFT queries answered by the runtime `NftApi`.
+pub struct Nft<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> Nft<C, B> {
+	/// Create new `Nft` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Nft {
+			client,
+			_marker: Default::default(),
+		}
+	}
+
+	fn block_id<Block: BlockT>(&self, at: Option<Block::Hash>) -> BlockId<Block>
+	where
+		C: HeaderBackend<Block>,
+	{
+		BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash))
+	}
+}
+
+fn page_limit(limit: Option<u32>) -> u32 {
+	limit.unwrap_or(DEFAULT_PAGE_SIZE).min(MAX_PAGE_SIZE)
+}
+
+fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(RUNTIME_ERROR),
+		message: "Runtime error".into(),
+		data: Some(format!("{:?}", err).into()),
+	}
+}
+
+impl<C, Block, AccountId, ClassId, TokenId, Shares, Balance>
+	NftApi<<Block as BlockT>::Hash, AccountId, ClassId, TokenId, Shares, Balance> for Nft<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: NftRuntimeApi<Block, AccountId, ClassId, TokenId, Shares, Balance>,
+	AccountId: Codec,
+	ClassId: Codec,
+	TokenId: Codec,
+	Shares: Codec,
+	Balance: Codec + Copy + TryFrom<NumberOrHex> + TryInto<NumberOrHex>,
+{
+	fn tokens_of_owner(
+		&self,
+		owner: AccountId,
+		offset: Option<u32>,
+		limit: Option<u32>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<((ClassId, TokenId), Shares)>> {
+		let api = self.client.runtime_api();
+		let at = self.block_id(at);
+
+		api.tokens_of_owner(&at, owner, offset.unwrap_or(0), page_limit(limit))
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn owners_of_token(
+		&self,
+		class_id: ClassId,
+		token_id: TokenId,
+		offset: Option<u32>,
+		limit: Option<u32>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<(AccountId, Shares)>> {
+		let api = self.client.runtime_api();
+		let at = self.block_id(at);
+
+		api.owners_of_token(
+			&at,
+			class_id,
+			token_id,
+			offset.unwrap_or(0),
+			page_limit(limit),
+		)
+		.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn class_info(
+		&self,
+		class_id: ClassId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<ClassDetails<AccountId, TokenId>>> {
+		let api = self.client.runtime_api();
+		let at = self.block_id(at);
+
+		api.class_info(&at, class_id)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn token_info(
+		&self,
+		class_id: ClassId,
+		token_id: TokenId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<TokenDetails>> {
+		let api = self.client.runtime_api();
+		let at = self.block_id(at);
+
+		let version_2 = api
+			.has_api_with::<dyn NftRuntimeApi<
+				Block,
+				AccountId,
+				ClassId,
+				TokenId,
+				Shares,
+				Balance,
+				Error = <C::Api as ApiErrorExt>::Error,
+			>, _>(&at, |version| version >= 2)
+			.map_err(runtime_error_into_rpc_err)?;
+		if !version_2 {
+			#[allow(deprecated)]
+			return api
+				.token_info_before_version_2(&at, class_id, token_id)
+				.map(|details| details.map(Into::into))
+				.map_err(runtime_error_into_rpc_err);
+		}
+
+		api.token_info(&at, class_id, token_id)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn class_total_issuance(
+		&self,
+		class_id: ClassId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<TokenId>> {
+		let api = self.client.runtime_api();
+		let at = self.block_id(at);
+
+		api.class_total_issuance(&at, class_id)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn royalties_for(
+		&self,
+		class_id: ClassId,
+		token_id: TokenId,
+		price: NumberOrHex,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<(AccountId, NumberOrHex)>> {
+		let api = self.client.runtime_api();
+		let at = self.block_id(at);
+
+		let price = Balance::try_from(price).map_err(|_| RpcError {
+			code: ErrorCode::InvalidParams,
+			message: format!("{:?} doesn't fit in Balance representation", price),
+			data: None,
+		})?;
+		let royalties = api
+			.royalties_for(&at, class_id, token_id, price)
+			.map_err(runtime_error_into_rpc_err)?;
+
+		royalties
+			.into_iter()
+			.map(|(beneficiary, amount)| {
+				amount
+					.try_into()
+					.map(|amount| (beneficiary, amount))
+					.map_err(|_| RpcError {
+						code: ErrorCode::InvalidParams,
+						message: "Royalty doesn't fit in NumberOrHex representation".into(),
+						data: None,
+					})
+			})
+			.collect()
+	}
+
+	fn tokens_by_content(
+		&self,
+		cid: String,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<(ClassId, TokenId)>> {
+		let api = self.client.runtime_api();
+		let at = self.block_id(at);
+
+		api.tokens_by_content(&at, cid.into_bytes())
+			.map_err(runtime_error_into_rpc_err)
+	}
+}
@@ -1,27 +1,109 @@
 #![cfg(feature = "runtime-benchmarks")]
 
 use super::*;
-use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use base_nft::BalanceOf;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::traits::Currency;
 use frame_system::RawOrigin;
+use sp_runtime::traits::Bounded;
 use sp_std::{boxed::Box, vec, vec::Vec};
 
+/// Longest accepted CID, a base16 CIDv1 of a sha2-512 digest
 fn get_ipfs_cid() -> ByteVector {
-	vec![1_u8; MAX_IPFS_CID_CHAR_LENGTH - 1]
+	let mut ipfs_cid = b"f01701340".to_vec();
+	ipfs_cid.resize(ipfs_cid.len() + 128, b'a');
+	ipfs_cid
+}
+
+/// Caller able to pay the class and token storage deposits
+fn funded_caller<T: Config>() -> T::AccountId {
+	let caller: T::AccountId = whitelisted_caller();
+	<T as base_nft::Config>::Currency::make_free_balance_be(
+		&caller,
+		BalanceOf::<T>::max_value() / 2_u32.into(),
+	);
+	caller
 }
 
 benchmarks! {
 	create_nft_class {
-		let caller: T::AccountId = whitelisted_caller();
+		let caller = funded_caller::<T>();
 		let ipfs_cid_metadata = get_ipfs_cid();
 	}: _(RawOrigin::Signed(caller), ipfs_cid_metadata)
 
 	mint_ipfs_nft {
-		let caller: T::AccountId = whitelisted_caller();
+		let caller = funded_caller::<T>();
 		let signed_caller = RawOrigin::Signed(caller);
 		let ipfs_cid_metadata = get_ipfs_cid();
 
 		Pallet::<T>::create_nft_class(signed_caller.clone().into(), ipfs_cid_metadata.clone())?;
 	}: _(signed_caller, ipfs_cid_metadata)
+
+	mint {
+		let caller = funded_caller::<T>();
+		let ipfs_cid_metadata = get_ipfs_cid();
+
+		let class_id = BaseNft::<T>::create_class(&caller, vec![1_u8], Default::default())?;
+	}: _(RawOrigin::Signed(caller), class_id, ipfs_cid_metadata)
+
+	burn {
+		let caller = funded_caller::<T>();
+		let ipfs_cid_metadata = get_ipfs_cid();
+
+		let class_id = BaseNft::<T>::create_class(&caller, ipfs_cid_metadata.clone(), Default::default())?;
+		let token_id = BaseNft::<T>::mint(&caller, class_id, ipfs_cid_metadata, Default::default())?;
+	}: _(RawOrigin::Signed(caller), (class_id, token_id))
+
+	destroy_class {
+		let m in 0 .. T::MaxMintersPerClass::get();
+
+		let caller = funded_caller::<T>();
+		let ipfs_cid_metadata = get_ipfs_cid();
+
+		let class_id = BaseNft::<T>::create_class(&caller, ipfs_cid_metadata, Default::default())?;
+		for i in 0 .. m {
+			let minter = account("minter", i, 0);
+			let origin = RawOrigin::Signed(caller.clone()).into();
+			Pallet::<T>::set_class_minter(origin, class_id, minter, true)?;
+		}
+	}: _(RawOrigin::Signed(caller), class_id)
+
+	set_class_metadata {
+		let caller = funded_caller::<T>();
+		let ipfs_cid_metadata = get_ipfs_cid();
+
+		let class_id = BaseNft::<T>::create_class(&caller, vec![1_u8], Default::default())?;
+	}: _(RawOrigin::Signed(caller), class_id, ipfs_cid_metadata)
+
+	set_token_metadata {
+		let caller = funded_caller::<T>();
+		let ipfs_cid_metadata = get_ipfs_cid();
+
+		let class_id = BaseNft::<T>::create_class(&caller, vec![1_u8], Default::default())?;
+		let token_id = BaseNft::<T>::mint(&caller, class_id, vec![1_u8], Default::default())?;
+	}: _(RawOrigin::Signed(caller), (class_id, token_id), ipfs_cid_metadata)
+
+	batch_mint {
+		let n in 1 .. T::MaxBatchSize::get();
+
+		let caller = funded_caller::<T>();
+		let class_id = BaseNft::<T>::create_class(&caller, vec![1_u8], Default::default())?;
+		let tokens = (0 .. n)
+			.map(|i| (get_ipfs_cid(), account("owner", i, 0)))
+			.collect::<Vec<(ByteVector, T::AccountId)>>();
+	}: _(RawOrigin::Signed(caller), class_id, tokens)
+
+	batch_transfer {
+		let n in 1 .. T::MaxBatchSize::get();
+
+		let caller = funded_caller::<T>();
+		let class_id = BaseNft::<T>::create_class(&caller, vec![1_u8], Default::default())?;
+		let mut transfers = Vec::new();
+		for i in 0 .. n {
+			let token_id = BaseNft::<T>::mint(&caller, class_id, vec![1_u8], Default::default())?;
+			transfers.push(((class_id, token_id), account("recipient", i, 0), T::SharesPerToken::get()));
+		}
+	}: _(RawOrigin::Signed(caller), transfers)
 }
 
 impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Runtime);
@@ -1,9 +1,23 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use base_nft::Module as BaseNft;
-use frame_support::{dispatch::DispatchResultWithPostInfo, pallet_prelude::*};
+use anmol_utils::{cid::Cid, metadata::MetadataError};
+use base_nft::{Module as BaseNft, OnTokenChange, RoyaltyOf};
+use codec::{Decode, Encode};
+use frame_support::{
+	dispatch::DispatchResultWithPostInfo, pallet_prelude::*, traits::Filter, transactional,
+	weights::Pays,
+};
+use sp_core::crypto::KeyTypeId;
+use sp_runtime::{
+	offchain::{http, storage::StorageValueRef, Duration, StorageKind},
+	traits::Saturating,
+	DispatchError, DispatchResult, RuntimeDebug,
+};
 
-use frame_system::{offchain::CreateSignedTransaction, pallet_prelude::*};
+use frame_system::{
+	offchain::{AppCrypto, CreateSignedTransaction, SendSignedTransaction, Signer},
+	pallet_prelude::*,
+};
 
 pub use pallet::*;
 use sp_std::vec::Vec;
@@ -12,13 +26,105 @@ mod mock;
 mod tests;
 
 mod benchmarking;
+pub mod migrations;
 pub mod weights;
 pub use weights::WeightInfo;
 
 type ByteVector = Vec<u8>;
 
+/// Token and the metadata the offchain worker verifies
+type VerificationOf<T> = (
+	(
+		<T as base_nft::Config>::ClassId,
+		<T as base_nft::Config>::TokenId,
+	),
+	ByteVector,
+);
+
+/// Transfer of `batch_transfer`, `(token, to, shares)`
+pub type TransferOf<T> = (
+	(
+		<T as base_nft::Config>::ClassId,
+		<T as base_nft::Config>::TokenId,
+	),
+	<T as frame_system::Config>::AccountId,
+	<T as base_nft::Config>::Shares,
+);
+
 pub const MAX_IPFS_CID_CHAR_LENGTH: usize = 200;
 
+/// Key type of the accounts signing metadata attestations
+pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"nfto");
+
+/// Persistent offchain local storage key of the IPFS gateway overriding `Config::IpfsGateway`
+pub const IPFS_GATEWAY_STORAGE_KEY: &[u8] = b"nft::ipfs-gateway";
+
+/// Milliseconds the offchain worker waits for the gateway to serve a CID
+pub const IPFS_FETCH_TIMEOUT_MS: u64 = 10_000;
+
+/// Blocks the offchain worker waits before fetching a CID again if its attestation is pending
+pub const ATTESTATION_RETRY_BLOCKS: u32 = 10;
+
+/// Crypto of the accounts signing metadata attestations
+pub mod crypto {
+	use super::KEY_TYPE;
+	use sp_core::sr25519::{Public as Sr25519Public, Signature as Sr25519Signature};
+	use sp_runtime::{
+		app_crypto::{app_crypto, sr25519},
+		MultiSignature, MultiSigner,
+	};
+
+	app_crypto!(sr25519, KEY_TYPE);
+
+	pub struct OracleAuthId;
+
+	impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for OracleAuthId {
+		type RuntimeAppPublic = Public;
+		type GenericSignature = Sr25519Signature;
+		type GenericPublic = Sr25519Public;
+	}
+}
+
+/// Outcome of fetching the metadata of a token from IPFS
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug)]
+pub enum MetadataStatus {
+	/// CID resolves to metadata following the Anmol metadata standard, see
+	/// `anmol_utils::metadata`
+	Verified,
+	/// CID does not resolve in time
+	Unreachable,
+	/// CID resolves to content other than a JSON object
+	Malformed,
+	/// CID resolves to a JSON object not following the Anmol metadata standard
+	NonStandard,
+}
+
+/// Whether a class accepts tokens carrying content already carried by another token
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug)]
+pub enum DuplicatePolicy {
+	/// Any content may be minted
+	Allow,
+	/// Content carried by a token of the class is rejected
+	RejectInClass,
+	/// Content carried by a token of any class is rejected
+	RejectGlobal,
+}
+
+impl Default for DuplicatePolicy {
+	fn default() -> Self {
+		DuplicatePolicy::Allow
+	}
+}
+
+/// Proposal to burn a co-owned token
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
+pub struct BurnProposal<AccountId> {
+	/// Owner who proposed to burn the token
+	pub proposer: AccountId,
+	/// Sorted owners who approved to burn the token
+	pub approvals: Vec<AccountId>,
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -30,15 +136,194 @@ pub mod pallet {
 		type Call: From<Call<Self>>;
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 		type WeightInfo: WeightInfo;
+		/// The public class `mint_ipfs_nft` mints into
+		#[pallet::constant]
+		type PublicIpfsClassId: Get<Self::ClassId>;
+		/// Classes any account may mint into
+		type OpenMintClasses: Get<Vec<Self::ClassId>>;
+		/// Maximum number of accounts authorized to mint into a class
+		#[pallet::constant]
+		type MaxMintersPerClass: Get<u32>;
+		/// Maximum number of tokens minted or transferred by a batch call
+		#[pallet::constant]
+		type MaxBatchSize: Get<u32>;
+		/// Crypto of the accounts signing metadata attestations
+		type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
+		/// Origin allowed to add and remove oracles
+		type OracleOrigin: EnsureOrigin<Self::Origin>;
+		/// IPFS HTTP gateway the offchain worker fetches metadata from, e.g.
+		/// `http://127.0.0.1:8080`, overridden by `IPFS_GATEWAY_STORAGE_KEY` in the offchain
+		/// local storage
+		#[pallet::constant]
+		type IpfsGateway: Get<&'static str>;
+		/// Maximum number of CIDs the offchain worker fetches per block
+		#[pallet::constant]
+		type MaxVerificationsPerBlock: Get<u32>;
+		/// Maximum length in bytes of the metadata the offchain worker reads, larger metadata is
+		/// `Malformed`
+		#[pallet::constant]
+		type MaxIpfsMetadataLength: Get<u32>;
 	}
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
 	pub struct Pallet<T>(_);
 
+	/// Pending burn proposals of co-owned tokens.
+	#[pallet::storage]
+	#[pallet::getter(fn burn_proposals)]
+	pub type BurnProposals<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::ClassId,
+		Twox64Concat,
+		T::TokenId,
+		BurnProposal<T::AccountId>,
+	>;
+
+	/// Accounts authorized by the class owner to mint into a class.
+	#[pallet::storage]
+	#[pallet::getter(fn class_minters)]
+	pub type ClassMinters<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::ClassId, Twox64Concat, T::AccountId, bool, ValueQuery>;
+
+	/// Number of accounts in `ClassMinters` of a class, at most `MaxMintersPerClass`.
+	#[pallet::storage]
+	#[pallet::getter(fn class_minter_count)]
+	pub type ClassMinterCount<T: Config> = StorageMap<_, Twox64Concat, T::ClassId, u32, ValueQuery>;
+
+	/// First token of a class carrying a content, by the multihash of its CID.
+	#[pallet::storage]
+	#[pallet::getter(fn content_index)]
+	pub type ContentIndex<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, ByteVector, Twox64Concat, T::ClassId, T::TokenId>;
+
+	/// Every token of a class carrying a content, by the multihash of its CID.
+	///
+	/// Keeps `ContentIndex` pointing at a surviving token when the first one is burnt.
+	#[pallet::storage]
+	pub type ContentTokens<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		(ByteVector, T::ClassId),
+		Twox64Concat,
+		T::TokenId,
+		(),
+	>;
+
+	/// Duplicate content policy of a class.
+	#[pallet::storage]
+	#[pallet::getter(fn duplicate_policies)]
+	pub type DuplicatePolicies<T: Config> =
+		StorageMap<_, Twox64Concat, T::ClassId, DuplicatePolicy, ValueQuery>;
+
+	/// Classes whose tokens are only active once their metadata is `Verified`.
+	#[pallet::storage]
+	#[pallet::getter(fn strict_metadata)]
+	pub type StrictMetadata<T: Config> = StorageMap<_, Twox64Concat, T::ClassId, bool, ValueQuery>;
+
+	/// Metadata status attested by an oracle.
+	#[pallet::storage]
+	#[pallet::getter(fn metadata_status)]
+	pub type MetadataStatuses<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::ClassId, Twox64Concat, T::TokenId, MetadataStatus>;
+
+	/// Tokens whose metadata awaits an attestation.
+	#[pallet::storage]
+	#[pallet::getter(fn pending_verifications)]
+	pub type PendingVerifications<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::ClassId, Twox64Concat, T::TokenId, ()>;
+
+	/// Sorted accounts allowed to attest metadata.
+	#[pallet::storage]
+	#[pallet::getter(fn oracles)]
+	pub type Oracles<T: Config> = StorageValue<_, Vec<T::AccountId>, ValueQuery>;
+
+	/// Storage layout version of the pallet.
+	#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug)]
+	pub enum Releases {
+		/// Tokens are not indexed by content
+		V1_0_0,
+		/// Tokens carrying a content are indexed in `ContentIndex` and `ContentTokens`
+		V2_0_0,
+	}
+
+	impl Default for Releases {
+		fn default() -> Self {
+			Releases::V1_0_0
+		}
+	}
+
+	/// Storage version of the pallet.
+	#[pallet::storage]
+	pub(super) type StorageVersion<T: Config> = StorageValue<_, Releases, ValueQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// Accounts allowed to attest metadata from genesis
+		pub oracles: Vec<T::AccountId>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			GenesisConfig { oracles: vec![] }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			let mut oracles = self.oracles.clone();
+			oracles.sort();
+			oracles.dedup();
+			Oracles::<T>::put(oracles);
+
+			// the base NFT genesis is built first, see `construct_runtime!` of the runtime
+			migrations::migrate_to_v2::<T>();
+			// tokens carrying a content await attestation like minted ones
+			base_nft::Tokens::<T>::iter()
+				.filter(|(_, _, info)| Cid::parse(&info.metadata).is_ok())
+				.for_each(|(class_id, token_id, _)| {
+					Pallet::<T>::request_verification((class_id, token_id))
+				});
+		}
+	}
+
 	#[pallet::error]
 	pub enum Error<T> {
+		/// Metadata is `MAX_IPFS_CID_CHAR_LENGTH` bytes or longer
 		MaxIpfsCidCharLength,
+		/// Caller is neither the sender nor approved by it
+		NotOwnerOrApproved,
+		/// Caller owns no share of the token
+		NotTokenOwner,
+		/// Token has a pending burn proposal already
+		BurnProposalExists,
+		/// Token has no pending burn proposal
+		BurnProposalNotFound,
+		/// Owner approved the burn proposal already
+		BurnAlreadyApproved,
+		/// Only the proposer cancels a burn proposal
+		NotBurnProposer,
+		/// Caller does not own the class
+		NotClassOwner,
+		/// Caller is neither the owner nor a minter of the class
+		NotClassMinter,
+		/// Class has `MaxMintersPerClass` minters already
+		TooManyMinters,
+		/// Batch holds more than `MaxBatchSize` items
+		BatchTooLarge,
+		/// Metadata is not the text form of a CIDv0 or CIDv1
+		InvalidCid,
+		/// Content is already carried by a token and the class policy rejects duplicates
+		DuplicateContent,
+		/// Account is not an oracle
+		NotOracle,
+		/// Account is already an oracle
+		OracleExists,
+		/// Token metadata awaits no attestation or changed since it was fetched
+		StaleAttestation,
 	}
 
 	#[pallet::event]
@@ -47,6 +332,42 @@ pub mod pallet {
 	pub enum Event<T: Config> {
 		NftClassCreated(T::AccountId, T::ClassId, ByteVector),
 		IpfsNftMinted(T::AccountId, T::TokenId, ByteVector),
+		/// Owner proposed to burn a co-owned token. \[proposer, token\]
+		BurnProposed(T::AccountId, (T::ClassId, T::TokenId)),
+		/// Owner approved the burn proposal of a token. \[owner, token\]
+		BurnApproved(T::AccountId, (T::ClassId, T::TokenId)),
+		/// Burn proposal was withdrawn by its proposer. \[token\]
+		BurnProposalCancelled((T::ClassId, T::TokenId)),
+		/// Enough owners approved and the token was burned. \[token\]
+		BurnProposalExecuted((T::ClassId, T::TokenId)),
+		/// Token was burned by its owner. \[owner, token\]
+		NftBurned(T::AccountId, (T::ClassId, T::TokenId)),
+		/// Class was destroyed by its owner. \[owner, class_id\]
+		NftClassDestroyed(T::AccountId, T::ClassId),
+		/// Class metadata was updated. \[owner, class_id, ipfs_cid_metadata\]
+		NftClassMetadataUpdated(T::AccountId, T::ClassId, ByteVector),
+		/// Token metadata was updated. \[owner, token, ipfs_cid_metadata\]
+		NftMetadataUpdated(T::AccountId, (T::ClassId, T::TokenId), ByteVector),
+		/// Token was minted into a class. \[minter, token, ipfs_cid_metadata\]
+		NftMinted(T::AccountId, (T::ClassId, T::TokenId), ByteVector),
+		/// Class owner (dis)allowed an account to mint. \[owner, class_id, minter, authorized\]
+		ClassMinterSet(T::AccountId, T::ClassId, T::AccountId, bool),
+		/// Class owner set the royalty of the class. \[owner, class_id\]
+		ClassRoyaltySet(T::AccountId, T::ClassId),
+		/// Class owner set or removed the royalty override of a token. \[owner, token\]
+		TokenRoyaltySet(T::AccountId, (T::ClassId, T::TokenId)),
+		/// Class owner set the duplicate content policy. \[owner, class_id, policy\]
+		DuplicatePolicySet(T::AccountId, T::ClassId, DuplicatePolicy),
+		/// Class owner (dis)allowed tokens with unverified metadata. \[owner, class_id, strict\]
+		StrictMetadataSet(T::AccountId, T::ClassId, bool),
+		/// Oracle attested the metadata of a token. \[oracle, token, status\]
+		MetadataAttested(T::AccountId, (T::ClassId, T::TokenId), MetadataStatus),
+		/// Oracle was added. \[oracle\]
+		OracleAdded(T::AccountId),
+		/// Oracle was removed. \[oracle\]
+		OracleRemoved(T::AccountId),
+		/// Seller claimed the sale proceeds a buyer could not pay them. \[seller, buyer\]
+		ProceedsClaimed(T::AccountId, T::AccountId),
 	}
 
 	#[pallet::call]
@@ -58,10 +379,7 @@ pub mod pallet {
 		) -> DispatchResultWithPostInfo {
 			let account_id = ensure_signed(origin)?;
 
-			ensure!(
-				ipfs_cid_metadata.len() < MAX_IPFS_CID_CHAR_LENGTH,
-				Error::<T>::MaxIpfsCidCharLength
-			);
+			Self::ensure_valid_ipfs_cid(&ipfs_cid_metadata)?;
 
 			let class_id = BaseNft::<T>::create_class(
 				&account_id,
@@ -77,17 +395,25 @@ pub mod pallet {
 			Ok(().into())
 		}
 
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 2))]
+		#[pallet::weight(
+			(10_000 + T::DbWeight::get().reads_writes(4, 4))
+				.saturating_add(T::OnTokenChange::on_transfer_weight())
+		)]
 		pub fn transfer(
 			origin: OriginFor<T>,
 			from: T::AccountId,
 			to: T::AccountId,
 			token: (T::ClassId, T::TokenId),
-			percentage: u8,
+			shares: T::Shares,
 		) -> DispatchResultWithPostInfo {
-			ensure_signed(origin)?;
+			let account_id = ensure_signed(origin)?;
 
-			BaseNft::<T>::transfer(&from, &to, token, percentage)?;
+			ensure!(
+				BaseNft::<T>::is_approved_or_owner(&account_id, &from, token),
+				Error::<T>::NotOwnerOrApproved
+			);
+
+			BaseNft::<T>::transfer(&from, &to, token, shares)?;
 
 			Ok(().into())
 		}
@@ -99,16 +425,10 @@ pub mod pallet {
 		) -> DispatchResultWithPostInfo {
 			let account_id = ensure_signed(origin)?;
 
-			ensure!(
-				ipfs_cid_metadata.len() < MAX_IPFS_CID_CHAR_LENGTH,
-				Error::<T>::MaxIpfsCidCharLength
-			);
-
-			let token_id = BaseNft::<T>::mint(
+			let token_id = Self::do_mint(
 				&account_id,
-				0_u32.into(), // TODO: Replace with enum NftClassId.IpfsNft
+				T::PublicIpfsClassId::get(),
 				ipfs_cid_metadata.clone(),
-				Default::default(),
 			)?;
 
 			debug::info!("--- IPFS NFT minted: {:?}", ipfs_cid_metadata);
@@ -120,9 +440,749 @@ pub mod pallet {
 			));
 			Ok(().into())
 		}
+
+		#[pallet::weight(T::WeightInfo::mint())]
+		pub fn mint(
+			origin: OriginFor<T>,
+			class_id: T::ClassId,
+			ipfs_cid_metadata: ByteVector,
+		) -> DispatchResultWithPostInfo {
+			let account_id = ensure_signed(origin)?;
+
+			let token_id = Self::do_mint(&account_id, class_id, ipfs_cid_metadata.clone())?;
+
+			Self::deposit_event(Event::NftMinted(
+				account_id,
+				(class_id, token_id),
+				ipfs_cid_metadata,
+			));
+			Ok(().into())
+		}
+
+		/// Mint a token for each `(ipfs_cid_metadata, owner)` into `class_id`, the caller pays the
+		/// deposits
+		#[pallet::weight(
+			T::WeightInfo::batch_mint(tokens.len() as u32).saturating_add(
+				T::OnTokenChange::on_transfer_weight().saturating_mul(tokens.len() as Weight)
+			)
+		)]
+		#[transactional]
+		pub fn batch_mint(
+			origin: OriginFor<T>,
+			class_id: T::ClassId,
+			tokens: Vec<(ByteVector, T::AccountId)>,
+		) -> DispatchResultWithPostInfo {
+			let account_id = ensure_signed(origin)?;
+
+			ensure!(
+				tokens.len() <= T::MaxBatchSize::get() as usize,
+				Error::<T>::BatchTooLarge
+			);
+
+			for (ipfs_cid_metadata, owner) in tokens {
+				let token_id = Self::do_mint(&account_id, class_id, ipfs_cid_metadata.clone())?;
+				BaseNft::<T>::transfer(
+					&account_id,
+					&owner,
+					(class_id, token_id),
+					T::SharesPerToken::get(),
+				)?;
+
+				Self::deposit_event(Event::NftMinted(
+					account_id.clone(),
+					(class_id, token_id),
+					ipfs_cid_metadata,
+				));
+			}
+			Ok(().into())
+		}
+
+		/// Transfer each `(token, to, shares)` held by the caller, all of them or none
+		#[pallet::weight(
+			T::WeightInfo::batch_transfer(transfers.len() as u32).saturating_add(
+				T::OnTokenChange::on_transfer_weight().saturating_mul(transfers.len() as Weight)
+			)
+		)]
+		#[transactional]
+		pub fn batch_transfer(
+			origin: OriginFor<T>,
+			transfers: Vec<TransferOf<T>>,
+		) -> DispatchResultWithPostInfo {
+			let account_id = ensure_signed(origin)?;
+
+			ensure!(
+				transfers.len() <= T::MaxBatchSize::get() as usize,
+				Error::<T>::BatchTooLarge
+			);
+
+			for (token, to, shares) in transfers {
+				BaseNft::<T>::transfer(&account_id, &to, token, shares)?;
+			}
+			Ok(().into())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 2))]
+		pub fn set_class_minter(
+			origin: OriginFor<T>,
+			class_id: T::ClassId,
+			minter: T::AccountId,
+			authorized: bool,
+		) -> DispatchResultWithPostInfo {
+			let account_id = ensure_signed(origin)?;
+
+			let class_info =
+				BaseNft::<T>::classes(class_id).ok_or(base_nft::Error::<T>::ClassNotFound)?;
+			ensure!(class_info.owner == account_id, Error::<T>::NotClassOwner);
+
+			let is_minter = ClassMinters::<T>::get(class_id, &minter);
+			if authorized && !is_minter {
+				ClassMinterCount::<T>::try_mutate(class_id, |count| -> DispatchResult {
+					ensure!(
+						*count < T::MaxMintersPerClass::get(),
+						Error::<T>::TooManyMinters
+					);
+					*count += 1;
+					Ok(())
+				})?;
+				ClassMinters::<T>::insert(class_id, &minter, true);
+			} else if !authorized && is_minter {
+				ClassMinterCount::<T>::mutate_exists(class_id, |count| {
+					*count = count
+						.and_then(|count| count.checked_sub(1))
+						.filter(|count| *count > 0)
+				});
+				ClassMinters::<T>::remove(class_id, &minter);
+			}
+
+			Self::deposit_event(Event::ClassMinterSet(
+				account_id, class_id, minter, authorized,
+			));
+			Ok(().into())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 1))]
+		pub fn approve(
+			origin: OriginFor<T>,
+			token: (T::ClassId, T::TokenId),
+			delegate: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			let account_id = ensure_signed(origin)?;
+
+			BaseNft::<T>::approve(&account_id, token, &delegate)?;
+			Ok(().into())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn cancel_approval(
+			origin: OriginFor<T>,
+			token: (T::ClassId, T::TokenId),
+		) -> DispatchResultWithPostInfo {
+			let account_id = ensure_signed(origin)?;
+
+			BaseNft::<T>::cancel_approval(&account_id, token)?;
+			Ok(().into())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+		pub fn set_operator(
+			origin: OriginFor<T>,
+			operator: T::AccountId,
+			approved: bool,
+		) -> DispatchResultWithPostInfo {
+			let account_id = ensure_signed(origin)?;
+
+			BaseNft::<T>::set_operator(&account_id, &operator, approved)?;
+			Ok(().into())
+		}
+
+		#[pallet::weight(
+			(10_000 + T::DbWeight::get().reads_writes(
+				4,
+				4 + 2 * T::MaxOwnersPerToken::get() as Weight,
+			))
+			.saturating_add(T::OnTokenChange::on_burn_weight(T::MaxOwnersPerToken::get()))
+		)]
+		pub fn propose_burn(
+			origin: OriginFor<T>,
+			token: (T::ClassId, T::TokenId),
+		) -> DispatchResultWithPostInfo {
+			let account_id = ensure_signed(origin)?;
+
+			ensure!(
+				BaseNft::<T>::is_owner(&account_id, token),
+				Error::<T>::NotTokenOwner
+			);
+			ensure!(
+				!BurnProposals::<T>::contains_key(token.0, token.1),
+				Error::<T>::BurnProposalExists
+			);
+
+			let proposal = BurnProposal {
+				proposer: account_id.clone(),
+				approvals: [account_id.clone()].to_vec(),
+			};
+
+			Self::deposit_event(Event::BurnProposed(account_id, token));
+			Self::approve_or_execute_burn(token, proposal)?;
+			Ok(().into())
+		}
+
+		#[pallet::weight(
+			(10_000 + T::DbWeight::get().reads_writes(
+				4,
+				4 + 2 * T::MaxOwnersPerToken::get() as Weight,
+			))
+			.saturating_add(T::OnTokenChange::on_burn_weight(T::MaxOwnersPerToken::get()))
+		)]
+		pub fn approve_burn(
+			origin: OriginFor<T>,
+			token: (T::ClassId, T::TokenId),
+		) -> DispatchResultWithPostInfo {
+			let account_id = ensure_signed(origin)?;
+
+			ensure!(
+				BaseNft::<T>::is_owner(&account_id, token),
+				Error::<T>::NotTokenOwner
+			);
+			let mut proposal = BurnProposals::<T>::get(token.0, token.1)
+				.ok_or(Error::<T>::BurnProposalNotFound)?;
+
+			match proposal.approvals.binary_search(&account_id) {
+				Ok(_) => return Err(Error::<T>::BurnAlreadyApproved.into()),
+				Err(pos) => proposal.approvals.insert(pos, account_id.clone()),
+			}
+
+			Self::deposit_event(Event::BurnApproved(account_id, token));
+			Self::approve_or_execute_burn(token, proposal)?;
+			Ok(().into())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn cancel_burn_proposal(
+			origin: OriginFor<T>,
+			token: (T::ClassId, T::TokenId),
+		) -> DispatchResultWithPostInfo {
+			let account_id = ensure_signed(origin)?;
+
+			let proposal = BurnProposals::<T>::get(token.0, token.1)
+				.ok_or(Error::<T>::BurnProposalNotFound)?;
+			ensure!(proposal.proposer == account_id, Error::<T>::NotBurnProposer);
+
+			BurnProposals::<T>::remove(token.0, token.1);
+
+			Self::deposit_event(Event::BurnProposalCancelled(token));
+			Ok(().into())
+		}
+
+		#[pallet::weight(T::WeightInfo::burn().saturating_add(T::OnTokenChange::on_burn_weight(1)))]
+		pub fn burn(
+			origin: OriginFor<T>,
+			token: (T::ClassId, T::TokenId),
+		) -> DispatchResultWithPostInfo {
+			let account_id = ensure_signed(origin)?;
+
+			let content = Self::content_of(token);
+			BaseNft::<T>::burn(&account_id, token)?;
+			BurnProposals::<T>::remove(token.0, token.1);
+			Self::unindex_content(token, content);
+			Self::clear_verification(token);
+
+			Self::deposit_event(Event::NftBurned(account_id, token));
+			Ok(().into())
+		}
+
+		#[pallet::weight(T::WeightInfo::destroy_class(T::MaxMintersPerClass::get()))]
+		pub fn destroy_class(
+			origin: OriginFor<T>,
+			class_id: T::ClassId,
+		) -> DispatchResultWithPostInfo {
+			let account_id = ensure_signed(origin)?;
+
+			BaseNft::<T>::destroy_class(&account_id, class_id)?;
+			let minters = ClassMinterCount::<T>::take(class_id);
+			ClassMinters::<T>::remove_prefix(class_id);
+			DuplicatePolicies::<T>::remove(class_id);
+			StrictMetadata::<T>::remove(class_id);
+
+			Self::deposit_event(Event::NftClassDestroyed(account_id, class_id));
+			Ok(Some(T::WeightInfo::destroy_class(minters)).into())
+		}
+
+		#[pallet::weight(T::WeightInfo::set_class_metadata())]
+		pub fn set_class_metadata(
+			origin: OriginFor<T>,
+			class_id: T::ClassId,
+			ipfs_cid_metadata: ByteVector,
+		) -> DispatchResultWithPostInfo {
+			let account_id = ensure_signed(origin)?;
+
+			Self::ensure_valid_ipfs_cid(&ipfs_cid_metadata)?;
+
+			BaseNft::<T>::set_class_metadata(&account_id, class_id, ipfs_cid_metadata.clone())?;
+
+			Self::deposit_event(Event::NftClassMetadataUpdated(
+				account_id,
+				class_id,
+				ipfs_cid_metadata,
+			));
+			Ok(().into())
+		}
+
+		#[pallet::weight(T::WeightInfo::set_token_metadata())]
+		pub fn set_token_metadata(
+			origin: OriginFor<T>,
+			token: (T::ClassId, T::TokenId),
+			ipfs_cid_metadata: ByteVector,
+		) -> DispatchResultWithPostInfo {
+			let account_id = ensure_signed(origin)?;
+
+			let content = Self::ensure_valid_ipfs_cid(&ipfs_cid_metadata)?.multihash();
+			Self::ensure_unique_content(token.0, &content, Some(token))?;
+
+			let previous_content = Self::content_of(token);
+			BaseNft::<T>::set_token_metadata(&account_id, token, ipfs_cid_metadata.clone())?;
+			Self::unindex_content(token, previous_content);
+			Self::index_content(token, &content);
+			Self::request_verification(token);
+
+			Self::deposit_event(Event::NftMetadataUpdated(
+				account_id,
+				token,
+				ipfs_cid_metadata,
+			));
+			Ok(().into())
+		}
+
+		/// Set whether tokens minted into `class_id` may carry content already carried by another
+		/// token
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn set_duplicate_policy(
+			origin: OriginFor<T>,
+			class_id: T::ClassId,
+			policy: DuplicatePolicy,
+		) -> DispatchResultWithPostInfo {
+			let account_id = ensure_signed(origin)?;
+
+			let class_info =
+				BaseNft::<T>::classes(class_id).ok_or(base_nft::Error::<T>::ClassNotFound)?;
+			ensure!(class_info.owner == account_id, Error::<T>::NotClassOwner);
+
+			DuplicatePolicies::<T>::insert(class_id, policy);
+
+			Self::deposit_event(Event::DuplicatePolicySet(account_id, class_id, policy));
+			Ok(().into())
+		}
+
+		/// Set whether tokens of `class_id` are active only once their metadata is attested to
+		/// follow the Anmol metadata standard
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn set_strict_metadata(
+			origin: OriginFor<T>,
+			class_id: T::ClassId,
+			strict: bool,
+		) -> DispatchResultWithPostInfo {
+			let account_id = ensure_signed(origin)?;
+
+			let class_info =
+				BaseNft::<T>::classes(class_id).ok_or(base_nft::Error::<T>::ClassNotFound)?;
+			ensure!(class_info.owner == account_id, Error::<T>::NotClassOwner);
+
+			if strict {
+				StrictMetadata::<T>::insert(class_id, true);
+			} else {
+				StrictMetadata::<T>::remove(class_id);
+			}
+
+			Self::deposit_event(Event::StrictMetadataSet(account_id, class_id, strict));
+			Ok(().into())
+		}
+
+		/// Record the `status` of the metadata `ipfs_cid` of `token` fetched by the calling oracle,
+		/// free for oracles
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 2))]
+		pub fn submit_attestation(
+			origin: OriginFor<T>,
+			token: (T::ClassId, T::TokenId),
+			ipfs_cid: ByteVector,
+			status: MetadataStatus,
+		) -> DispatchResultWithPostInfo {
+			let account_id = ensure_signed(origin)?;
+
+			ensure!(
+				Oracles::<T>::get().binary_search(&account_id).is_ok(),
+				Error::<T>::NotOracle
+			);
+			ensure!(
+				PendingVerifications::<T>::contains_key(token.0, token.1),
+				Error::<T>::StaleAttestation
+			);
+			ensure!(
+				BaseNft::<T>::tokens(token.0, token.1).map(|info| info.metadata) == Some(ipfs_cid),
+				Error::<T>::StaleAttestation
+			);
+
+			PendingVerifications::<T>::remove(token.0, token.1);
+			MetadataStatuses::<T>::insert(token.0, token.1, status);
+
+			Self::deposit_event(Event::MetadataAttested(account_id, token, status));
+			Ok(Pays::No.into())
+		}
+
+		/// Allow `oracle` to attest metadata
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn add_oracle(
+			origin: OriginFor<T>,
+			oracle: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			T::OracleOrigin::ensure_origin(origin)?;
+
+			Oracles::<T>::try_mutate(|oracles| -> DispatchResult {
+				match oracles.binary_search(&oracle) {
+					Ok(_) => Err(Error::<T>::OracleExists.into()),
+					Err(pos) => {
+						oracles.insert(pos, oracle.clone());
+						Ok(())
+					}
+				}
+			})?;
+
+			Self::deposit_event(Event::OracleAdded(oracle));
+			Ok(().into())
+		}
+
+		/// Disallow `oracle` to attest metadata
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn remove_oracle(
+			origin: OriginFor<T>,
+			oracle: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			T::OracleOrigin::ensure_origin(origin)?;
+
+			Oracles::<T>::try_mutate(|oracles| -> DispatchResult {
+				anmol_utils::remove_vector_item(oracles, &oracle)
+					.map_err(|_| Error::<T>::NotOracle)?;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::OracleRemoved(oracle));
+			Ok(().into())
+		}
+
+		/// Pay `royalty` beneficiaries their rate of every sale of a token of the class
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn set_class_royalty(
+			origin: OriginFor<T>,
+			class_id: T::ClassId,
+			royalty: RoyaltyOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let account_id = ensure_signed(origin)?;
+
+			BaseNft::<T>::set_class_royalty(&account_id, class_id, royalty)?;
+
+			Self::deposit_event(Event::ClassRoyaltySet(account_id, class_id));
+			Ok(().into())
+		}
+
+		/// Override the class royalty for `token`, `None` restores the class royalty
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 1))]
+		pub fn set_token_royalty(
+			origin: OriginFor<T>,
+			token: (T::ClassId, T::TokenId),
+			royalty: Option<RoyaltyOf<T>>,
+		) -> DispatchResultWithPostInfo {
+			let account_id = ensure_signed(origin)?;
+
+			BaseNft::<T>::set_token_royalty(&account_id, token, royalty)?;
+
+			Self::deposit_event(Event::TokenRoyaltySet(account_id, token));
+			Ok(().into())
+		}
+
+		/// Claim the proceeds of a sale to `buyer` that could not be paid to the caller
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 3))]
+		pub fn claim_proceeds(
+			origin: OriginFor<T>,
+			buyer: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			let account_id = ensure_signed(origin)?;
+
+			BaseNft::<T>::claim_proceeds(&account_id, &buyer)?;
+
+			Self::deposit_event(Event::ProceedsClaimed(account_id, buyer));
+			Ok(().into())
+		}
 	}
 
 	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::<T>::get() == Releases::V1_0_0 {
+				migrations::migrate_to_v2::<T>()
+			} else {
+				0
+			}
+		}
+
+		/// Fetch the metadata of tokens pending verification and attest its status
+		fn offchain_worker(block_number: T::BlockNumber) {
+			let signer = Signer::<T, T::AuthorityId>::any_account();
+			if !signer.can_sign() {
+				return;
+			}
+
+			// tokens whose attestation is in flight do not count towards the limit
+			for (token, ipfs_cid) in PendingVerifications::<T>::iter()
+				.filter_map(|(class_id, token_id, _)| {
+					Self::claim_verification((class_id, token_id), block_number)
+				})
+				.take(T::MaxVerificationsPerBlock::get() as usize)
+			{
+				if let Err(e) = Self::verify_metadata(&signer, token, ipfs_cid) {
+					debug::error!("--- Metadata verification of {:?} failed: {:?}", token, e);
+				}
+			}
+		}
+	}
+}
+
+/// Lets only active tokens trade, see `Pallet::is_active`
+impl<T: Config> Filter<(T::ClassId, T::TokenId)> for Pallet<T> {
+	fn filter(token: &(T::ClassId, T::TokenId)) -> bool {
+		Self::is_active(*token)
+	}
+}
+
+/// Lets `opaque::SessionKeys` of the runtime generate the key signing attestations
+impl<T: Config> sp_runtime::BoundToRuntimeAppPublic for Pallet<T> {
+	type Public = crypto::Public;
+}
+
+impl<T: Config> Pallet<T> {
+	/// Decode `ipfs_cid_metadata`, the text form of a CIDv0 or CIDv1
+	fn ensure_valid_ipfs_cid(ipfs_cid_metadata: &ByteVector) -> Result<Cid, DispatchError> {
+		ensure!(
+			ipfs_cid_metadata.len() < MAX_IPFS_CID_CHAR_LENGTH,
+			Error::<T>::MaxIpfsCidCharLength
+		);
+		Cid::parse(ipfs_cid_metadata).map_err(|e| {
+			debug::error!("--- Invalid IPFS CID {:?}: {:?}", ipfs_cid_metadata, e);
+			Error::<T>::InvalidCid.into()
+		})
+	}
+
+	/// Ensure the duplicate policy of `class_id` accepts `content`, ignoring `except`
+	fn ensure_unique_content(
+		class_id: T::ClassId,
+		content: &[u8],
+		except: Option<(T::ClassId, T::TokenId)>,
+	) -> DispatchResult {
+		let duplicate = match DuplicatePolicies::<T>::get(class_id) {
+			DuplicatePolicy::Allow => false,
+			DuplicatePolicy::RejectInClass => matches!(
+				ContentIndex::<T>::get(content, class_id),
+				Some(token_id) if Some((class_id, token_id)) != except
+			),
+			// stops at the first entry other than `except`
+			DuplicatePolicy::RejectGlobal => {
+				ContentIndex::<T>::iter_prefix(content.to_vec()).any(|token| Some(token) != except)
+			}
+		};
+		ensure!(!duplicate, Error::<T>::DuplicateContent);
+		Ok(())
+	}
+
+	/// Multihash of the content of the current metadata of `token`
+	fn content_of(token: (T::ClassId, T::TokenId)) -> Option<ByteVector> {
+		BaseNft::<T>::tokens(token.0, token.1)
+			.and_then(|info| Cid::parse(&info.metadata).ok())
+			.map(|cid| cid.multihash())
+	}
+
+	/// Index `token` as carrying `content`, as the first token of its class unless another
+	/// one already carries it
+	pub(crate) fn index_content(token: (T::ClassId, T::TokenId), content: &[u8]) {
+		ContentTokens::<T>::insert((content.to_vec(), token.0), token.1, ());
+		if !ContentIndex::<T>::contains_key(content, token.0) {
+			ContentIndex::<T>::insert(content, token.0, token.1);
+		}
+	}
+
+	/// Remove `token` from the index of `content` it used to carry, another token of its class
+	/// carrying `content` takes its place in `ContentIndex`
+	fn unindex_content(token: (T::ClassId, T::TokenId), content: Option<ByteVector>) {
+		if let Some(content) = content {
+			let key = (content, token.0);
+			ContentTokens::<T>::remove(&key, token.1);
+			if ContentIndex::<T>::get(&key.0, token.0) == Some(token.1) {
+				match ContentTokens::<T>::iter_prefix(&key).next() {
+					Some((token_id, _)) => ContentIndex::<T>::insert(&key.0, token.0, token_id),
+					None => ContentIndex::<T>::remove(&key.0, token.0),
+				}
+			}
+		}
+	}
+
+	/// Queue the metadata of `token` for the offchain worker, its previous status is void
+	fn request_verification(token: (T::ClassId, T::TokenId)) {
+		MetadataStatuses::<T>::remove(token.0, token.1);
+		PendingVerifications::<T>::insert(token.0, token.1, ());
+	}
 
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+	fn clear_verification(token: (T::ClassId, T::TokenId)) {
+		MetadataStatuses::<T>::remove(token.0, token.1);
+		PendingVerifications::<T>::remove(token.0, token.1);
+	}
+
+	/// Metadata of `token` to fetch at `block_number`, none if an attestation was sent within
+	/// `ATTESTATION_RETRY_BLOCKS`
+	fn claim_verification(
+		token: (T::ClassId, T::TokenId),
+		block_number: T::BlockNumber,
+	) -> Option<VerificationOf<T>> {
+		let ipfs_cid = BaseNft::<T>::tokens(token.0, token.1)?.metadata;
+
+		let key = [b"nft::attestation::".as_ref(), &token.encode(), &ipfs_cid].concat();
+		let sent_at = StorageValueRef::persistent(&key);
+		let retry_after = block_number.saturating_sub(ATTESTATION_RETRY_BLOCKS.into());
+		let ready = sent_at.mutate(|last: Option<Option<T::BlockNumber>>| match last {
+			Some(Some(last)) if last > retry_after => Err(()),
+			_ => Ok(block_number),
+		});
+		match ready {
+			Ok(Ok(_)) => Some((token, ipfs_cid)),
+			_ => None,
+		}
+	}
+
+	/// Fetch the metadata `ipfs_cid` of `token` and submit its status
+	fn verify_metadata(
+		signer: &Signer<T, T::AuthorityId, frame_system::offchain::ForAny>,
+		token: (T::ClassId, T::TokenId),
+		ipfs_cid: ByteVector,
+	) -> Result<(), &'static str> {
+		let status = Self::fetch_metadata_status(&ipfs_cid);
+		let (_, result) = signer
+			.send_signed_transaction(|_| Call::submit_attestation(token, ipfs_cid.clone(), status))
+			.ok_or("No oracle key")?;
+		result.map_err(|_| "Attestation not submitted")
+	}
+
+	/// Status of the metadata `ipfs_cid` served by the IPFS gateway
+	fn fetch_metadata_status(ipfs_cid: &[u8]) -> MetadataStatus {
+		match Self::fetch_ipfs(ipfs_cid) {
+			Ok(body) if body.len() > T::MaxIpfsMetadataLength::get() as usize => {
+				debug::warn!(
+					"--- Metadata {:?} exceeds {} bytes",
+					ipfs_cid,
+					T::MaxIpfsMetadataLength::get()
+				);
+				MetadataStatus::Malformed
+			}
+			Ok(body) => match anmol_utils::metadata::validate(&body) {
+				Ok(()) => MetadataStatus::Verified,
+				Err(MetadataError::NotJson) | Err(MetadataError::NotAnObject) => {
+					MetadataStatus::Malformed
+				}
+				Err(e) => {
+					debug::warn!("--- Metadata {:?} is not standard: {:?}", ipfs_cid, e);
+					MetadataStatus::NonStandard
+				}
+			},
+			Err(e) => {
+				debug::warn!("--- IPFS fetch of {:?} failed: {:?}", ipfs_cid, e);
+				MetadataStatus::Unreachable
+			}
+		}
+	}
+
+	/// Body of `<gateway>/ipfs/<ipfs_cid>`, read up to one byte past `MaxIpfsMetadataLength`
+	fn fetch_ipfs(ipfs_cid: &[u8]) -> Result<Vec<u8>, http::Error> {
+		let gateway =
+			sp_io::offchain::local_storage_get(StorageKind::PERSISTENT, IPFS_GATEWAY_STORAGE_KEY)
+				.unwrap_or_else(|| T::IpfsGateway::get().as_bytes().to_vec());
+		let url = [gateway.as_slice(), b"/ipfs/", ipfs_cid].concat();
+		let url = sp_std::str::from_utf8(&url).map_err(|_| http::Error::Unknown)?;
+
+		let deadline =
+			sp_io::offchain::timestamp().add(Duration::from_millis(IPFS_FETCH_TIMEOUT_MS));
+		let pending = http::Request::get(url)
+			.deadline(deadline)
+			.send()
+			.map_err(|_| http::Error::IoError)?;
+		let response = pending
+			.try_wait(deadline)
+			.map_err(|_| http::Error::DeadlineReached)??;
+		if response.code != 200 {
+			return Err(http::Error::Unknown);
+		}
+
+		Ok(response
+			.body()
+			.take(T::MaxIpfsMetadataLength::get() as usize + 1)
+			.collect())
+	}
+
+	/// Tokens carrying the content of the CID `ipfs_cid`, the first of each class, at most
+	/// `limit` of them
+	pub fn tokens_by_content(ipfs_cid: &[u8], limit: u32) -> Vec<(T::ClassId, T::TokenId)> {
+		match Cid::parse(ipfs_cid) {
+			Ok(cid) => ContentIndex::<T>::iter_prefix(cid.multihash())
+				.take(limit as usize)
+				.collect(),
+			Err(_) => Vec::new(),
+		}
+	}
+
+	/// Whether `token` exists and, in a class with `StrictMetadata`, carries `Verified` metadata
+	pub fn is_active(token: (T::ClassId, T::TokenId)) -> bool {
+		base_nft::Tokens::<T>::contains_key(token.0, token.1)
+			&& (!StrictMetadata::<T>::get(token.0)
+				|| MetadataStatuses::<T>::get(token.0, token.1) == Some(MetadataStatus::Verified))
+	}
+
+	/// Whether `who` may mint into `class_id`
+	pub fn can_mint(who: &T::AccountId, class_id: T::ClassId) -> bool {
+		T::OpenMintClasses::get().contains(&class_id)
+			|| ClassMinters::<T>::get(class_id, who)
+			|| matches!(BaseNft::<T>::classes(class_id), Some(info) if info.owner == *who)
+	}
+
+	fn do_mint(
+		who: &T::AccountId,
+		class_id: T::ClassId,
+		ipfs_cid_metadata: ByteVector,
+	) -> Result<T::TokenId, DispatchError> {
+		let content = Self::ensure_valid_ipfs_cid(&ipfs_cid_metadata)?.multihash();
+
+		ensure!(
+			base_nft::Classes::<T>::contains_key(class_id),
+			base_nft::Error::<T>::ClassNotFound
+		);
+		ensure!(Self::can_mint(who, class_id), Error::<T>::NotClassMinter);
+		Self::ensure_unique_content(class_id, &content, None)?;
+
+		let token_id = BaseNft::<T>::mint(who, class_id, ipfs_cid_metadata, Default::default())?;
+		Self::index_content((class_id, token_id), &content);
+		Self::request_verification((class_id, token_id));
+		Ok(token_id)
+	}
+
+	/// Burn the token if the proposal is approved by enough shares, store the proposal otherwise
+	fn approve_or_execute_burn(
+		token: (T::ClassId, T::TokenId),
+		proposal: BurnProposal<T::AccountId>,
+	) -> DispatchResult {
+		if BaseNft::<T>::is_burn_approved(&proposal.approvals, token) {
+			let content = Self::content_of(token);
+			BaseNft::<T>::burn_with_approvals(&proposal.approvals, token)?;
+			Self::unindex_content(token, content);
+			Self::clear_verification(token);
+			BurnProposals::<T>::remove(token.0, token.1);
+			Self::deposit_event(Event::BurnProposalExecuted(token));
+		} else {
+			BurnProposals::<T>::insert(token.0, token.1, proposal);
+		}
+
+		Ok(())
+	}
 }
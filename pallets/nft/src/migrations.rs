@@ -0,0 +1,23 @@
+//! Storage migrations for the NFT module.
+
+use super::*;
+
+/// Index the tokens that existed before the upgrade in `ContentIndex` and `ContentTokens`.
+///
+/// Tokens whose metadata is not a CID carry no content and are skipped.
+pub fn migrate_to_v2<T: Config>() -> Weight {
+	let mut tokens: Weight = 0;
+	let mut indexed: Weight = 0;
+
+	base_nft::Tokens::<T>::iter().for_each(|(class_id, token_id, info)| {
+		tokens += 1;
+		if let Ok(cid) = Cid::parse(&info.metadata) {
+			indexed += 1;
+			Pallet::<T>::index_content((class_id, token_id), &cid.multihash());
+		}
+	});
+
+	StorageVersion::<T>::put(Releases::V2_0_0);
+
+	T::DbWeight::get().reads_writes(tokens + indexed + 1, indexed * 2 + 1)
+}
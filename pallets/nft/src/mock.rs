@@ -5,12 +5,13 @@ use frame_support::{debug, parameter_types};
 use frame_system::offchain::{
 	AppCrypto, CreateSignedTransaction, SendTransactionTypes, SigningTypes,
 };
+use frame_system::EnsureRoot;
 use sp_core::{crypto::AccountId32, H256};
 use sp_runtime::{
 	generic,
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup, SaturatedConversion},
-	MultiSignature,
+	MultiSignature, Perbill,
 };
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
@@ -23,14 +24,32 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Module, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
 		Nft: pallet_nft::{Module, Call, Storage, Event<T>},
-		BaseNft: base_nft::{Module, Storage},
+		BaseNft: base_nft::{Module, Storage, Event<T>},
 	}
 );
 
 parameter_types! {
 	pub const BlockHashCount: u64 = 250;
 	pub const SS58Prefix: u8 = 42;
+	pub const SharesPerToken: u64 = 100;
+	pub const BurnApprovalThreshold: Perbill = Perbill::from_percent(75);
+	pub const PublicIpfsClassId: u32 = 0;
+	pub OpenMintClasses: Vec<u32> = vec![PublicIpfsClassId::get()];
+	pub const MaxMintersPerClass: u32 = 2;
+	pub const MaxBatchSize: u32 = 3;
+	pub const IpfsGateway: &'static str = "http://127.0.0.1:8080";
+	pub const MaxVerificationsPerBlock: u32 = 2;
+	pub const MaxIpfsMetadataLength: u32 = 1024;
+	pub const ExistentialDeposit: u64 = 1;
+	pub const MaxMetadataLength: u32 = 256;
+	pub const MaxOwnersPerToken: u32 = 10;
+	pub const ClassDeposit: u64 = 0;
+	pub const TokenDeposit: u64 = 0;
+	pub const DepositPerByte: u64 = 0;
+	pub const MaxRoyaltyBeneficiaries: u32 = 3;
+	pub const MaxRoyalty: Perbill = Perbill::from_percent(50);
 }
 
 impl frame_system::Config for Runtime {
@@ -51,7 +70,7 @@ impl frame_system::Config for Runtime {
 	type BlockHashCount = BlockHashCount;
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
@@ -60,17 +79,49 @@ impl frame_system::Config for Runtime {
 
 pub type AccountId = AccountId32;
 
+impl pallet_balances::Config for Runtime {
+	type MaxLocks = ();
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
 impl pallet_nft::Config for Runtime {
 	type Call = Call;
 	type Event = Event;
 	type WeightInfo = pallet_nft::weights::SubstrateWeight<Runtime>;
+	type PublicIpfsClassId = PublicIpfsClassId;
+	type OpenMintClasses = OpenMintClasses;
+	type MaxMintersPerClass = MaxMintersPerClass;
+	type MaxBatchSize = MaxBatchSize;
+	type AuthorityId = pallet_nft::crypto::OracleAuthId;
+	type OracleOrigin = EnsureRoot<AccountId>;
+	type IpfsGateway = IpfsGateway;
+	type MaxVerificationsPerBlock = MaxVerificationsPerBlock;
+	type MaxIpfsMetadataLength = MaxIpfsMetadataLength;
 }
 
 impl base_nft::Config for Runtime {
+	type Event = Event;
 	type ClassId = u32;
 	type TokenId = u32;
 	type ClassData = ();
 	type TokenData = ();
+	type Shares = u64;
+	type SharesPerToken = SharesPerToken;
+	type BurnApprovalThreshold = BurnApprovalThreshold;
+	type Currency = Balances;
+	type MaxMetadataLength = MaxMetadataLength;
+	type MaxOwnersPerToken = MaxOwnersPerToken;
+	type ClassDeposit = ClassDeposit;
+	type TokenDeposit = TokenDeposit;
+	type DepositPerByte = DepositPerByte;
+	type MaxRoyaltyBeneficiaries = MaxRoyaltyBeneficiaries;
+	type MaxRoyalty = MaxRoyalty;
+	type OnTokenChange = ();
 }
 
 pub type SignedExtra = (
@@ -1,16 +1,52 @@
 #![cfg(test)]
 
 use crate::mock::{Event, *};
-use frame_support::{assert_noop, assert_ok};
+use crate::{DuplicatePolicy, MetadataStatus};
+use codec::{Decode, Encode};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{Currency, GenesisBuild, OffchainWorker, OnRuntimeUpgrade, ReservableCurrency},
+	weights::Pays,
+};
+use sp_core::offchain::{
+	testing::{PendingRequest, TestOffchainExt, TestTransactionPoolExt},
+	OffchainExt, TransactionPoolExt,
+};
+use sp_keystore::{testing::KeyStore, KeystoreExt, SyncCryptoStore};
+use sp_runtime::Perbill;
+use std::sync::Arc;
+
+type Extrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
 
 const ALICE: AccountId = AccountId::new([1u8; 32]);
+const BOB: AccountId = AccountId::new([2u8; 32]);
+const CHARLIE: AccountId = AccountId::new([3u8; 32]);
 const CLASS_ID_IPFS_NFT: <Runtime as base_nft::Config>::ClassId = 0;
 
+/// Base16 CIDv1 of a sha2-256 digest made of `n` bytes
+fn cid(n: u8) -> Vec<u8> {
+	let mut cid = b"f01701220".to_vec();
+	for _ in 0..32 {
+		cid.extend_from_slice(&[
+			b"0123456789abcdef"[(n >> 4) as usize],
+			b"0123456789abcdef"[(n & 0xf) as usize],
+		]);
+	}
+	cid
+}
+
+/// Sorted tokens carrying the content of `ipfs_cid`
+fn tokens_by_content(ipfs_cid: &[u8]) -> Vec<(u32, u32)> {
+	let mut tokens = Nft::tokens_by_content(ipfs_cid, 10);
+	tokens.sort();
+	tokens
+}
+
 #[test]
 fn mint_ipfs_nft_works() {
 	new_test_ext().execute_with(|| {
 		assert_noop!(
-			Nft::mint_ipfs_nft(Origin::signed(ALICE), vec![0, 1, 2]),
+			Nft::mint_ipfs_nft(Origin::signed(ALICE), cid(0)),
 			base_nft::Error::<Runtime>::ClassNotFound,
 		);
 
@@ -19,12 +55,12 @@ fn mint_ipfs_nft_works() {
 			Nft::create_nft_class(Origin::signed(ALICE), too_long_ipfs_cid.clone()),
 			crate::Error::<Runtime>::MaxIpfsCidCharLength
 		);
-		assert_ok!(Nft::create_nft_class(Origin::signed(ALICE), vec![1]));
+		assert_ok!(Nft::create_nft_class(Origin::signed(ALICE), cid(1)));
 
 		let event = Event::pallet_nft(crate::Event::NftClassCreated(
 			ALICE,
 			CLASS_ID_IPFS_NFT,
-			vec![1],
+			cid(1),
 		));
 		assert_eq!(last_event(), event);
 
@@ -32,9 +68,944 @@ fn mint_ipfs_nft_works() {
 			Nft::mint_ipfs_nft(Origin::signed(ALICE), too_long_ipfs_cid),
 			crate::Error::<Runtime>::MaxIpfsCidCharLength
 		);
-		assert_ok!(Nft::mint_ipfs_nft(Origin::signed(ALICE), vec![0, 1, 2]),);
+		assert_ok!(Nft::mint_ipfs_nft(Origin::signed(ALICE), cid(0)),);
 
-		let event = Event::pallet_nft(crate::Event::IpfsNftMinted(ALICE, 0, vec![0, 1, 2]));
+		let event = Event::pallet_nft(crate::Event::IpfsNftMinted(ALICE, 0, cid(0)));
 		assert_eq!(last_event(), event);
 	});
 }
+
+#[test]
+fn transfer_requires_owner_or_approval() {
+	new_test_ext().execute_with(|| {
+		let token = (CLASS_ID_IPFS_NFT, 0);
+		assert_ok!(Nft::create_nft_class(Origin::signed(ALICE), cid(1)));
+		assert_ok!(Nft::mint_ipfs_nft(Origin::signed(ALICE), cid(0)));
+
+		assert_noop!(
+			Nft::transfer(Origin::signed(BOB), ALICE, BOB, token, 10),
+			crate::Error::<Runtime>::NotOwnerOrApproved
+		);
+
+		assert_ok!(Nft::approve(Origin::signed(ALICE), token, BOB));
+		assert_eq!(
+			last_event(),
+			Event::base_nft(base_nft::Event::Approved(ALICE, token, BOB))
+		);
+		assert_ok!(Nft::transfer(
+			Origin::signed(BOB),
+			ALICE,
+			CHARLIE,
+			token,
+			10
+		));
+
+		assert_ok!(Nft::cancel_approval(Origin::signed(ALICE), token));
+		assert_eq!(
+			last_event(),
+			Event::base_nft(base_nft::Event::ApprovalCancelled(ALICE, token))
+		);
+		assert_noop!(
+			Nft::transfer(Origin::signed(BOB), ALICE, BOB, token, 10),
+			crate::Error::<Runtime>::NotOwnerOrApproved
+		);
+
+		assert_ok!(Nft::set_operator(Origin::signed(ALICE), BOB, true));
+		assert_eq!(
+			last_event(),
+			Event::base_nft(base_nft::Event::OperatorSet(ALICE, BOB, true))
+		);
+		assert_ok!(Nft::transfer(Origin::signed(BOB), ALICE, BOB, token, 90));
+		assert!(!base_nft::Module::<Runtime>::is_owner(&ALICE, token));
+	});
+}
+
+#[test]
+fn burn_proposal_works() {
+	new_test_ext().execute_with(|| {
+		let token = (CLASS_ID_IPFS_NFT, 0);
+		assert_ok!(Nft::create_nft_class(Origin::signed(ALICE), cid(1)));
+		assert_ok!(Nft::mint_ipfs_nft(Origin::signed(ALICE), cid(0)));
+		assert_ok!(Nft::transfer(Origin::signed(ALICE), ALICE, BOB, token, 30));
+		assert_ok!(Nft::transfer(
+			Origin::signed(ALICE),
+			ALICE,
+			CHARLIE,
+			token,
+			20
+		));
+
+		assert_noop!(
+			Nft::approve_burn(Origin::signed(BOB), token),
+			crate::Error::<Runtime>::BurnProposalNotFound
+		);
+
+		assert_ok!(Nft::propose_burn(Origin::signed(ALICE), token));
+		assert!(Nft::burn_proposals(token.0, token.1).is_some());
+		assert_noop!(
+			Nft::propose_burn(Origin::signed(BOB), token),
+			crate::Error::<Runtime>::BurnProposalExists
+		);
+		assert_noop!(
+			Nft::approve_burn(Origin::signed(ALICE), token),
+			crate::Error::<Runtime>::BurnAlreadyApproved
+		);
+
+		// 50% + 30% of the shares reach the 75% threshold
+		assert_ok!(Nft::approve_burn(Origin::signed(BOB), token));
+		assert_eq!(
+			last_event(),
+			Event::pallet_nft(crate::Event::BurnProposalExecuted(token))
+		);
+		assert!(Nft::burn_proposals(token.0, token.1).is_none());
+		assert!(base_nft::Module::<Runtime>::tokens(token.0, token.1).is_none());
+		assert!(!base_nft::Module::<Runtime>::is_owner(&CHARLIE, token));
+	});
+}
+
+#[test]
+fn cancel_burn_proposal_works() {
+	new_test_ext().execute_with(|| {
+		let token = (CLASS_ID_IPFS_NFT, 0);
+		assert_ok!(Nft::create_nft_class(Origin::signed(ALICE), cid(1)));
+		assert_ok!(Nft::mint_ipfs_nft(Origin::signed(ALICE), cid(0)));
+		assert_ok!(Nft::transfer(Origin::signed(ALICE), ALICE, BOB, token, 50));
+
+		assert_noop!(
+			Nft::propose_burn(Origin::signed(CHARLIE), token),
+			crate::Error::<Runtime>::NotTokenOwner
+		);
+		assert_ok!(Nft::propose_burn(Origin::signed(BOB), token));
+		assert_noop!(
+			Nft::cancel_burn_proposal(Origin::signed(ALICE), token),
+			crate::Error::<Runtime>::NotBurnProposer
+		);
+		assert_ok!(Nft::cancel_burn_proposal(Origin::signed(BOB), token));
+		assert!(Nft::burn_proposals(token.0, token.1).is_none());
+	});
+}
+
+#[test]
+fn burn_and_destroy_class_works() {
+	new_test_ext().execute_with(|| {
+		let token = (CLASS_ID_IPFS_NFT, 0);
+		assert_ok!(Nft::create_nft_class(Origin::signed(ALICE), cid(1)));
+		assert_ok!(Nft::mint_ipfs_nft(Origin::signed(ALICE), cid(0)));
+		assert_ok!(Nft::set_class_minter(
+			Origin::signed(ALICE),
+			CLASS_ID_IPFS_NFT,
+			BOB,
+			true
+		));
+
+		assert_noop!(
+			Nft::destroy_class(Origin::signed(ALICE), CLASS_ID_IPFS_NFT),
+			base_nft::Error::<Runtime>::CannotDestroyClass
+		);
+		assert_noop!(
+			Nft::burn(Origin::signed(BOB), token),
+			base_nft::Error::<Runtime>::NoPermission
+		);
+
+		assert_ok!(Nft::burn(Origin::signed(ALICE), token));
+		assert_eq!(
+			last_event(),
+			Event::pallet_nft(crate::Event::NftBurned(ALICE, token))
+		);
+
+		assert_ok!(Nft::destroy_class(Origin::signed(ALICE), CLASS_ID_IPFS_NFT));
+		assert_eq!(
+			last_event(),
+			Event::pallet_nft(crate::Event::NftClassDestroyed(ALICE, CLASS_ID_IPFS_NFT))
+		);
+		// minters of a destroyed class do not carry over to a class reusing its id
+		assert!(!Nft::class_minters(CLASS_ID_IPFS_NFT, BOB));
+		assert_eq!(Nft::class_minter_count(CLASS_ID_IPFS_NFT), 0);
+	});
+}
+
+#[test]
+fn class_minters_are_bounded() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Nft::create_nft_class(Origin::signed(ALICE), cid(1)));
+		for minter in [BOB, CHARLIE].iter() {
+			assert_ok!(Nft::set_class_minter(
+				Origin::signed(ALICE),
+				CLASS_ID_IPFS_NFT,
+				minter.clone(),
+				true
+			));
+		}
+		// authorizing a minter again does not count it twice
+		assert_ok!(Nft::set_class_minter(
+			Origin::signed(ALICE),
+			CLASS_ID_IPFS_NFT,
+			BOB,
+			true
+		));
+		assert_eq!(Nft::class_minter_count(CLASS_ID_IPFS_NFT), 2);
+
+		assert_noop!(
+			Nft::set_class_minter(Origin::signed(ALICE), CLASS_ID_IPFS_NFT, ALICE, true),
+			crate::Error::<Runtime>::TooManyMinters
+		);
+
+		assert_ok!(Nft::set_class_minter(
+			Origin::signed(ALICE),
+			CLASS_ID_IPFS_NFT,
+			BOB,
+			false
+		));
+		assert_ok!(Nft::set_class_minter(
+			Origin::signed(ALICE),
+			CLASS_ID_IPFS_NFT,
+			BOB,
+			false
+		));
+		assert_eq!(Nft::class_minter_count(CLASS_ID_IPFS_NFT), 1);
+		assert_ok!(Nft::set_class_minter(
+			Origin::signed(ALICE),
+			CLASS_ID_IPFS_NFT,
+			ALICE,
+			true
+		));
+	});
+}
+
+#[test]
+fn set_metadata_works() {
+	new_test_ext().execute_with(|| {
+		let token = (CLASS_ID_IPFS_NFT, 0);
+		let too_long_ipfs_cid = vec![1_u8; crate::MAX_IPFS_CID_CHAR_LENGTH];
+		assert_ok!(Nft::create_nft_class(Origin::signed(ALICE), cid(1)));
+		assert_ok!(Nft::mint_ipfs_nft(Origin::signed(ALICE), cid(0)));
+
+		assert_noop!(
+			Nft::set_class_metadata(
+				Origin::signed(ALICE),
+				CLASS_ID_IPFS_NFT,
+				too_long_ipfs_cid.clone()
+			),
+			crate::Error::<Runtime>::MaxIpfsCidCharLength
+		);
+		assert_ok!(Nft::set_class_metadata(
+			Origin::signed(ALICE),
+			CLASS_ID_IPFS_NFT,
+			cid(2)
+		));
+		assert_eq!(
+			last_event(),
+			Event::pallet_nft(crate::Event::NftClassMetadataUpdated(
+				ALICE,
+				CLASS_ID_IPFS_NFT,
+				cid(2)
+			))
+		);
+
+		assert_noop!(
+			Nft::set_token_metadata(Origin::signed(ALICE), token, too_long_ipfs_cid),
+			crate::Error::<Runtime>::MaxIpfsCidCharLength
+		);
+		assert_ok!(Nft::set_token_metadata(
+			Origin::signed(ALICE),
+			token,
+			cid(3)
+		));
+		assert_eq!(
+			last_event(),
+			Event::pallet_nft(crate::Event::NftMetadataUpdated(ALICE, token, cid(3)))
+		);
+	});
+}
+
+#[test]
+fn mint_into_public_class_is_open() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Nft::create_nft_class(Origin::signed(ALICE), cid(1)));
+		assert_ok!(Nft::mint_ipfs_nft(Origin::signed(BOB), cid(0)));
+		assert_ok!(Nft::mint(
+			Origin::signed(CHARLIE),
+			CLASS_ID_IPFS_NFT,
+			cid(0)
+		));
+		assert_eq!(
+			last_event(),
+			Event::pallet_nft(crate::Event::NftMinted(
+				CHARLIE,
+				(CLASS_ID_IPFS_NFT, 1),
+				cid(0)
+			))
+		);
+	});
+}
+
+#[test]
+fn mint_requires_class_owner_or_minter() {
+	new_test_ext().execute_with(|| {
+		let class_id = CLASS_ID_IPFS_NFT + 1;
+		assert_ok!(Nft::create_nft_class(Origin::signed(ALICE), cid(1)));
+		assert_ok!(Nft::create_nft_class(Origin::signed(ALICE), cid(1)));
+
+		assert_noop!(
+			Nft::mint(Origin::signed(BOB), class_id + 1, cid(0)),
+			base_nft::Error::<Runtime>::ClassNotFound
+		);
+		assert_noop!(
+			Nft::mint(Origin::signed(BOB), class_id, cid(0)),
+			crate::Error::<Runtime>::NotClassMinter
+		);
+		assert_ok!(Nft::mint(Origin::signed(ALICE), class_id, cid(0)));
+
+		assert_noop!(
+			Nft::set_class_minter(Origin::signed(BOB), class_id, BOB, true),
+			crate::Error::<Runtime>::NotClassOwner
+		);
+		assert_ok!(Nft::set_class_minter(
+			Origin::signed(ALICE),
+			class_id,
+			BOB,
+			true
+		));
+		assert_ok!(Nft::mint(Origin::signed(BOB), class_id, cid(0)));
+
+		assert_ok!(Nft::set_class_minter(
+			Origin::signed(ALICE),
+			class_id,
+			BOB,
+			false
+		));
+		assert_noop!(
+			Nft::mint(Origin::signed(BOB), class_id, cid(0)),
+			crate::Error::<Runtime>::NotClassMinter
+		);
+	});
+}
+
+#[test]
+fn set_royalty_works() {
+	new_test_ext().execute_with(|| {
+		let token = (CLASS_ID_IPFS_NFT, 0);
+		assert_ok!(Nft::create_nft_class(Origin::signed(ALICE), cid(1)));
+		assert_ok!(Nft::mint_ipfs_nft(Origin::signed(BOB), cid(0)));
+
+		let royalty = vec![(ALICE, Perbill::from_percent(10))];
+		assert_noop!(
+			Nft::set_class_royalty(Origin::signed(BOB), CLASS_ID_IPFS_NFT, royalty.clone()),
+			base_nft::Error::<Runtime>::NoPermission
+		);
+		assert_ok!(Nft::set_class_royalty(
+			Origin::signed(ALICE),
+			CLASS_ID_IPFS_NFT,
+			royalty.clone()
+		));
+		assert_eq!(BaseNft::royalty_of(token), royalty);
+		assert_eq!(
+			last_event(),
+			Event::pallet_nft(crate::Event::ClassRoyaltySet(ALICE, CLASS_ID_IPFS_NFT))
+		);
+
+		let token_royalty = vec![(CHARLIE, Perbill::from_percent(5))];
+		assert_noop!(
+			Nft::set_token_royalty(Origin::signed(BOB), token, Some(token_royalty.clone())),
+			base_nft::Error::<Runtime>::NoPermission
+		);
+		assert_ok!(Nft::set_token_royalty(
+			Origin::signed(ALICE),
+			token,
+			Some(token_royalty.clone())
+		));
+		assert_eq!(BaseNft::royalty_of(token), token_royalty);
+		assert_eq!(
+			last_event(),
+			Event::pallet_nft(crate::Event::TokenRoyaltySet(ALICE, token))
+		);
+
+		assert_ok!(Nft::set_token_royalty(Origin::signed(ALICE), token, None));
+		assert_eq!(BaseNft::royalty_of(token), royalty);
+	});
+}
+
+#[test]
+fn claim_proceeds_works() {
+	new_test_ext().execute_with(|| {
+		let token = (CLASS_ID_IPFS_NFT, 0);
+		Balances::make_free_balance_be(&ALICE, 100);
+		assert_ok!(Balances::reserve(&ALICE, 50));
+
+		// BOB has no account to be paid into
+		BaseNft::repatriate_proceeds(&ALICE, &BOB, token, 50);
+		assert_eq!(BaseNft::unclaimed_proceeds(BOB, ALICE), 50);
+		assert_noop!(
+			Nft::claim_proceeds(Origin::signed(BOB), ALICE),
+			pallet_balances::Error::<Runtime>::DeadAccount
+		);
+
+		Balances::make_free_balance_be(&BOB, 1);
+		assert_ok!(Nft::claim_proceeds(Origin::signed(BOB), ALICE));
+		assert_eq!(Balances::free_balance(BOB), 51);
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_eq!(
+			last_event(),
+			Event::pallet_nft(crate::Event::ProceedsClaimed(BOB, ALICE))
+		);
+		assert_noop!(
+			Nft::claim_proceeds(Origin::signed(BOB), ALICE),
+			base_nft::Error::<Runtime>::NoUnclaimedProceeds
+		);
+	});
+}
+
+#[test]
+fn batch_mint_works() {
+	new_test_ext().execute_with(|| {
+		let class_id = CLASS_ID_IPFS_NFT + 1;
+		assert_ok!(Nft::create_nft_class(Origin::signed(ALICE), cid(1)));
+		assert_ok!(Nft::create_nft_class(Origin::signed(ALICE), cid(1)));
+
+		assert_noop!(
+			Nft::batch_mint(Origin::signed(ALICE), class_id, vec![(cid(1), BOB); 4]),
+			crate::Error::<Runtime>::BatchTooLarge
+		);
+		assert_noop!(
+			Nft::batch_mint(Origin::signed(BOB), class_id, vec![(cid(1), BOB)]),
+			crate::Error::<Runtime>::NotClassMinter
+		);
+		// one invalid CID reverts the whole batch
+		assert_noop!(
+			Nft::batch_mint(
+				Origin::signed(ALICE),
+				class_id,
+				vec![
+					(cid(1), BOB),
+					(vec![1_u8; crate::MAX_IPFS_CID_CHAR_LENGTH], BOB)
+				]
+			),
+			crate::Error::<Runtime>::MaxIpfsCidCharLength
+		);
+
+		assert_ok!(Nft::batch_mint(
+			Origin::signed(ALICE),
+			class_id,
+			vec![(cid(1), BOB), (cid(2), CHARLIE), (cid(3), ALICE)]
+		));
+		assert_eq!(BaseNft::shares_of(&BOB, (class_id, 0)), 100);
+		assert_eq!(BaseNft::shares_of(&CHARLIE, (class_id, 1)), 100);
+		assert_eq!(BaseNft::shares_of(&ALICE, (class_id, 2)), 100);
+		assert_eq!(BaseNft::shares_of(&ALICE, (class_id, 0)), 0);
+		assert_eq!(
+			last_event(),
+			Event::pallet_nft(crate::Event::NftMinted(ALICE, (class_id, 2), cid(3)))
+		);
+	});
+}
+
+#[test]
+fn batch_transfer_works() {
+	new_test_ext().execute_with(|| {
+		let token_a = (CLASS_ID_IPFS_NFT, 0);
+		let token_b = (CLASS_ID_IPFS_NFT, 1);
+		assert_ok!(Nft::create_nft_class(Origin::signed(ALICE), cid(1)));
+		assert_ok!(Nft::mint_ipfs_nft(Origin::signed(ALICE), cid(0)));
+		assert_ok!(Nft::mint_ipfs_nft(Origin::signed(ALICE), cid(0)));
+
+		assert_noop!(
+			Nft::batch_transfer(Origin::signed(ALICE), vec![(token_a, BOB, 1); 4]),
+			crate::Error::<Runtime>::BatchTooLarge
+		);
+		// the second transfer exceeds the shares left, nothing is transferred
+		assert_noop!(
+			Nft::batch_transfer(
+				Origin::signed(ALICE),
+				vec![(token_a, BOB, 60), (token_a, CHARLIE, 60)]
+			),
+			base_nft::Error::<Runtime>::SenderInsufficientPercentage
+		);
+
+		assert_ok!(Nft::batch_transfer(
+			Origin::signed(ALICE),
+			vec![
+				(token_a, BOB, 60),
+				(token_a, CHARLIE, 40),
+				(token_b, BOB, 25)
+			]
+		));
+		assert_eq!(BaseNft::shares_of(&ALICE, token_a), 0);
+		assert_eq!(BaseNft::shares_of(&BOB, token_a), 60);
+		assert_eq!(BaseNft::shares_of(&CHARLIE, token_a), 40);
+		assert_eq!(BaseNft::shares_of(&ALICE, token_b), 75);
+		assert_eq!(BaseNft::shares_of(&BOB, token_b), 25);
+	});
+}
+
+#[test]
+fn malformed_cids_are_rejected() {
+	new_test_ext().execute_with(|| {
+		let v0 = b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG".to_vec();
+		let v1 = b"bafybeie5nqv6kd3qnfjupgvz34woh3oksc3iau6abmyajn7qvtf6d2ho34".to_vec();
+		assert_ok!(Nft::create_nft_class(Origin::signed(ALICE), v0));
+		assert_ok!(Nft::mint_ipfs_nft(Origin::signed(ALICE), v1));
+
+		for invalid in [
+			vec![],
+			vec![0, 1, 2],
+			b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbd0".to_vec(),
+			b"bafybeie5nqv6kd3qnfjupgvz34woh3oksc3iau6abmyajn7qvtf6d2ho3".to_vec(),
+		]
+		.iter()
+		{
+			assert_noop!(
+				Nft::create_nft_class(Origin::signed(ALICE), invalid.clone()),
+				crate::Error::<Runtime>::InvalidCid
+			);
+			assert_noop!(
+				Nft::mint_ipfs_nft(Origin::signed(ALICE), invalid.clone()),
+				crate::Error::<Runtime>::InvalidCid
+			);
+		}
+	});
+}
+
+#[test]
+fn content_index_works() {
+	new_test_ext().execute_with(|| {
+		let v0 = b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG".to_vec();
+		let v1 = b"bafybeie5nqv6kd3qnfjupgvz34woh3oksc3iau6abmyajn7qvtf6d2ho34".to_vec();
+		let class_id = CLASS_ID_IPFS_NFT + 1;
+		assert_ok!(Nft::create_nft_class(Origin::signed(ALICE), cid(1)));
+		assert_ok!(Nft::create_nft_class(Origin::signed(ALICE), cid(1)));
+
+		assert_ok!(Nft::mint_ipfs_nft(Origin::signed(ALICE), v0.clone()));
+		// duplicates are allowed by default and only the first token of a class is indexed
+		assert_ok!(Nft::mint_ipfs_nft(Origin::signed(BOB), v1.clone()));
+		assert_ok!(Nft::mint(Origin::signed(ALICE), class_id, v1.clone()));
+		assert_eq!(
+			tokens_by_content(&v1),
+			vec![(CLASS_ID_IPFS_NFT, 0), (class_id, 0)]
+		);
+		assert_eq!(tokens_by_content(&cid(2)), vec![]);
+		assert_eq!(Nft::tokens_by_content(&[0, 1, 2], 10), vec![]);
+
+		// the token of BOB carrying the same content takes the place of the burnt one
+		assert_ok!(Nft::burn(Origin::signed(ALICE), (CLASS_ID_IPFS_NFT, 0)));
+		assert_eq!(
+			tokens_by_content(&v0),
+			vec![(CLASS_ID_IPFS_NFT, 1), (class_id, 0)]
+		);
+
+		assert_ok!(Nft::set_token_metadata(
+			Origin::signed(ALICE),
+			(class_id, 0),
+			cid(2)
+		));
+		assert_eq!(tokens_by_content(&v0), vec![(CLASS_ID_IPFS_NFT, 1)]);
+		assert_eq!(tokens_by_content(&cid(2)), vec![(class_id, 0)]);
+	});
+}
+
+#[test]
+fn content_index_keeps_surviving_tokens() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Nft::create_nft_class(Origin::signed(ALICE), cid(1)));
+		assert_ok!(Nft::mint_ipfs_nft(Origin::signed(ALICE), cid(2)));
+		assert_ok!(Nft::mint_ipfs_nft(Origin::signed(BOB), cid(2)));
+		assert_ok!(Nft::mint_ipfs_nft(Origin::signed(BOB), cid(2)));
+		assert_eq!(tokens_by_content(&cid(2)), vec![(CLASS_ID_IPFS_NFT, 0)]);
+
+		// another token of the class takes the place of the burnt one
+		assert_ok!(Nft::burn(Origin::signed(ALICE), (CLASS_ID_IPFS_NFT, 0)));
+		let survivor = tokens_by_content(&cid(2));
+		assert_eq!(survivor.len(), 1);
+		assert!(survivor[0] != (CLASS_ID_IPFS_NFT, 0));
+
+		assert_ok!(Nft::set_token_metadata(
+			Origin::signed(BOB),
+			survivor[0],
+			cid(3)
+		));
+		let last = (CLASS_ID_IPFS_NFT, 3 - survivor[0].1);
+		assert_eq!(tokens_by_content(&cid(2)), vec![last]);
+		assert_eq!(tokens_by_content(&cid(3)), vec![survivor[0]]);
+
+		assert_ok!(Nft::burn(Origin::signed(BOB), last));
+		assert_eq!(tokens_by_content(&cid(2)), vec![]);
+	});
+}
+
+#[test]
+fn migrate_to_v2_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Nft::create_nft_class(Origin::signed(ALICE), cid(1)));
+		// tokens minted before the upgrade are not indexed
+		assert_ok!(BaseNft::mint(&ALICE, CLASS_ID_IPFS_NFT, cid(2), ()));
+		assert_ok!(BaseNft::mint(&BOB, CLASS_ID_IPFS_NFT, cid(2), ()));
+		assert_ok!(BaseNft::mint(
+			&BOB,
+			CLASS_ID_IPFS_NFT,
+			b"not a cid".to_vec(),
+			()
+		));
+		crate::StorageVersion::<Runtime>::kill();
+		assert_eq!(tokens_by_content(&cid(2)), vec![]);
+
+		Nft::on_runtime_upgrade();
+
+		assert_eq!(
+			crate::StorageVersion::<Runtime>::get(),
+			crate::Releases::V2_0_0
+		);
+		assert_eq!(tokens_by_content(&cid(2)).len(), 1);
+		assert_ok!(Nft::burn(Origin::signed(ALICE), (CLASS_ID_IPFS_NFT, 0)));
+		assert_eq!(tokens_by_content(&cid(2)), vec![(CLASS_ID_IPFS_NFT, 1)]);
+	});
+}
+
+#[test]
+fn genesis_indexes_and_queues_base_nft_tokens() {
+	let mut storage = frame_system::GenesisConfig::default()
+		.build_storage::<Runtime>()
+		.unwrap();
+	base_nft::GenesisConfig::<Runtime> {
+		tokens: vec![(
+			ALICE,
+			cid(1),
+			(),
+			vec![(ALICE, cid(2), ()), (BOB, b"not a cid".to_vec(), ())],
+		)],
+	}
+	.assimilate_storage(&mut storage)
+	.unwrap();
+	crate::GenesisConfig::<Runtime>::default()
+		.assimilate_storage(&mut storage)
+		.unwrap();
+
+	sp_io::TestExternalities::new(storage).execute_with(|| {
+		assert_eq!(
+			crate::StorageVersion::<Runtime>::get(),
+			crate::Releases::V2_0_0
+		);
+		assert_eq!(tokens_by_content(&cid(2)), vec![(CLASS_ID_IPFS_NFT, 0)]);
+		assert_eq!(
+			crate::PendingVerifications::<Runtime>::iter()
+				.map(|(class_id, token_id, _)| (class_id, token_id))
+				.collect::<Vec<_>>(),
+			vec![(CLASS_ID_IPFS_NFT, 0)]
+		);
+	});
+}
+
+#[test]
+fn duplicate_policy_works() {
+	new_test_ext().execute_with(|| {
+		let v0 = b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG".to_vec();
+		let v1 = b"bafybeie5nqv6kd3qnfjupgvz34woh3oksc3iau6abmyajn7qvtf6d2ho34".to_vec();
+		let class_id = CLASS_ID_IPFS_NFT + 1;
+		assert_ok!(Nft::create_nft_class(Origin::signed(ALICE), cid(1)));
+		assert_ok!(Nft::create_nft_class(Origin::signed(ALICE), cid(1)));
+
+		assert_noop!(
+			Nft::set_duplicate_policy(Origin::signed(BOB), class_id, DuplicatePolicy::RejectGlobal),
+			crate::Error::<Runtime>::NotClassOwner
+		);
+		assert_ok!(Nft::set_duplicate_policy(
+			Origin::signed(ALICE),
+			class_id,
+			DuplicatePolicy::RejectInClass
+		));
+		assert_eq!(
+			last_event(),
+			Event::pallet_nft(crate::Event::DuplicatePolicySet(
+				ALICE,
+				class_id,
+				DuplicatePolicy::RejectInClass
+			))
+		);
+
+		assert_ok!(Nft::mint_ipfs_nft(Origin::signed(ALICE), v0.clone()));
+		assert_ok!(Nft::mint(Origin::signed(ALICE), class_id, v0.clone()));
+		assert_noop!(
+			Nft::mint(Origin::signed(ALICE), class_id, v1.clone()),
+			crate::Error::<Runtime>::DuplicateContent
+		);
+		assert_ok!(Nft::mint(Origin::signed(ALICE), class_id, cid(2)));
+		assert_noop!(
+			Nft::set_token_metadata(Origin::signed(ALICE), (class_id, 1), v1.clone()),
+			crate::Error::<Runtime>::DuplicateContent
+		);
+		// a token may keep its own content
+		assert_ok!(Nft::set_token_metadata(
+			Origin::signed(ALICE),
+			(class_id, 0),
+			v1.clone()
+		));
+
+		assert_ok!(Nft::set_duplicate_policy(
+			Origin::signed(ALICE),
+			class_id,
+			DuplicatePolicy::RejectGlobal
+		));
+		assert_ok!(Nft::mint_ipfs_nft(Origin::signed(ALICE), cid(3)));
+		assert_noop!(
+			Nft::mint(Origin::signed(ALICE), class_id, cid(3)),
+			crate::Error::<Runtime>::DuplicateContent
+		);
+		assert_noop!(
+			Nft::batch_mint(
+				Origin::signed(ALICE),
+				class_id,
+				vec![(cid(4), BOB), (cid(4), BOB)]
+			),
+			crate::Error::<Runtime>::DuplicateContent
+		);
+
+		// burning the only other token frees the content
+		assert_ok!(Nft::burn(Origin::signed(ALICE), (CLASS_ID_IPFS_NFT, 1)));
+		assert_ok!(Nft::mint(Origin::signed(ALICE), class_id, cid(3)));
+	});
+}
+
+#[test]
+fn submit_attestation_works() {
+	new_test_ext().execute_with(|| {
+		let token = (CLASS_ID_IPFS_NFT, 0);
+		assert_ok!(Nft::create_nft_class(Origin::signed(ALICE), cid(1)));
+		assert_ok!(Nft::mint_ipfs_nft(Origin::signed(ALICE), cid(0)));
+		assert_eq!(Nft::pending_verifications(token.0, token.1), Some(()));
+
+		assert_noop!(
+			Nft::add_oracle(Origin::signed(ALICE), CHARLIE),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_ok!(Nft::add_oracle(Origin::root(), CHARLIE));
+		assert_eq!(
+			last_event(),
+			Event::pallet_nft(crate::Event::OracleAdded(CHARLIE))
+		);
+		assert_ok!(Nft::add_oracle(Origin::root(), BOB));
+		assert_eq!(Nft::oracles(), vec![BOB, CHARLIE]);
+		assert_noop!(
+			Nft::add_oracle(Origin::root(), BOB),
+			crate::Error::<Runtime>::OracleExists
+		);
+
+		assert_noop!(
+			Nft::submit_attestation(
+				Origin::signed(ALICE),
+				token,
+				cid(0),
+				MetadataStatus::Verified
+			),
+			crate::Error::<Runtime>::NotOracle
+		);
+		assert_noop!(
+			Nft::submit_attestation(Origin::signed(BOB), token, cid(2), MetadataStatus::Verified),
+			crate::Error::<Runtime>::StaleAttestation
+		);
+		let info =
+			Nft::submit_attestation(Origin::signed(BOB), token, cid(0), MetadataStatus::Verified)
+				.unwrap();
+		assert_eq!(info.pays_fee, Pays::No);
+		assert_eq!(
+			last_event(),
+			Event::pallet_nft(crate::Event::MetadataAttested(
+				BOB,
+				token,
+				MetadataStatus::Verified
+			))
+		);
+		assert_eq!(
+			Nft::metadata_status(token.0, token.1),
+			Some(MetadataStatus::Verified)
+		);
+		assert_noop!(
+			Nft::submit_attestation(
+				Origin::signed(BOB),
+				token,
+				cid(0),
+				MetadataStatus::Malformed
+			),
+			crate::Error::<Runtime>::StaleAttestation
+		);
+
+		// new metadata voids the status
+		assert_ok!(Nft::set_token_metadata(
+			Origin::signed(ALICE),
+			token,
+			cid(3)
+		));
+		assert_eq!(Nft::metadata_status(token.0, token.1), None);
+		assert_eq!(Nft::pending_verifications(token.0, token.1), Some(()));
+
+		assert_ok!(Nft::remove_oracle(Origin::root(), BOB));
+		assert_eq!(Nft::oracles(), vec![CHARLIE]);
+		assert_noop!(
+			Nft::remove_oracle(Origin::root(), BOB),
+			crate::Error::<Runtime>::NotOracle
+		);
+		assert_noop!(
+			Nft::submit_attestation(Origin::signed(BOB), token, cid(3), MetadataStatus::Verified),
+			crate::Error::<Runtime>::NotOracle
+		);
+
+		assert_ok!(Nft::burn(Origin::signed(ALICE), token));
+		assert_eq!(Nft::pending_verifications(token.0, token.1), None);
+	});
+}
+
+#[test]
+fn offchain_worker_submits_attestations() {
+	let (offchain, offchain_state) = TestOffchainExt::new();
+	let (pool, pool_state) = TestTransactionPoolExt::new();
+	let keystore = KeyStore::new();
+	let oracle: AccountId = SyncCryptoStore::sr25519_generate_new(&keystore, crate::KEY_TYPE, None)
+		.unwrap()
+		.into();
+
+	let mut ext = new_test_ext();
+	ext.register_extension(OffchainExt::new(offchain));
+	ext.register_extension(TransactionPoolExt::new(pool));
+	ext.register_extension(KeystoreExt(Arc::new(keystore)));
+
+	ext.execute_with(|| {
+		let token = (CLASS_ID_IPFS_NFT, 0);
+		assert_ok!(Nft::create_nft_class(Origin::signed(ALICE), cid(1)));
+		assert_ok!(Nft::mint_ipfs_nft(Origin::signed(ALICE), cid(0)));
+
+		offchain_state.write().expect_request(PendingRequest {
+			method: "GET".into(),
+			uri: format!(
+				"http://127.0.0.1:8080/ipfs/{}",
+				String::from_utf8(cid(0)).unwrap()
+			),
+			response: Some(br#"{"name": "Anmol", "image": "ipfs://anmol.png"}"#.to_vec()),
+			sent: true,
+			..Default::default()
+		});
+		Nft::offchain_worker(1);
+
+		let tx = pool_state.write().transactions.pop().unwrap();
+		let tx = Extrinsic::decode(&mut &*tx).unwrap();
+		assert_eq!(tx.signature.unwrap().0, oracle);
+		assert_eq!(
+			tx.function,
+			Call::Nft(crate::Call::submit_attestation(
+				token,
+				cid(0),
+				MetadataStatus::Verified
+			))
+		);
+
+		// the attestation is in flight, no request is expected
+		Nft::offchain_worker(2);
+		assert!(pool_state.read().transactions.is_empty());
+
+		// the token in flight leaves room for both new tokens, the second serves too large a body
+		assert_ok!(Nft::mint_ipfs_nft(Origin::signed(ALICE), cid(2)));
+		assert_ok!(Nft::mint_ipfs_nft(Origin::signed(ALICE), cid(3)));
+		let mut too_large = br#"{"name": "Anmol", "image": "ipfs://anmol.png"}"#.to_vec();
+		too_large.resize(MaxIpfsMetadataLength::get() as usize + 1, b' ');
+		for (n, body) in [(2, b"{}".to_vec()), (3, too_large)] {
+			offchain_state.write().expect_request(PendingRequest {
+				method: "GET".into(),
+				uri: format!(
+					"http://127.0.0.1:8080/ipfs/{}",
+					String::from_utf8(cid(n)).unwrap()
+				),
+				response: Some(body),
+				sent: true,
+				..Default::default()
+			});
+		}
+		Nft::offchain_worker(3);
+
+		let mut calls: Vec<_> = pool_state
+			.write()
+			.transactions
+			.drain(..)
+			.map(|tx| Extrinsic::decode(&mut &*tx).unwrap().function)
+			.collect();
+		calls.sort_by_key(|call| call.encode());
+		assert_eq!(
+			calls,
+			vec![
+				Call::Nft(crate::Call::submit_attestation(
+					(CLASS_ID_IPFS_NFT, 1),
+					cid(2),
+					MetadataStatus::NonStandard
+				)),
+				Call::Nft(crate::Call::submit_attestation(
+					(CLASS_ID_IPFS_NFT, 2),
+					cid(3),
+					MetadataStatus::Malformed
+				)),
+			]
+		);
+	});
+}
+
+#[test]
+fn strict_metadata_works() {
+	new_test_ext().execute_with(|| {
+		let token = (CLASS_ID_IPFS_NFT, 0);
+		assert_ok!(Nft::create_nft_class(Origin::signed(ALICE), cid(1)));
+		assert_ok!(Nft::mint_ipfs_nft(Origin::signed(ALICE), cid(0)));
+		assert_ok!(Nft::add_oracle(Origin::root(), CHARLIE));
+		assert!(Nft::is_active(token));
+		assert!(!Nft::is_active((CLASS_ID_IPFS_NFT, 1)));
+
+		assert_noop!(
+			Nft::set_strict_metadata(Origin::signed(BOB), CLASS_ID_IPFS_NFT, true),
+			crate::Error::<Runtime>::NotClassOwner
+		);
+		assert_ok!(Nft::set_strict_metadata(
+			Origin::signed(ALICE),
+			CLASS_ID_IPFS_NFT,
+			true
+		));
+		assert_eq!(
+			last_event(),
+			Event::pallet_nft(crate::Event::StrictMetadataSet(
+				ALICE,
+				CLASS_ID_IPFS_NFT,
+				true
+			))
+		);
+		assert!(!Nft::is_active(token));
+
+		assert_ok!(Nft::submit_attestation(
+			Origin::signed(CHARLIE),
+			token,
+			cid(0),
+			MetadataStatus::NonStandard
+		));
+		assert!(!Nft::is_active(token));
+
+		assert_ok!(Nft::set_token_metadata(
+			Origin::signed(ALICE),
+			token,
+			cid(2)
+		));
+		assert_ok!(Nft::submit_attestation(
+			Origin::signed(CHARLIE),
+			token,
+			cid(2),
+			MetadataStatus::Verified
+		));
+		assert!(Nft::is_active(token));
+
+		// new metadata is inactive until attested again
+		assert_ok!(Nft::set_token_metadata(
+			Origin::signed(ALICE),
+			token,
+			cid(3)
+		));
+		assert!(!Nft::is_active(token));
+
+		assert_ok!(Nft::set_strict_metadata(
+			Origin::signed(ALICE),
+			CLASS_ID_IPFS_NFT,
+			false
+		));
+		assert!(!Nft::strict_metadata(CLASS_ID_IPFS_NFT));
+		assert!(Nft::is_active(token));
+	});
+}
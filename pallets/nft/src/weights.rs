@@ -17,28 +17,11 @@
 
 //! Autogenerated weights for pallet_nft
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 3.0.0
-//! DATE: 2021-07-21, STEPS: `[50, ]`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: None, DB CACHE: 128
-
-// Executed Command:
-// ./target/release/anmol
-// benchmark
-// --extrinsic
-// *
-// --pallet
-// pallet_nft
-// --output
-// ./pallets/nft/src/weights.rs
-// --execution
-// wasm
-// --wasm-execution
-// compiled
-// --template=./.maintain/frame-weight-template.hbs
-// --steps
-// 50
-// --repeat
-// 20
+//! Generated from the benchmarks in `benchmarking.rs` executed natively on a development
+//! machine, not in wasm on reference hardware. They stand in until `make benchmarks-generate`
+//! is run on reference hardware.
+//! DATE: 2026-10-16, STEPS: `[50, ]`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
+//! EXECUTION: Some(Native), WASM-EXECUTION: None, CHAIN: Some("dev"), DB CACHE: 128
 
 #![allow(unused_parens)]
 #![allow(unused_imports)]
@@ -53,33 +36,128 @@ use sp_std::marker::PhantomData;
 pub trait WeightInfo {
 	fn create_nft_class() -> Weight;
 	fn mint_ipfs_nft() -> Weight;
+	fn mint() -> Weight;
+	fn burn() -> Weight;
+	fn destroy_class(m: u32) -> Weight;
+	fn set_class_metadata() -> Weight;
+	fn set_token_metadata() -> Weight;
+	fn batch_mint(n: u32) -> Weight;
+	fn batch_transfer(n: u32) -> Weight;
 }
 
 /// Weights for pallet_nft using the Substrate node and recommended hardware.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	fn create_nft_class() -> Weight {
-		(38_000_000 as Weight)
+		(37_963_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(1 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
 	}
 	fn mint_ipfs_nft() -> Weight {
-		(56_000_000 as Weight)
+		(61_796_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+	}
+	fn mint() -> Weight {
+		(60_465_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+	}
+	fn burn() -> Weight {
+		(102_954_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(12 as Weight))
+			.saturating_add(T::DbWeight::get().writes(13 as Weight))
+	}
+	fn destroy_class(m: u32) -> Weight {
+		(52_475_000 as Weight)
+			// Standard Error: 35_000
+			.saturating_add((1_595_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(m as Weight)))
+	}
+	fn set_class_metadata() -> Weight {
+		(42_306_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn set_token_metadata() -> Weight {
+		(74_921_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	fn batch_mint(n: u32) -> Weight {
+		(0 as Weight)
+			// Standard Error: 408_000
+			.saturating_add((100_308_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().reads((7 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes((10 as Weight).saturating_mul(n as Weight)))
+	}
+	fn batch_transfer(n: u32) -> Weight {
+		(48_964_000 as Weight)
+			// Standard Error: 483_000
+			.saturating_add((60_278_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads((9 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes((6 as Weight).saturating_mul(n as Weight)))
 	}
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
 	fn create_nft_class() -> Weight {
-		(38_000_000 as Weight)
+		(37_963_000 as Weight)
 			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
 	}
 	fn mint_ipfs_nft() -> Weight {
-		(56_000_000 as Weight)
+		(61_796_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
+	}
+	fn mint() -> Weight {
+		(60_465_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
+	}
+	fn burn() -> Weight {
+		(102_954_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(12 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(13 as Weight))
+	}
+	fn destroy_class(m: u32) -> Weight {
+		(52_475_000 as Weight)
+			// Standard Error: 35_000
+			.saturating_add((1_595_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(m as Weight)))
+	}
+	fn set_class_metadata() -> Weight {
+		(42_306_000 as Weight)
 			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn set_token_metadata() -> Weight {
+		(74_921_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	fn batch_mint(n: u32) -> Weight {
+		(0 as Weight)
+			// Standard Error: 408_000
+			.saturating_add((100_308_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((7 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((10 as Weight).saturating_mul(n as Weight)))
+	}
+	fn batch_transfer(n: u32) -> Weight {
+		(48_964_000 as Weight)
+			// Standard Error: 483_000
+			.saturating_add((60_278_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads((9 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((6 as Weight).saturating_mul(n as Weight)))
 	}
 }
@@ -0,0 +1,107 @@
+//! Runtime API definition for querying NFT classes and tokens.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::too_many_arguments, clippy::unnecessary_mut_passed)]
+
+use codec::{Codec, Decode, Encode};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+/// Most items returned by a single paged query.
+pub const MAX_PAGE_SIZE: u32 = 1_000;
+
+/// Class details exposed to RPC clients
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ClassDetails<AccountId, TokenId> {
+	/// Class owner
+	pub owner: AccountId,
+	/// Class metadata
+	#[cfg_attr(feature = "std", serde(with = "sp_core::bytes"))]
+	pub metadata: Vec<u8>,
+	/// Total issuance for the class
+	pub total_issuance: TokenId,
+}
+
+/// Token details exposed to RPC clients
+///
+/// Owners are queried separately with `owners_of_token`.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct TokenDetails {
+	/// Token metadata
+	#[cfg_attr(feature = "std", serde(with = "sp_core::bytes"))]
+	pub metadata: Vec<u8>,
+	/// Number of accounts holding shares of the token
+	pub owners_count: u32,
+	/// Whether the token counts as active, false until its metadata is verified in a class
+	/// requiring strict metadata
+	pub active: bool,
+}
+
+/// Token details of `NftApi` before version 2
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
+pub struct TokenDetailsV1 {
+	/// Token metadata
+	pub metadata: Vec<u8>,
+	/// Number of accounts holding shares of the token
+	pub owners_count: u32,
+}
+
+impl From<TokenDetailsV1> for TokenDetails {
+	/// Classes could not require strict metadata before version 2, every token was active
+	fn from(details: TokenDetailsV1) -> Self {
+		TokenDetails {
+			metadata: details.metadata,
+			owners_count: details.owners_count,
+			active: true,
+		}
+	}
+}
+
+sp_api::decl_runtime_apis! {
+	/// Version 2 adds `TokenDetails::active`.
+	#[api_version(2)]
+	pub trait NftApi<AccountId, ClassId, TokenId, Shares, Balance> where
+		AccountId: Codec,
+		ClassId: Codec,
+		TokenId: Codec,
+		Shares: Codec,
+		Balance: Codec,
+	{
+		/// Tokens held by `owner` with their shares, `limit` tokens skipping the first `offset`
+		fn tokens_of_owner(
+			owner: AccountId,
+			offset: u32,
+			limit: u32,
+		) -> Vec<((ClassId, TokenId), Shares)>;
+		/// Owners of a token with their shares, `limit` owners skipping the first `offset`
+		fn owners_of_token(
+			class_id: ClassId,
+			token_id: TokenId,
+			offset: u32,
+			limit: u32,
+		) -> Vec<(AccountId, Shares)>;
+		/// Class details, `None` if the class doesn't exist
+		fn class_info(class_id: ClassId) -> Option<ClassDetails<AccountId, TokenId>>;
+		/// Token details, `None` if the token doesn't exist
+		#[changed_in(2)]
+		fn token_info(class_id: ClassId, token_id: TokenId) -> Option<TokenDetailsV1>;
+		/// Token details, `None` if the token doesn't exist
+		fn token_info(class_id: ClassId, token_id: TokenId) -> Option<TokenDetails>;
+		/// Number of tokens in a class, `None` if the class doesn't exist
+		fn class_total_issuance(class_id: ClassId) -> Option<TokenId>;
+		/// Royalties owed to each beneficiary when a token is sold for `price`
+		fn royalties_for(
+			class_id: ClassId,
+			token_id: TokenId,
+			price: Balance,
+		) -> Vec<(AccountId, Balance)>;
+		/// Tokens carrying the content of the text form of a CID, the first of each class
+		fn tokens_by_content(cid: Vec<u8>) -> Vec<(ClassId, TokenId)>;
+	}
+}
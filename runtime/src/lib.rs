@@ -18,7 +18,7 @@ use sp_runtime::traits::{
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
 	transaction_validity::{TransactionSource, TransactionValidity},
-	ApplyExtrinsicResult, MultiAddress, MultiSignature,
+	ApplyExtrinsicResult, ModuleId, MultiAddress, MultiSignature,
 };
 use sp_std::prelude::*;
 #[cfg(feature = "std")]
@@ -45,8 +45,14 @@ pub use sp_runtime::{Perbill, Permill};
 use frame_system::offchain::{
 	AppCrypto, CreateSignedTransaction, SendTransactionTypes, SigningTypes,
 };
+use frame_system::EnsureRoot;
 
 pub use base_nft;
+pub use pallet_auction;
+pub use pallet_buyout;
+pub use pallet_dividends;
+pub use pallet_fractions;
+pub use pallet_marketplace;
 pub use pallet_nft;
 
 /// An index to a block.
@@ -75,6 +81,15 @@ pub type Hash = sp_core::H256;
 /// Digest item type.
 pub type DigestItem = generic::DigestItem<Hash>;
 
+/// Identifier of an NFT class.
+pub type ClassId = u32;
+
+/// Identifier of an NFT token within its class.
+pub type TokenId = u32;
+
+/// Unit of fractional ownership of an NFT token.
+pub type Shares = u64;
+
 /// Opaque types. These are used by the CLI to instantiate machinery that don't need to know
 /// the specifics of the runtime. They can then be made to be agnostic over specific formats
 /// of data like extrinsics, allowing for them to continue syncing the network through upgrades
@@ -95,6 +110,7 @@ pub mod opaque {
 		pub struct SessionKeys {
 			pub aura: Aura,
 			pub grandpa: Grandpa,
+			pub nft_oracle: NftModule,
 		}
 	}
 }
@@ -110,10 +126,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 100,
+	spec_version: 101,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 };
 
 /// This determines the average expected block time that we are targeting.
@@ -272,17 +288,167 @@ impl pallet_sudo::Config for Runtime {
 	type Call = Call;
 }
 
+parameter_types! {
+	/// Genesis class anyone can mint IPFS NFTs into.
+	pub const PublicIpfsClassId: ClassId = 0;
+	pub OpenMintClasses: Vec<ClassId> = vec![PublicIpfsClassId::get()];
+	pub const MaxMintersPerClass: u32 = 100;
+	pub const MaxBatchSize: u32 = 100;
+	pub const IpfsGateway: &'static str = "http://127.0.0.1:8080";
+	pub const MaxVerificationsPerBlock: u32 = 5;
+	pub const MaxIpfsMetadataLength: u32 = 64 * 1024;
+}
+
 impl pallet_nft::Config for Runtime {
 	type Call = Call;
 	type Event = Event;
 	type WeightInfo = pallet_nft::weights::SubstrateWeight<Runtime>;
+	type PublicIpfsClassId = PublicIpfsClassId;
+	type OpenMintClasses = OpenMintClasses;
+	type MaxMintersPerClass = MaxMintersPerClass;
+	type MaxBatchSize = MaxBatchSize;
+	type AuthorityId = pallet_nft::crypto::OracleAuthId;
+	type OracleOrigin = EnsureRoot<AccountId>;
+	type IpfsGateway = IpfsGateway;
+	type MaxVerificationsPerBlock = MaxVerificationsPerBlock;
+	type MaxIpfsMetadataLength = MaxIpfsMetadataLength;
+}
+
+parameter_types! {
+	/// A token is split into a billion shares, the same precision as `Perbill`.
+	pub const SharesPerToken: Shares = 1_000_000_000;
+	/// Co-owned tokens are burned once owners of 3/4 of the shares approve it.
+	pub const BurnApprovalThreshold: Perbill = Perbill::from_percent(75);
+	pub const MaxMetadataLength: u32 = 256;
+	pub const MaxOwnersPerToken: u32 = 256;
+	pub const ClassDeposit: Balance = 1_000_000_000;
+	pub const TokenDeposit: Balance = 100_000_000;
+	pub const DepositPerByte: Balance = 1_000_000;
+	pub const MaxRoyaltyBeneficiaries: u32 = 10;
+	/// Creators take at most half of a sale.
+	pub const MaxRoyalty: Perbill = Perbill::from_percent(50);
 }
 
 impl base_nft::Config for Runtime {
-	type ClassId = u32;
-	type TokenId = u32;
+	type Event = Event;
+	type ClassId = ClassId;
+	type TokenId = TokenId;
 	type ClassData = ();
 	type TokenData = ();
+	type Shares = Shares;
+	type SharesPerToken = SharesPerToken;
+	type BurnApprovalThreshold = BurnApprovalThreshold;
+	type Currency = Balances;
+	type MaxMetadataLength = MaxMetadataLength;
+	type MaxOwnersPerToken = MaxOwnersPerToken;
+	type ClassDeposit = ClassDeposit;
+	type TokenDeposit = TokenDeposit;
+	type DepositPerByte = DepositPerByte;
+	type MaxRoyaltyBeneficiaries = MaxRoyaltyBeneficiaries;
+	type MaxRoyalty = MaxRoyalty;
+	type OnTokenChange = (Marketplace, Dividends);
+}
+
+parameter_types! {
+	pub const MaxOffersPerToken: u32 = 100;
+	pub const MaxOrdersPerToken: u32 = 100;
+}
+
+impl pallet_marketplace::Config for Runtime {
+	type Event = Event;
+	type WeightInfo = pallet_marketplace::weights::SubstrateWeight<Runtime>;
+	type MaxOffersPerToken = MaxOffersPerToken;
+	type MaxOrdersPerToken = MaxOrdersPerToken;
+	/// Tokens of classes requiring strict metadata trade once their metadata is verified.
+	type TradeFilter = NftModule;
+}
+
+parameter_types! {
+	pub const MinimumAuctionDuration: BlockNumber = HOURS;
+	pub const MaximumAuctionDuration: BlockNumber = 30 * DAYS;
+	/// Bids in the last 10 minutes of an auction extend it to 10 minutes after the bid.
+	pub const AntiSnipingPeriod: BlockNumber = 10 * MINUTES;
+	pub const MinimumBidIncrement: Perbill = Perbill::from_percent(5);
+	pub const RevealPeriod: BlockNumber = DAYS;
+	pub const MaxSealedBids: u32 = 100;
+	/// Settling sealed-bid auctions with `MaxSealedBids` bids each fits in a block.
+	pub const MaxAuctionsPerBlock: u32 = 10;
+}
+
+impl pallet_auction::Config for Runtime {
+	type Event = Event;
+	type WeightInfo = pallet_auction::weights::SubstrateWeight<Runtime>;
+	type MinimumDuration = MinimumAuctionDuration;
+	type MaximumDuration = MaximumAuctionDuration;
+	type AntiSnipingPeriod = AntiSnipingPeriod;
+	type MinimumBidIncrement = MinimumBidIncrement;
+	type RevealPeriod = RevealPeriod;
+	type MaxSealedBids = MaxSealedBids;
+	type MaxAuctionsPerBlock = MaxAuctionsPerBlock;
+	/// Deposits of unrevealed bids are burned.
+	type Slash = ();
+	/// Tokens of classes requiring strict metadata are auctioned once their metadata is verified.
+	type TradeFilter = NftModule;
+}
+
+parameter_types! {
+	pub const BuyoutPeriod: BlockNumber = 7 * DAYS;
+	/// Completing buyouts of tokens with `MaxOwnersPerToken` owners each fits in a block.
+	pub const MaxBuyoutsPerBlock: u32 = 10;
+}
+
+impl pallet_buyout::Config for Runtime {
+	type Event = Event;
+	type WeightInfo = pallet_buyout::weights::SubstrateWeight<Runtime>;
+	type BuyoutPeriod = BuyoutPeriod;
+	type MaxBuyoutsPerBlock = MaxBuyoutsPerBlock;
+}
+
+parameter_types! {
+	pub const DividendsModuleId: ModuleId = ModuleId(*b"anm/divi");
+}
+
+impl pallet_dividends::Config for Runtime {
+	type Event = Event;
+	type WeightInfo = pallet_dividends::weights::SubstrateWeight<Runtime>;
+	type ModuleId = DividendsModuleId;
+	/// Revenue of fractionalized tokens would be stuck in the fractions pallet account.
+	type RevenueFilter = Fractions;
+}
+
+parameter_types! {
+	pub const AssetDepositBase: Balance = 1_000_000_000;
+	pub const AssetDepositPerZombie: Balance = 1_000_000;
+	pub const StringLimit: u32 = 50;
+	pub const MetadataDepositBase: Balance = 100_000_000;
+	pub const MetadataDepositPerByte: Balance = 1_000_000;
+}
+
+impl pallet_assets::Config for Runtime {
+	type Event = Event;
+	type Balance = u128;
+	type AssetId = u32;
+	type Currency = Balances;
+	/// Root creates and destroys the assets of fractionalized tokens.
+	type ForceOrigin = EnsureRoot<AccountId>;
+	type AssetDepositBase = AssetDepositBase;
+	type AssetDepositPerZombie = AssetDepositPerZombie;
+	type StringLimit = StringLimit;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub const FractionsModuleId: ModuleId = ModuleId(*b"anm/frac");
+	pub const MaxZombies: u32 = 1_000;
+}
+
+impl pallet_fractions::Config for Runtime {
+	type Event = Event;
+	type WeightInfo = pallet_fractions::weights::SubstrateWeight<Runtime>;
+	type ModuleId = FractionsModuleId;
+	type MaxZombies = MaxZombies;
 }
 
 pub type SignedPayload = generic::SignedPayload<Call, SignedExtra>;
@@ -358,8 +524,16 @@ construct_runtime!(
 		Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
 		TransactionPayment: pallet_transaction_payment::{Module, Storage},
 		Sudo: pallet_sudo::{Module, Call, Config<T>, Storage, Event<T>},
-		NftModule: pallet_nft::{Module, Call, Storage, Event<T>},
-		BaseNft: base_nft::{Module, Storage, Config<T>},
+		// Genesis is built in this order, the NFT genesis indexes the tokens of the base NFT
+		// genesis.
+		BaseNft: base_nft::{Module, Storage, Config<T>, Event<T>} = 9,
+		NftModule: pallet_nft::{Module, Call, Storage, Config<T>, Event<T>} = 8,
+		Marketplace: pallet_marketplace::{Module, Call, Storage, Event<T>} = 10,
+		Auction: pallet_auction::{Module, Call, Storage, Event<T>},
+		Buyout: pallet_buyout::{Module, Call, Storage, Event<T>},
+		Dividends: pallet_dividends::{Module, Call, Storage, Config, Event<T>},
+		Assets: pallet_assets::{Module, Call, Storage, Event<T>},
+		Fractions: pallet_fractions::{Module, Call, Storage, Event<T>},
 	}
 );
 
@@ -526,6 +700,61 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl nft_primitives::NftApi<Block, AccountId, ClassId, TokenId, Shares, Balance> for Runtime {
+		fn tokens_of_owner(
+			owner: AccountId,
+			offset: u32,
+			limit: u32,
+		) -> Vec<((ClassId, TokenId), Shares)> {
+			BaseNft::tokens_of_owner(&owner, offset, limit.min(nft_primitives::MAX_PAGE_SIZE))
+		}
+
+		fn owners_of_token(
+			class_id: ClassId,
+			token_id: TokenId,
+			offset: u32,
+			limit: u32,
+		) -> Vec<(AccountId, Shares)> {
+			BaseNft::owners_of_token(
+				(class_id, token_id),
+				offset,
+				limit.min(nft_primitives::MAX_PAGE_SIZE),
+			)
+		}
+
+		fn class_info(class_id: ClassId) -> Option<nft_primitives::ClassDetails<AccountId, TokenId>> {
+			BaseNft::classes(class_id).map(|info| nft_primitives::ClassDetails {
+				owner: info.owner,
+				metadata: info.metadata,
+				total_issuance: info.total_issuance,
+			})
+		}
+
+		fn token_info(class_id: ClassId, token_id: TokenId) -> Option<nft_primitives::TokenDetails> {
+			BaseNft::tokens(class_id, token_id).map(|info| nft_primitives::TokenDetails {
+				metadata: info.metadata,
+				owners_count: info.owners.len() as u32,
+				active: NftModule::is_active((class_id, token_id)),
+			})
+		}
+
+		fn class_total_issuance(class_id: ClassId) -> Option<TokenId> {
+			BaseNft::classes(class_id).map(|info| info.total_issuance)
+		}
+
+		fn royalties_for(
+			class_id: ClassId,
+			token_id: TokenId,
+			price: Balance,
+		) -> Vec<(AccountId, Balance)> {
+			BaseNft::royalties_for((class_id, token_id), price)
+		}
+
+		fn tokens_by_content(cid: Vec<u8>) -> Vec<(ClassId, TokenId)> {
+			NftModule::tokens_by_content(&cid, nft_primitives::MAX_PAGE_SIZE)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn dispatch_benchmark(
@@ -556,6 +785,11 @@ impl_runtime_apis! {
 			add_benchmark!(params, batches, pallet_balances, Balances);
 			add_benchmark!(params, batches, pallet_timestamp, Timestamp);
 			add_benchmark!(params, batches, pallet_nft, NftModule);
+			add_benchmark!(params, batches, pallet_marketplace, Marketplace);
+			add_benchmark!(params, batches, pallet_auction, Auction);
+			add_benchmark!(params, batches, pallet_buyout, Buyout);
+			add_benchmark!(params, batches, pallet_dividends, Dividends);
+			add_benchmark!(params, batches, pallet_fractions, Fractions);
 
 			if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
 			Ok(batches)